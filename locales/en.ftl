@@ -0,0 +1,424 @@
+# Common
+
+done = Done
+error = Error
+choose = Choose:
+cancel-button = Cancel
+back-button = ↩ Back
+delete-button = Delete
+pause-button = Pause
+resume-button = Resume
+previous-page-button = ← Back
+next-page-button = Next →
+join-with-and = { $rest } and { $last }
+unknown-callback =
+    Unhandled button:
+    "{ $data }"
+unsupported-message = I understand text, receipt photos and import files, so I'll skip this one
+no-action-selected = No action selected. Open /menu or type it in one line, for example "lent alice 50 for pizza"
+direction-gave = Lent
+direction-took = Borrowed
+
+# Menu
+
+menu-prompt = Choose an action:
+menu-debts = Debts
+menu-summary = Summary
+menu-add-contact = Add contact
+menu-edit-contact = Edit contact
+menu-delete-contact = Delete contact
+menu-recurring = Recurring
+menu-settings = Settings
+
+# Contacts
+
+choose-contact = Choose a contact:
+contacts-empty = You have no contacts yet. Add the first one to start recording debts
+contacts-page = Page { $page } of { $pages }
+contacts-sort-recent-button = Sort: recent first
+contacts-sort-balance-button = Sort: biggest debt first
+contacts-search-button = 🔍 Search
+contacts-search-clear-button = ✖ Clear search
+contacts-search-prompt = Send part of a name or @username
+contacts-search-active = Search: «{ $query }»
+contacts-search-empty = No contacts match «{ $query }»
+contacts-list =
+    Your contacts:
+    { $contacts }
+new-contact-username-prompt = Send the Telegram username of the new contact
+new-contact-name-prompt = Send the name you want to give this contact
+new-contact-not-found =
+    User not found
+    Most likely they haven't started the bot yet or the name has a typo
+    Send it again or go to /menu
+edit-contact-prompt = Send the new name or go back to /menu to cancel
+gave-button = I lent
+took-button = I borrowed
+history-button = History
+settled-button = Settled up
+nudge-button = Remind
+recurring-button = Recurring payment
+statement-pdf-button = PDF statement
+
+# Transactions
+
+amount-prompt = Send the amount, you can also write it as "120/3" or "45+12.5", or go back to /menu to cancel
+amount-error-empty = Send the amount, for example "12.50" or "1,000"
+amount-error-invalid = Couldn't read the amount, send it as "12.50", "1,000" or "12.5 zł"
+amount-error-not-positive = The amount must be greater than zero
+amount-error-too-large = That's too much, the limit is { $max }
+amount-error-too-precise = The amount can have at most two decimal places
+amount-error-division-by-zero = Can't divide by zero
+amount-computed =
+    { $expression } = { $amount }
+    Save this amount?
+amount-confirm-button = Save
+amount-confirm-expired = This amount has already been saved or cancelled
+transaction-saved = Saved.
+tags-prompt = Pick tags (food, travel, rent…) or send the name of a new one, then tap "Done"
+tag-name-invalid = A tag name must be 1 to { $max } characters long
+due-date-prompt = Send the due date as DD.MM.YYYY or tap "No due date"
+no-due-date-button = No due date
+due-date-saved = Done, I'll remind you about the due date
+due-date-invalid = Couldn't read the date, send it as DD.MM.YYYY (today or later)
+history = History:
+history-empty = No records yet
+receipt-button = Receipt
+attachment-missing = This record has no receipt attached
+photo-unexpected = You can send a receipt photo instead of the amount while recording a debt via /menu
+photo-caption-missing = Add a caption with the amount and a comment, for example "45.50 pizza"
+interest-button = Interest
+tags-button = Tags
+transaction-deleted = Record deleted
+transaction-delete-failed = Record not found or already deleted
+transaction-due-date = Due by { $date }
+transaction-interest =
+    Principal: { $principal }
+    Interest ({ $condition }): { $interest }
+transaction-tags = Tags: { $tags }
+transaction-audit = Change log:
+audit-insert = created
+audit-update = changed
+audit-delete = deleted
+
+# Quick entry
+
+quick-entry-confirm =
+    Save this?
+    { $entry }
+quick-entry-saved = Saved: { $entry }
+quick-entry-cancelled = Not saved
+quick-entry-changed = The message was edited after the question. Send the entry again
+quick-entry-lent = { $name }: you lent { $amount }
+quick-entry-borrowed = { $name }: you borrowed { $amount }
+quick-entry-contact-not-found = Couldn't find the contact "{ $name }". Check the name or add the contact via /menu
+quick-entry-contact-ambiguous = "{ $name }" matches several contacts: { $names }. Be more specific
+
+# Commands
+
+command-start = Start using the bot
+command-debts = Debt summary
+command-contacts = Contact list
+command-menu = Main menu
+command-settings = Settings
+command-export = Export records to CSV
+command-import = Import records from CSV or JSON
+command-backup = Account backup
+command-restore = Restore from a backup
+command-deleteme = Delete my data
+command-chart = Balance chart
+command-stats = Stats
+command-find = Search records
+command-gave = Record that I lent: contact amount [comment]
+command-took = Record that I borrowed: contact amount [comment]
+command-balance = Balance with a contact
+command-history = History with a contact
+command-add-contact = Add a contact: @username [name]
+command-rename = Rename a contact: old new
+usage-gave = Usage: /gave <contact> <amount> [comment], for example /gave alice 50 pizza
+usage-took = Usage: /took <contact> <amount> [comment], for example /took alice 120/3 taxi
+usage-balance = Usage: /balance <contact>
+usage-history = Usage: /history <contact>
+usage-add-contact = Usage: /addcontact @username [name]
+usage-rename = Usage: /rename <old name> <new name>
+balance-owed-to-you = { $name } owes you { $amount } { $currency }
+balance-you-owe = You owe { $name } { $amount } { $currency }
+balance-settled = You and { $name } are settled up
+contact-added = Contact { $name } added
+contact-not-registered = User @{ $username } not found: most likely they haven't started the bot yet
+contact-renamed = Done: { $old } is now { $new }
+
+# Inline mode
+
+inline-record-button = Record a debt with { $name }
+inline-start-button = Open the bot to get started
+inline-balance-owes = @{ $debtor } owes @{ $creditor } { $amount } { $currency }
+inline-balance-settled = @{ $user } and @{ $contact } are settled up
+
+# Interest
+
+interest-prompt =
+    Send the terms:
+    "5%" — simple annual interest
+    "5% compound" — compound, capitalized daily
+    "fee 50" — fixed late fee after the due date
+    "none" — no interest
+
+    Or go back to /menu to cancel
+interest-rule-invalid = Couldn't read the terms, send for example "5%", "5% compound", "fee 50" or "none"
+interest-simple = { $rate }% per year
+interest-compound = { $rate }% per year, compound
+interest-late-fee = late fee { $fee }
+late-fee-without-due-date = Done. The debt has no due date, so no late fee will be charged
+
+# Summary and tags
+
+summary-empty = No debts
+summary-tag-header = Summary for tag #{ $tag }
+summary-line-with-interest = { $name }: { $amount } (+ interest { $interest })
+summary-all-button = All
+summary-by-tag-button = By tag
+tag-summary-empty = No tagged debts yet
+tag-summary =
+    Summary by tag
+
+    { $lines }
+tag-summary-line = #{ $tag }: lent { $lent }, borrowed { $borrowed } { $currency }
+
+# Reminders to a contact
+
+nudge-nothing-owed = This contact doesn't owe you anything
+nudge-muted-by-contact = The contact has muted your reminders
+nudge-cooldown = You can remind again after { $date } UTC
+nudge-due-date = due { $date }
+nudge-text =
+    Hi! { $name } is reminding you about a debt.
+    According to the bot you owe { $balance }.
+
+    Open records:
+    { $items }
+nudge-more-items = …and { $count } more
+nudge-mute-button = Mute reminders from { $name }
+nudge-sent = Reminder sent
+nudges-muted = I won't forward reminders from { $name } anymore
+nudges-unmuted = Reminders from { $name } are back on
+nudges-unmute-button = Unmute
+
+# Recurring payments
+
+recurring-direction = Who pays?
+recurring-gave-button = I lend
+recurring-took-button = I borrow
+recurring-prompt =
+    Send the amount, the day and a comment:
+    "40 5 Netflix" — every month on the 5th
+    "40 mon Gym" — every Monday
+
+    Or go back to /menu to cancel
+recurring-input-invalid = Couldn't read that. Send the amount, the day and a comment, for example "40 5 Netflix" or "40 mon Gym"
+recurring-created =
+    Done: { $recurring }
+    First payment { $next_run } UTC
+recurring-view =
+    { $recurring }
+    Next payment { $next_run } UTC
+recurring-list = Recurring payments:
+recurring-list-empty = No recurring payments. You can set one up from a contact card under "Debts"
+recurring-gives = { $name }: I lend { $amount } { $schedule }
+recurring-takes = { $name }: I borrow { $amount } { $schedule }
+recurring-monthly = every month on day { $day }
+recurring-weekly =
+    { $weekday ->
+        [1] every Monday
+        [2] every Tuesday
+        [3] every Wednesday
+        [4] every Thursday
+        [5] every Friday
+        [6] every Saturday
+       *[7] every Sunday
+    }
+recurring-paused = paused
+recurring-run-lender = Recurring payment: { $name } owes you { $amount }{ $comment }
+recurring-run-borrower = Recurring payment: you owe { $name } { $amount }{ $comment }
+
+# Due date reminders
+
+reminder-borrower-upcoming = Reminder: { $date } is the due date for your debt of { $amount } to { $name }
+reminder-borrower-overdue = Your debt of { $amount } to { $name } is overdue, it was due { $date }
+reminder-lender-upcoming = Reminder: { $name } has to pay you back { $amount } by { $date }
+reminder-lender-overdue = { $name } is late paying back { $amount }, it was due { $date }
+
+# Settings
+
+settings-text =
+    Settings
+
+    Debt reminders: { $frequency }
+    Quiet hours: { $quiet_hours }
+    Monthly statement: { $statement }
+    Language: { $language }
+settings-reminders-off = off
+settings-reminders-daily = daily
+settings-reminders-weekly = weekly
+settings-quiet-hours-none = none
+settings-statement-on = on
+settings-statement-off = off
+settings-language-auto = same as Telegram ({ $language })
+reminders-off-button = Off
+reminders-daily-button = Daily
+reminders-weekly-button = Weekly
+quiet-hours-off-button = No quiet hours
+statement-on-button = Statement: on
+statement-off-button = Statement: off
+language-auto-button = Telegram language
+
+# Export, import, backups
+
+export-usage = Usage: /export [contact] [since:YYYY-MM-DD] [until:YYYY-MM-DD]. The until day itself is not included
+export-failed = Export failed: check the contact name
+import-prompt = Send a CSV or JSON file with columns date, counterparty, direction (lent/borrowed), amount, comment — the same format /export produces
+waiting-import-file = Waiting for a CSV or JSON file, or go back to /menu to cancel
+document-unexpected = To upload a file, use /import to import debts or /restore to restore a backup
+file-too-large = The file is too large, 1 MB max
+file-unreadable = Couldn't read the file: { $error }
+import-rows =
+    { $count } { $count ->
+        [one] row
+       *[other] rows
+    }
+import-skipped = , deleted skipped: { $count }
+import-no-errors = , no errors
+import-errors =
+    , { $count } { $count ->
+        [one] error
+       *[other] errors
+    } in { $lines_count ->
+        [one] row
+       *[other] rows
+    } { $lines }
+import-error-line = Row { $line }: { $message }
+import-error-too-many-rows = too many rows, { $max } max
+import-error-csv-header = couldn't read the CSV header
+import-error-csv-row = CSV error in row { $line }
+import-error-not-json = expected a JSON array of objects
+import-error-no-counterparty = no counterparty
+import-error-no-direction = no direction
+import-error-direction = unknown direction "{ $direction }"
+import-error-no-amount = no amount
+import-error-not-positive = the amount must be greater than zero
+import-error-amount = couldn't read the amount "{ $amount }"
+import-error-date = couldn't read the date "{ $date }"
+import-error-no-contact = no contact "{ $name }"
+import-nothing = Nothing to import
+import-confirm-button = Import { $count }
+import-applied = Records imported: { $count }
+import-failed = Import failed, nothing was changed
+import-undo-button = Undo import
+import-cancelled = Import cancelled
+import-reverted = Import undone, records removed: { $count }
+import-revert-failed = Import not found or already undone
+import-revert-button = Undo import from { $date }
+backup-caption = Account backup. You can load it back with /restore
+restore-prompt = Send a backup file from /backup or go back to /menu to cancel
+waiting-backup-file = Waiting for a backup file, or go back to /menu to cancel
+backup-restored = Backup restored: contacts { $contacts }, records { $transactions }, already present { $duplicates }, skipped { $skipped } (people who aren't in the bot or in your contacts)
+backup-wrong-owner = This backup belongs to another account, it can only be restored by its owner
+backup-restore-failed = Couldn't restore the backup, nothing was changed
+backup-error-not-backup = this isn't a grosze backup
+backup-error-version = backup version { $version } is newer than the supported { $supported }, update the bot
+backup-error-direction = unknown direction "{ $direction }"
+backup-error-interest-kind = unknown interest kind "{ $kind }"
+backup-error-amount = amount { $amount } is out of range or finer than a cent
+backup-error-interest-rate = interest rate { $rate }% is out of range
+backup-error-reminder-frequency = unknown reminder frequency "{ $frequency }"
+
+# Account deletion
+
+delete-account-prompt =
+    Delete your account?
+
+    Your username, contacts, reminders, recurring payments and settings will be deleted. Debt records stay with your contacts, but you will appear in them as "deleted user". Deletion can't be undone, so you may want to save a /backup first
+delete-account-confirm-button = Yes, delete my data
+account-deleted = Your data has been deleted. To use the bot again, send /start
+account-delete-cancelled = Deletion cancelled
+start-required = To use the bot, send /start
+deleted-user = deleted user
+
+# Search
+
+find-usage =
+    Couldn't read the query: { $error }
+
+    Examples: /find pizza, /find >100, /find since:2026-01-01 until:2026-02-01, /find @alice pizza <50
+find-contact-not-found = Contact "{ $name }" not found
+find-nothing = Nothing found
+find-results = Found: { $total }, page { $page } of { $pages }
+search-error-empty = the query is empty
+search-error-empty-contact = a contact name is needed after "@"
+search-error-several-contacts = only one contact can be given
+search-error-inverted-dates = since must be earlier than until
+search-error-date = couldn't read the date "{ $value }", the format is YYYY-MM-DD
+search-error-amount = couldn't read the amount in "{ $token }"
+search-error-negative-amount = the amount in "{ $token }" can't be negative
+
+# Stats and charts
+
+stats-period-month = Month
+stats-period-quarter = Quarter
+stats-period-year = Year
+stats-period-all = All time
+stats-title-month = Stats for this month
+stats-title-quarter = Stats for this quarter
+stats-title-year = Stats for this year
+stats-title-all = Stats for all time
+stats-lent = Lent: { $amount } { $currency }
+stats-borrowed = Borrowed: { $amount } { $currency }
+stats-open-debts = Open debts now: { $count }
+stats-average-repay = Average time to repay: { $days } days
+stats-most-frequent = Most frequent: { $name } ({ $count } records)
+stats-largest-lent = Largest debt: { $amount } { $currency } — { $name }, you lent it on { $date }
+stats-largest-borrowed = Largest debt: { $amount } { $currency } — { $name }, you borrowed it on { $date }
+chart-empty = Nothing to draw yet: no transactions
+chart-history-title = Balance with contacts
+chart-balances-title = Who owes whom: plus — owed to you, minus — you owe
+
+# Monthly statement
+
+statement-title =
+    Statement for { $month ->
+        [1] January
+        [2] February
+        [3] March
+        [4] April
+        [5] May
+        [6] June
+        [7] July
+        [8] August
+        [9] September
+        [10] October
+        [11] November
+       *[12] December
+    } { $year }
+statement-line = { $name }: { $opening } → { $closing } (new debts { $new_debts }, repaid { $repaid })
+statement-opening = Opening balance: { $amount } { $currency }
+statement-new-debts = New debts: { $amount } { $currency }
+statement-repaid = Repaid: { $amount } { $currency }
+statement-closing = Closing balance: { $amount } { $currency }
+statement-biggest-changes = Biggest changes:
+statement-footer = Plus — you are owed, minus — you owe. You can turn statements off in /settings
+statement-caption = Statement for last month
+
+# PDF statement for a contact
+
+pdf-title = Statement: { $user } and { $contact }
+pdf-column-date = Date
+pdf-column-operation = Operation
+pdf-column-amount = Amount
+pdf-column-balance = Balance
+pdf-column-comment = Comment
+pdf-total-lent = Total lent: { $amount } { $currency }
+pdf-total-borrowed = Total borrowed: { $amount } { $currency }
+pdf-result-owed-to-you = Result: { $name } owes you { $amount } { $currency }
+pdf-result-you-owe = Result: you owe { $name } { $amount } { $currency }
+pdf-result-settled = Result: no debts
+pdf-signature = Generated by @{ $bot } { $date } UTC
@@ -0,0 +1,426 @@
+# Wspólne
+
+done = Gotowe
+error = Błąd
+choose = Wybierz:
+cancel-button = Anuluj
+back-button = ↩ Wstecz
+delete-button = Usuń
+pause-button = Wstrzymaj
+resume-button = Wznów
+previous-page-button = ← Wstecz
+next-page-button = Dalej →
+join-with-and = { $rest } i { $last }
+unknown-callback =
+    Nieobsłużony przycisk:
+    "{ $data }"
+unsupported-message = Rozumiem tekst, zdjęcia paragonów i pliki importu, tę wiadomość pominę
+no-action-selected = Nie wybrano żadnej akcji. Otwórz /menu albo napisz jednym zdaniem, na przykład „dałem Ani 50 za pizzę”
+direction-gave = Dałem
+direction-took = Wziąłem
+
+# Menu
+
+menu-prompt = Wybierz akcję:
+menu-debts = Długi
+menu-summary = Podsumowanie
+menu-add-contact = Dodaj kontakt
+menu-edit-contact = Edytuj kontakt
+menu-delete-contact = Usuń kontakt
+menu-recurring = Cykliczne
+menu-settings = Ustawienia
+
+# Kontakty
+
+choose-contact = Wybierz kontakt:
+contacts-empty = Nie masz jeszcze kontaktów. Dodaj pierwszy, aby zapisywać długi
+contacts-page = Strona { $page } z { $pages }
+contacts-sort-recent-button = Kolejność: najpierw ostatnie
+contacts-sort-balance-button = Kolejność: najpierw największy dług
+contacts-search-button = 🔍 Szukaj
+contacts-search-clear-button = ✖ Wyczyść wyszukiwanie
+contacts-search-prompt = Wyślij część imienia lub @username
+contacts-search-active = Wyszukiwanie: «{ $query }»
+contacts-search-empty = Żaden kontakt nie pasuje do «{ $query }»
+contacts-list =
+    Twoje kontakty:
+    { $contacts }
+new-contact-username-prompt = Wyślij nazwę użytkownika Telegrama nowego kontaktu
+new-contact-name-prompt = Wyślij nazwę, pod którą chcesz zapisać ten kontakt
+new-contact-not-found =
+    Nie znaleziono użytkownika
+    Najpewniej nie korzysta jeszcze z bota albo w nazwie jest literówka
+    Wyślij ponownie lub przejdź do /menu
+edit-contact-prompt = Wyślij nową nazwę lub wróć do /menu, aby anulować
+gave-button = Pożyczyłem komuś
+took-button = Pożyczyłem od kogoś
+history-button = Historia
+settled-button = Rozliczeni
+nudge-button = Przypomnij
+recurring-button = Płatność cykliczna
+statement-pdf-button = Wyciąg PDF
+
+# Transakcje
+
+amount-prompt = Wyślij kwotę, można też jako „120/3” albo „45+12,5”, lub wróć do /menu, aby anulować
+amount-error-empty = Wyślij kwotę, na przykład „12,50” albo „1 000”
+amount-error-invalid = Nie udało się odczytać kwoty, wyślij ją jako „12,50”, „1 000” albo „12,5 zł”
+amount-error-not-positive = Kwota musi być większa od zera
+amount-error-too-large = To za dużo, limit to { $max }
+amount-error-too-precise = Kwota może mieć najwyżej dwa miejsca po przecinku
+amount-error-division-by-zero = Nie można dzielić przez zero
+amount-computed =
+    { $expression } = { $amount }
+    Zapisać tę kwotę?
+amount-confirm-button = Zapisz
+amount-confirm-expired = Ta kwota została już zapisana albo anulowana
+transaction-saved = Zapisane.
+tags-prompt = Zaznacz tagi (jedzenie, podróże, czynsz…) lub wyślij nazwę nowego, a potem naciśnij „Gotowe”
+tag-name-invalid = Nazwa tagu musi mieć od 1 do { $max } znaków
+due-date-prompt = Wyślij termin zwrotu w formacie DD.MM.RRRR lub naciśnij „Bez terminu”
+no-due-date-button = Bez terminu
+due-date-saved = Gotowe, przypomnę o terminie
+due-date-invalid = Nie rozumiem daty, wyślij ją w formacie DD.MM.RRRR (nie wcześniejszą niż dziś)
+history = Historia:
+history-empty = Na razie brak wpisów
+receipt-button = Paragon
+attachment-missing = Do tego wpisu nie dołączono paragonu
+photo-unexpected = Zdjęcie paragonu możesz wysłać zamiast kwoty, gdy zapisujesz dług przez /menu
+photo-caption-missing = Dodaj do zdjęcia podpis z kwotą i komentarzem, na przykład „45,50 pizza”
+interest-button = Odsetki
+tags-button = Tagi
+transaction-deleted = Wpis usunięty
+transaction-delete-failed = Nie znaleziono wpisu albo został już usunięty
+transaction-due-date = Zwrot do { $date }
+transaction-interest =
+    Kwota główna: { $principal }
+    Odsetki ({ $condition }): { $interest }
+transaction-tags = Tagi: { $tags }
+transaction-audit = Historia zmian:
+audit-insert = utworzono
+audit-update = zmieniono
+audit-delete = usunięto
+
+# Szybki wpis
+
+quick-entry-confirm =
+    Zapisać?
+    { $entry }
+quick-entry-saved = Zapisano: { $entry }
+quick-entry-cancelled = Nie zapisuję
+quick-entry-changed = Wiadomość zmieniono po pytaniu. Wyślij wpis jeszcze raz
+quick-entry-lent = { $name }: pożyczyłeś { $amount }
+quick-entry-borrowed = { $name }: wziąłeś { $amount }
+quick-entry-contact-not-found = Nie znalazłem kontaktu „{ $name }”. Sprawdź imię albo dodaj kontakt przez /menu
+quick-entry-contact-ambiguous = „{ $name }” pasuje do kilku kontaktów: { $names }. Napisz imię dokładniej
+
+# Polecenia
+
+command-start = Zacznij korzystać z bota
+command-debts = Podsumowanie długów
+command-contacts = Lista kontaktów
+command-menu = Menu główne
+command-settings = Ustawienia
+command-export = Eksport wpisów do CSV
+command-import = Import wpisów z CSV lub JSON
+command-backup = Kopia zapasowa konta
+command-restore = Przywróć z kopii zapasowej
+command-deleteme = Usuń moje dane
+command-chart = Wykres sald
+command-stats = Statystyki
+command-find = Szukaj we wpisach
+command-gave = Zapisz, że pożyczyłem: kontakt kwota [komentarz]
+command-took = Zapisz, że wziąłem: kontakt kwota [komentarz]
+command-balance = Saldo z kontaktem
+command-history = Historia z kontaktem
+command-add-contact = Dodaj kontakt: @username [imię]
+command-rename = Zmień nazwę kontaktu: stara nowa
+usage-gave = Użycie: /gave <kontakt> <kwota> [komentarz], na przykład /gave Ania 50 pizza
+usage-took = Użycie: /took <kontakt> <kwota> [komentarz], na przykład /took Ania 120/3 taksówka
+usage-balance = Użycie: /balance <kontakt>
+usage-history = Użycie: /history <kontakt>
+usage-add-contact = Użycie: /addcontact @username [imię]
+usage-rename = Użycie: /rename <stara nazwa> <nowa nazwa>
+balance-owed-to-you = { $name } jest ci winien { $amount } { $currency }
+balance-you-owe = Jesteś winien { $name } { $amount } { $currency }
+balance-settled = Ty i { $name } jesteście rozliczeni
+contact-added = Dodano kontakt { $name }
+contact-not-registered = Nie znaleziono użytkownika @{ $username }: najpewniej jeszcze nie uruchomił bota
+contact-renamed = Gotowe: { $old } to teraz { $new }
+
+# Tryb inline
+
+inline-record-button = Zapisz dług: { $name }
+inline-start-button = Otwórz bota, aby zacząć
+inline-balance-owes = @{ $debtor } jest winien @{ $creditor } { $amount } { $currency }
+inline-balance-settled = @{ $user } i @{ $contact } są rozliczeni
+
+# Odsetki
+
+interest-prompt =
+    Wyślij warunki:
+    „5%” — proste odsetki roczne
+    „5% składane” — składane, z codzienną kapitalizacją
+    „kara 50” — stała kara po terminie zwrotu
+    „nie” — bez odsetek
+
+    Albo wróć do /menu, aby anulować
+interest-rule-invalid = Nie rozumiem warunków, wyślij na przykład „5%”, „5% składane”, „kara 50” lub „nie”
+interest-simple = { $rate }% rocznie
+interest-compound = { $rate }% rocznie, składane
+interest-late-fee = kara { $fee } po terminie
+late-fee-without-due-date = Gotowe. Dług nie ma terminu zwrotu, więc kara nie będzie naliczana
+
+# Podsumowanie i tagi
+
+summary-empty = Brak długów
+summary-tag-header = Podsumowanie dla tagu #{ $tag }
+summary-line-with-interest = { $name }: { $amount } (+ odsetki { $interest })
+summary-all-button = Wszystkie
+summary-by-tag-button = Według tagów
+tag-summary-empty = Nie ma jeszcze długów oznaczonych tagami
+tag-summary =
+    Podsumowanie według tagów
+
+    { $lines }
+tag-summary-line = #{ $tag }: pożyczone komuś { $lent }, pożyczone od kogoś { $borrowed } { $currency }
+
+# Przypomnienia dla kontaktu
+
+nudge-nothing-owed = Ten kontakt nic ci nie jest winien
+nudge-muted-by-contact = Kontakt wyciszył twoje przypomnienia
+nudge-cooldown = Ponownie przypomnieć możesz po { $date } UTC
+nudge-due-date = do { $date }
+nudge-text =
+    Cześć! { $name } przypomina o długu.
+    Według zapisów w bocie jesteś winien { $balance }.
+
+    Otwarte wpisy:
+    { $items }
+nudge-more-items = …i jeszcze { $count }
+nudge-mute-button = Nie otrzymuj przypomnień od { $name }
+nudge-sent = Przypomnienie wysłane
+nudges-muted = Nie będę już przesyłać przypomnień od { $name }
+nudges-unmuted = Przypomnienia od { $name } znów włączone
+nudges-unmute-button = Znów otrzymuj
+
+# Płatności cykliczne
+
+recurring-direction = Kto płaci?
+recurring-gave-button = Ja pożyczam
+recurring-took-button = Ja pożyczam od kogoś
+recurring-prompt =
+    Wyślij kwotę, dzień i komentarz:
+    „40 5 Netflix” — co miesiąc 5. dnia
+    „40 pn Siłownia” — w każdy poniedziałek
+
+    Albo wróć do /menu, aby anulować
+recurring-input-invalid = Nie rozumiem. Wyślij kwotę, dzień i komentarz, na przykład „40 5 Netflix” lub „40 pn Siłownia”
+recurring-created =
+    Gotowe: { $recurring }
+    Pierwsza płatność { $next_run } UTC
+recurring-view =
+    { $recurring }
+    Następna płatność { $next_run } UTC
+recurring-list = Płatności cykliczne:
+recurring-list-empty = Brak płatności cyklicznych. Możesz je dodać z karty kontaktu w sekcji „Długi”
+recurring-gives = { $name }: pożyczam { $amount } { $schedule }
+recurring-takes = { $name }: pożyczam od niego { $amount } { $schedule }
+recurring-monthly = co miesiąc { $day }. dnia
+recurring-weekly =
+    { $weekday ->
+        [1] w każdy poniedziałek
+        [2] w każdy wtorek
+        [3] w każdą środę
+        [4] w każdy czwartek
+        [5] w każdy piątek
+        [6] w każdą sobotę
+       *[7] w każdą niedzielę
+    }
+recurring-paused = wstrzymana
+recurring-run-lender = Płatność cykliczna: { $name } jest ci winien { $amount }{ $comment }
+recurring-run-borrower = Płatność cykliczna: jesteś winien { $name } { $amount }{ $comment }
+
+# Przypomnienia o terminach
+
+reminder-borrower-upcoming = Przypomnienie: { $date } mija termin zwrotu { $amount } dla { $name }
+reminder-borrower-overdue = Dług { $amount } wobec { $name } jest przeterminowany, termin minął { $date }
+reminder-lender-upcoming = Przypomnienie: { $date } { $name } ma ci oddać { $amount }
+reminder-lender-overdue = { $name } spóźnia się ze zwrotem { $amount }, termin minął { $date }
+
+# Ustawienia
+
+settings-text =
+    Ustawienia
+
+    Przypomnienia o długach: { $frequency }
+    Godziny ciszy: { $quiet_hours }
+    Miesięczny wyciąg: { $statement }
+    Język: { $language }
+settings-reminders-off = wyłączone
+settings-reminders-daily = codziennie
+settings-reminders-weekly = co tydzień
+settings-quiet-hours-none = brak
+settings-statement-on = włączony
+settings-statement-off = wyłączony
+settings-language-auto = jak w Telegramie ({ $language })
+reminders-off-button = Wył.
+reminders-daily-button = Codziennie
+reminders-weekly-button = Co tydzień
+quiet-hours-off-button = Bez godzin ciszy
+statement-on-button = Wyciąg: wł.
+statement-off-button = Wyciąg: wył.
+language-auto-button = Język Telegrama
+
+# Eksport, import, kopie zapasowe
+
+export-usage = Użycie: /export [kontakt] [since:RRRR-MM-DD] [until:RRRR-MM-DD]. Sam dzień until nie jest wliczany
+export-failed = Nie udało się wyeksportować: sprawdź nazwę kontaktu
+import-prompt = Wyślij plik CSV lub JSON z kolumnami date, counterparty, direction (lent/borrowed), amount, comment — taki sam, jaki tworzy /export
+waiting-import-file = Czekam na plik CSV lub JSON, albo wróć do /menu, aby anulować
+document-unexpected = Aby wgrać plik, wpisz /import, żeby zaimportować długi, lub /restore, żeby przywrócić kopię
+file-too-large = Plik jest za duży, maksymalnie 1 MB
+file-unreadable = Nie udało się odczytać pliku: { $error }
+import-rows =
+    { $count } { $count ->
+        [one] wiersz
+        [few] wiersze
+       *[many] wierszy
+    }
+import-skipped = , pominięto usuniętych: { $count }
+import-no-errors = , bez błędów
+import-errors =
+    , { $count } { $count ->
+        [one] błąd
+        [few] błędy
+       *[many] błędów
+    } w { $lines_count ->
+        [one] wierszu
+       *[other] wierszach
+    } { $lines }
+import-error-line = Wiersz { $line }: { $message }
+import-error-too-many-rows = za dużo wierszy, maksymalnie { $max }
+import-error-csv-header = nie udało się odczytać nagłówka CSV
+import-error-csv-row = błąd CSV w wierszu { $line }
+import-error-not-json = oczekiwano tablicy obiektów JSON
+import-error-no-counterparty = brak kontrahenta
+import-error-no-direction = brak kierunku
+import-error-direction = nieznany kierunek „{ $direction }”
+import-error-no-amount = brak kwoty
+import-error-not-positive = kwota musi być większa od zera
+import-error-amount = nieczytelna kwota „{ $amount }”
+import-error-date = nieczytelna data „{ $date }”
+import-error-no-contact = brak kontaktu „{ $name }”
+import-nothing = Nie ma nic do zaimportowania
+import-confirm-button = Importuj { $count }
+import-applied = Zaimportowano wpisów: { $count }
+import-failed = Import się nie powiódł, nic nie zmieniono
+import-undo-button = Cofnij import
+import-cancelled = Import anulowany
+import-reverted = Import cofnięty, usunięto wpisów: { $count }
+import-revert-failed = Nie znaleziono importu albo został już cofnięty
+import-revert-button = Cofnij import z { $date }
+backup-caption = Kopia zapasowa konta. Możesz ją wgrać poleceniem /restore
+restore-prompt = Wyślij plik kopii zapasowej z /backup lub wróć do /menu, aby anulować
+waiting-backup-file = Czekam na plik kopii zapasowej, albo wróć do /menu, aby anulować
+backup-restored = Kopia przywrócona: kontaktów { $contacts }, wpisów { $transactions }, już istniało { $duplicates }, pominięto { $skipped } (osoby spoza bota lub twoich kontaktów)
+backup-wrong-owner = Ta kopia należy do innego konta, przywrócić ją może tylko właściciel
+backup-restore-failed = Nie udało się przywrócić kopii, nic nie zmieniono
+backup-error-not-backup = to nie jest kopia zapasowa grosze
+backup-error-version = wersja kopii { $version } jest nowsza niż obsługiwana { $supported }, zaktualizuj bota
+backup-error-direction = nieznany kierunek „{ $direction }”
+backup-error-interest-kind = nieznany rodzaj odsetek „{ $kind }”
+backup-error-amount = kwota { $amount } jest poza zakresem lub dokładniejsza niż grosz
+backup-error-interest-rate = oprocentowanie { $rate }% jest poza zakresem
+backup-error-reminder-frequency = nieznana częstotliwość przypomnień „{ $frequency }”
+
+# Usuwanie konta
+
+delete-account-prompt =
+    Usunąć konto?
+
+    Usunięte zostaną twoja nazwa użytkownika, kontakty, przypomnienia, płatności cykliczne i ustawienia. Wpisy o długach zostaną u twoich kontaktów, ale zamiast ciebie będzie w nich „usunięty użytkownik”. Usunięcia nie można cofnąć, więc w razie potrzeby najpierw zapisz /backup
+delete-account-confirm-button = Tak, usuń moje dane
+account-deleted = Twoje dane zostały usunięte. Aby znów korzystać z bota, wpisz /start
+account-delete-cancelled = Usuwanie anulowane
+start-required = Aby korzystać z bota, wpisz /start
+deleted-user = usunięty użytkownik
+
+# Wyszukiwanie
+
+find-usage =
+    Nie rozumiem zapytania: { $error }
+
+    Przykłady: /find pizza, /find >100, /find since:2026-01-01 until:2026-02-01, /find @alice pizza <50
+find-contact-not-found = Nie znaleziono kontaktu „{ $name }”
+find-nothing = Nic nie znaleziono
+find-results = Znaleziono: { $total }, strona { $page } z { $pages }
+search-error-empty = puste zapytanie
+search-error-empty-contact = po „@” potrzebna jest nazwa kontaktu
+search-error-several-contacts = można podać tylko jeden kontakt
+search-error-inverted-dates = since musi być wcześniej niż until
+search-error-date = nieczytelna data „{ $value }”, format to RRRR-MM-DD
+search-error-amount = nieczytelna kwota w „{ $token }”
+search-error-negative-amount = kwota w „{ $token }” nie może być ujemna
+
+# Statystyki i wykresy
+
+stats-period-month = Miesiąc
+stats-period-quarter = Kwartał
+stats-period-year = Rok
+stats-period-all = Cały okres
+stats-title-month = Statystyki za ten miesiąc
+stats-title-quarter = Statystyki za ten kwartał
+stats-title-year = Statystyki za ten rok
+stats-title-all = Statystyki za cały okres
+stats-lent = Pożyczone komuś: { $amount } { $currency }
+stats-borrowed = Pożyczone od kogoś: { $amount } { $currency }
+stats-open-debts = Otwarte długi teraz: { $count }
+stats-average-repay = Średni czas zwrotu: { $days } dni
+stats-most-frequent = Najczęściej: { $name } ({ $count } wpisów)
+stats-largest-lent = Największy dług: { $amount } { $currency } — { $name }, pożyczyłeś { $date }
+stats-largest-borrowed = Największy dług: { $amount } { $currency } — { $name }, pożyczyłeś od niego { $date }
+chart-empty = Na razie nie ma czego rysować: brak transakcji
+chart-history-title = Saldo z kontaktami
+chart-balances-title = Kto komu jest winien: plus — tobie, minus — ty
+
+# Miesięczny wyciąg
+
+statement-title =
+    Wyciąg za { $month ->
+        [1] styczeń
+        [2] luty
+        [3] marzec
+        [4] kwiecień
+        [5] maj
+        [6] czerwiec
+        [7] lipiec
+        [8] sierpień
+        [9] wrzesień
+        [10] październik
+        [11] listopad
+       *[12] grudzień
+    } { $year }
+statement-line = { $name }: { $opening } → { $closing } (nowe długi { $new_debts }, spłacono { $repaid })
+statement-opening = Saldo na początek: { $amount } { $currency }
+statement-new-debts = Nowe długi: { $amount } { $currency }
+statement-repaid = Spłacono: { $amount } { $currency }
+statement-closing = Saldo na koniec: { $amount } { $currency }
+statement-biggest-changes = Największe zmiany:
+statement-footer = Plus — ktoś jest ci winien, minus — ty jesteś winien. Wyciągi możesz wyłączyć w /settings
+statement-caption = Wyciąg za poprzedni miesiąc
+
+# Wyciąg PDF dla kontaktu
+
+pdf-title = Wyciąg: { $user } i { $contact }
+pdf-column-date = Data
+pdf-column-operation = Operacja
+pdf-column-amount = Kwota
+pdf-column-balance = Saldo
+pdf-column-comment = Komentarz
+pdf-total-lent = Razem pożyczone komuś: { $amount } { $currency }
+pdf-total-borrowed = Razem pożyczone od kogoś: { $amount } { $currency }
+pdf-result-owed-to-you = Wynik: { $name } jest ci winien { $amount } { $currency }
+pdf-result-you-owe = Wynik: jesteś winien { $name } { $amount } { $currency }
+pdf-result-settled = Wynik: brak długów
+pdf-signature = Wygenerowane przez @{ $bot } { $date } UTC
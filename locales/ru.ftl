@@ -0,0 +1,426 @@
+# Общее
+
+done = Готово
+error = Ошибка
+choose = Выбери:
+cancel-button = Отмена
+back-button = ↩ Назад
+delete-button = Удалить
+pause-button = Пауза
+resume-button = Возобновить
+previous-page-button = ← Назад
+next-page-button = Дальше →
+join-with-and = { $rest } и { $last }
+unknown-callback =
+    Необработанное нажатие:
+    "{ $data }"
+unsupported-message = Я понимаю текст, фото чеков и файлы импорта, а это пропущу
+no-action-selected = Никакого действия не выбрано. Зайди в /menu или напиши одной фразой, например «дал Пете 50 за пиццу»
+direction-gave = Дал
+direction-took = Взял
+
+# Меню
+
+menu-prompt = Выбери действие:
+menu-debts = Долги
+menu-summary = Сводка
+menu-add-contact = Добавить контакт
+menu-edit-contact = Редактировать контакт
+menu-delete-contact = Удалить контакт
+menu-recurring = Регулярные
+menu-settings = Настройки
+
+# Контакты
+
+choose-contact = Выбери контакт:
+contacts-empty = Контактов пока нет. Добавь первый, чтобы записывать долги
+contacts-page = Страница { $page } из { $pages }
+contacts-sort-recent-button = Порядок: сначала недавние
+contacts-sort-balance-button = Порядок: сначала крупные долги
+contacts-search-button = 🔍 Поиск
+contacts-search-clear-button = ✖ Сбросить поиск
+contacts-search-prompt = Пришли часть имени или @username
+contacts-search-active = Поиск: «{ $query }»
+contacts-search-empty = Под «{ $query }» не подходит ни один контакт
+contacts-list =
+    Твои контакты:
+    { $contacts }
+new-contact-username-prompt = Пришли telegram username нового контакта
+new-contact-name-prompt = Пришли как ты хочешь подписать этот контакт
+new-contact-not-found =
+    Пользователь не найден
+    Скорее всего он не зарегестирован в боте или ошибка в имени
+    Пришли еще раз или перейди в /menu
+edit-contact-prompt = Пришли новое имя или вернись в /menu для отмены
+gave-button = Дал в долг
+took-button = Взял в долг
+history-button = История
+settled-button = Расчитались
+nudge-button = Напомнить
+recurring-button = Регулярный платёж
+statement-pdf-button = PDF-выписка
+
+# Транзакции
+
+amount-prompt = Пришли сумму, можно выражением вроде «120/3» или «45+12,5», или вернись в /menu для отмены
+amount-error-empty = Пришли сумму, например «12,50» или «1 000»
+amount-error-invalid = Не получилось разобрать сумму, пришли её как «12,50», «1 000» или «12,5 zł»
+amount-error-not-positive = Сумма должна быть больше нуля
+amount-error-too-large = Слишком большая сумма, предел — { $max }
+amount-error-too-precise = В сумме может быть не больше двух знаков после запятой
+amount-error-division-by-zero = На ноль делить нельзя
+amount-computed =
+    { $expression } = { $amount }
+    Записать эту сумму?
+amount-confirm-button = Записать
+amount-confirm-expired = Эта сумма уже записана или отменена
+transaction-saved = Записал.
+tags-prompt = Отметь теги (еда, поездки, аренда…) или пришли название нового, затем нажми «Готово»
+tag-name-invalid = Название тега — от 1 до { $max } символов
+due-date-prompt = Пришли срок возврата в формате ДД.ММ.ГГГГ или нажми «Без срока»
+no-due-date-button = Без срока
+due-date-saved = Готово, напомню о сроке
+due-date-invalid = Не понял дату, пришли её в формате ДД.ММ.ГГГГ (не раньше сегодняшней)
+history = История:
+history-empty = Записей пока нет
+receipt-button = Чек
+attachment-missing = К этой записи чек не прикреплён
+photo-unexpected = Фото чека можно прислать вместо суммы, когда записываешь долг через /menu
+photo-caption-missing = Добавь к фото подпись с суммой и комментарием, например «45.50 пицца»
+interest-button = Проценты
+tags-button = Теги
+transaction-deleted = Запись удалена
+transaction-delete-failed = Запись не найдена или уже удалена
+transaction-due-date = Вернуть до { $date }
+transaction-interest =
+    Основной долг: { $principal }
+    Проценты ({ $condition }): { $interest }
+transaction-tags = Теги: { $tags }
+transaction-audit = Журнал изменений:
+audit-insert = создано
+audit-update = изменено
+audit-delete = удалено
+
+# Быстрый ввод
+
+quick-entry-confirm =
+    Записать?
+    { $entry }
+quick-entry-saved = Записано: { $entry }
+quick-entry-cancelled = Не записываю
+quick-entry-changed = Сообщение изменили после вопроса. Отправь запись ещё раз
+quick-entry-lent = { $name }: ты дал { $amount }
+quick-entry-borrowed = { $name }: ты взял { $amount }
+quick-entry-contact-not-found = Не нашёл контакт «{ $name }». Проверь имя или добавь контакт через /menu
+quick-entry-contact-ambiguous = «{ $name }» подходит к нескольким контактам: { $names }. Напиши имя точнее
+
+# Команды
+
+command-start = Начать работу с ботом
+command-debts = Сводка долгов
+command-contacts = Список контактов
+command-menu = Главное меню
+command-settings = Настройки
+command-export = Выгрузить записи в CSV
+command-import = Загрузить записи из CSV или JSON
+command-backup = Резервная копия аккаунта
+command-restore = Восстановить из резервной копии
+command-deleteme = Удалить мои данные
+command-chart = График балансов
+command-stats = Статистика
+command-find = Поиск по записям
+command-gave = Записать, что я дал: контакт сумма [комментарий]
+command-took = Записать, что я взял: контакт сумма [комментарий]
+command-balance = Баланс с контактом
+command-history = История с контактом
+command-add-contact = Добавить контакт: @username [имя]
+command-rename = Переименовать контакт: старое новое
+usage-gave = Использование: /gave <контакт> <сумма> [комментарий], например /gave Петя 50 пицца
+usage-took = Использование: /took <контакт> <сумма> [комментарий], например /took Петя 120/3 такси
+usage-balance = Использование: /balance <контакт>
+usage-history = Использование: /history <контакт>
+usage-add-contact = Использование: /addcontact @username [имя]
+usage-rename = Использование: /rename <старое имя> <новое имя>
+balance-owed-to-you = { $name } должен тебе { $amount } { $currency }
+balance-you-owe = Ты должен { $name } { $amount } { $currency }
+balance-settled = С { $name } никто никому не должен
+contact-added = Контакт { $name } добавлен
+contact-not-registered = Пользователь @{ $username } не найден: скорее всего, он ещё не запускал бота
+contact-renamed = Готово: { $old } теперь { $new }
+
+# Inline-режим
+
+inline-record-button = Записать долг: { $name }
+inline-start-button = Открыть бота, чтобы начать
+inline-balance-owes = @{ $debtor } должен @{ $creditor } { $amount } { $currency }
+inline-balance-settled = @{ $user } и @{ $contact } никто никому не должен
+
+# Проценты
+
+interest-prompt =
+    Пришли условие:
+    «5%» — простые годовые проценты
+    «5% сложные» — сложные, с ежедневной капитализацией
+    «пеня 50» — фиксированная пеня после срока возврата
+    «нет» — без процентов
+
+    Или вернись в /menu для отмены
+interest-rule-invalid = Не понял условие, пришли например «5%», «5% сложные», «пеня 50» или «нет»
+interest-simple = { $rate }% годовых
+interest-compound = { $rate }% годовых, сложные
+interest-late-fee = пеня { $fee } после срока
+late-fee-without-due-date = Готово. У долга нет срока возврата, поэтому пеня начисляться не будет
+
+# Сводка и теги
+
+summary-empty = Долгов нет
+summary-tag-header = Сводка по тегу #{ $tag }
+summary-line-with-interest = { $name }: { $amount } (+ проценты { $interest })
+summary-all-button = Все
+summary-by-tag-button = По тегам
+tag-summary-empty = Помеченных тегами долгов пока нет
+tag-summary =
+    Сводка по тегам
+
+    { $lines }
+tag-summary-line = #{ $tag }: дал { $lent }, взял { $borrowed } { $currency }
+
+# Напоминания контакту
+
+nudge-nothing-owed = Этот контакт тебе ничего не должен
+nudge-muted-by-contact = Контакт отключил напоминания от тебя
+nudge-cooldown = Напомнить снова можно после { $date } UTC
+nudge-due-date = до { $date }
+nudge-text =
+    Привет! { $name } напоминает о долге.
+    По записям в боте ты должен { $balance }.
+
+    Открытые записи:
+    { $items }
+nudge-more-items = …и ещё { $count }
+nudge-mute-button = Не получать напоминания от { $name }
+nudge-sent = Напоминание отправлено
+nudges-muted = Больше не буду присылать напоминания от { $name }
+nudges-unmuted = Напоминания от { $name } снова включены
+nudges-unmute-button = Снова получать
+
+# Регулярные платежи
+
+recurring-direction = Кто платит?
+recurring-gave-button = Я даю в долг
+recurring-took-button = Я беру в долг
+recurring-prompt =
+    Пришли сумму, день и комментарий:
+    «40 5 Netflix» — каждый месяц 5-го числа
+    «40 пн Спортзал» — каждый понедельник
+
+    Или вернись в /menu для отмены
+recurring-input-invalid = Не понял. Пришли сумму, день и комментарий, например «40 5 Netflix» или «40 пн Спортзал»
+recurring-created =
+    Готово: { $recurring }
+    Первый платёж { $next_run } UTC
+recurring-view =
+    { $recurring }
+    Следующий платёж { $next_run } UTC
+recurring-list = Регулярные платежи:
+recurring-list-empty = Регулярных платежей нет. Завести их можно из карточки контакта в разделе «Долги»
+recurring-gives = { $name }: даю { $amount } { $schedule }
+recurring-takes = { $name }: беру { $amount } { $schedule }
+recurring-monthly = каждый месяц { $day }-го
+recurring-weekly =
+    { $weekday ->
+        [1] каждый понедельник
+        [2] каждый вторник
+        [3] каждую среду
+        [4] каждый четверг
+        [5] каждую пятницу
+        [6] каждую субботу
+       *[7] каждое воскресенье
+    }
+recurring-paused = пауза
+recurring-run-lender = Регулярный платёж: { $name } должен тебе { $amount }{ $comment }
+recurring-run-borrower = Регулярный платёж: ты должен { $name } { $amount }{ $comment }
+
+# Напоминания о сроках
+
+reminder-borrower-upcoming = Напоминание: { $date } срок возврата долга { $amount } для { $name }
+reminder-borrower-overdue = Просрочен долг { $amount } перед { $name }, срок был { $date }
+reminder-lender-upcoming = Напоминание: { $date } { $name } должен вернуть тебе { $amount }
+reminder-lender-overdue = { $name } просрочил возврат { $amount }, срок был { $date }
+
+# Настройки
+
+settings-text =
+    Настройки
+
+    Напоминания о долгах: { $frequency }
+    Тихие часы: { $quiet_hours }
+    Ежемесячная выписка: { $statement }
+    Язык: { $language }
+settings-reminders-off = выключены
+settings-reminders-daily = ежедневно
+settings-reminders-weekly = еженедельно
+settings-quiet-hours-none = нет
+settings-statement-on = включена
+settings-statement-off = выключена
+settings-language-auto = как в Telegram ({ $language })
+reminders-off-button = Выкл
+reminders-daily-button = Ежедневно
+reminders-weekly-button = Еженедельно
+quiet-hours-off-button = Без тихих часов
+statement-on-button = Выписка: вкл
+statement-off-button = Выписка: выкл
+language-auto-button = Язык Telegram
+
+# Экспорт, импорт, резервные копии
+
+export-usage = Использование: /export [контакт] [since:ГГГГ-ММ-ДД] [until:ГГГГ-ММ-ДД]. Сам день until не входит
+export-failed = Не удалось выгрузить: проверь имя контакта
+import-prompt = Пришли файл CSV или JSON с колонками date, counterparty, direction (дал/взял), amount, comment — такой же, как выдаёт /export
+waiting-import-file = Жду файл CSV или JSON, или вернись в /menu для отмены
+document-unexpected = Чтобы загрузить файл, набери /import для импорта долгов или /restore для восстановления копии
+file-too-large = Файл слишком большой, максимум 1 МБ
+file-unreadable = Не удалось прочитать файл: { $error }
+import-rows =
+    { $count } { $count ->
+        [one] строка
+        [few] строки
+       *[many] строк
+    }
+import-skipped = , пропущено удалённых: { $count }
+import-no-errors = , ошибок нет
+import-errors =
+    , { $count } { $count ->
+        [one] ошибка
+        [few] ошибки
+       *[many] ошибок
+    } в { $lines_count ->
+        [one] строке
+       *[other] строках
+    } { $lines }
+import-error-line = Строка { $line }: { $message }
+import-error-too-many-rows = слишком много строк, максимум { $max }
+import-error-csv-header = не удалось прочитать заголовок CSV
+import-error-csv-row = ошибка CSV в строке { $line }
+import-error-not-json = ожидается JSON-массив объектов
+import-error-no-counterparty = не указан контрагент
+import-error-no-direction = не указано направление
+import-error-direction = непонятное направление «{ $direction }»
+import-error-no-amount = не указана сумма
+import-error-not-positive = сумма должна быть больше нуля
+import-error-amount = непонятная сумма «{ $amount }»
+import-error-date = непонятная дата «{ $date }»
+import-error-no-contact = нет контакта «{ $name }»
+import-nothing = Импортировать нечего
+import-confirm-button = Импортировать { $count }
+import-applied = Импортировано записей: { $count }
+import-failed = Импорт не выполнен, ничего не изменилось
+import-undo-button = Отменить импорт
+import-cancelled = Импорт отменён
+import-reverted = Импорт отменён, удалено записей: { $count }
+import-revert-failed = Импорт не найден или уже отменён
+import-revert-button = Отменить импорт от { $date }
+backup-caption = Резервная копия аккаунта. Загрузить её можно командой /restore
+restore-prompt = Пришли файл резервной копии из /backup или вернись в /menu для отмены
+waiting-backup-file = Жду файл резервной копии, или вернись в /menu для отмены
+backup-restored = Копия восстановлена: контактов { $contacts }, записей { $transactions }, уже было { $duplicates }, пропущено { $skipped } (люди, которых нет в боте или в твоих контактах)
+backup-wrong-owner = Эта копия принадлежит другому аккаунту, восстановить её может только владелец
+backup-restore-failed = Не удалось восстановить копию, ничего не изменилось
+backup-error-not-backup = это не резервная копия grosze
+backup-error-version = версия копии { $version } новее поддерживаемой { $supported }, обнови бота
+backup-error-direction = непонятное направление «{ $direction }»
+backup-error-interest-kind = непонятный вид процентов «{ $kind }»
+backup-error-amount = сумма { $amount } вне допустимых границ или точнее копейки
+backup-error-interest-rate = ставка { $rate }% вне допустимых границ
+backup-error-reminder-frequency = непонятная частота напоминаний «{ $frequency }»
+
+# Удаление аккаунта
+
+delete-account-prompt =
+    Удалить аккаунт?
+
+    Будут удалены твой username, контакты, напоминания, регулярные платежи и настройки. Записи о долгах останутся у твоих контактов, но вместо тебя в них будет «удалённый пользователь». Отменить удаление нельзя, поэтому при желании сначала сохрани /backup
+delete-account-confirm-button = Да, удалить мои данные
+account-deleted = Твои данные удалены. Чтобы снова пользоваться ботом, набери /start
+account-delete-cancelled = Удаление отменено
+start-required = Чтобы пользоваться ботом, набери /start
+deleted-user = удалённый пользователь
+
+# Поиск
+
+find-usage =
+    Не понял запрос: { $error }
+
+    Примеры: /find пицца, /find >100, /find since:2026-01-01 until:2026-02-01, /find @alice пицца <50
+find-contact-not-found = Контакт «{ $name }» не найден
+find-nothing = Ничего не найдено
+find-results = Найдено: { $total }, страница { $page } из { $pages }
+search-error-empty = пустой запрос
+search-error-empty-contact = после «@» нужно имя контакта
+search-error-several-contacts = можно указать только один контакт
+search-error-inverted-dates = since должен быть раньше until
+search-error-date = не понял дату «{ $value }», нужен формат ГГГГ-ММ-ДД
+search-error-amount = не понял сумму в «{ $token }»
+search-error-negative-amount = сумма в «{ $token }» не может быть отрицательной
+
+# Статистика и графики
+
+stats-period-month = Месяц
+stats-period-quarter = Квартал
+stats-period-year = Год
+stats-period-all = Всё время
+stats-title-month = Статистика за этот месяц
+stats-title-quarter = Статистика за этот квартал
+stats-title-year = Статистика за этот год
+stats-title-all = Статистика за всё время
+stats-lent = Дал в долг: { $amount } { $currency }
+stats-borrowed = Взял в долг: { $amount } { $currency }
+stats-open-debts = Открытых долгов сейчас: { $count }
+stats-average-repay = Среднее время возврата: { $days } дн.
+stats-most-frequent = Чаще всего: { $name } ({ $count } транз.)
+stats-largest-lent = Крупнейший долг: { $amount } { $currency } — { $name }, ты дал { $date }
+stats-largest-borrowed = Крупнейший долг: { $amount } { $currency } — { $name }, ты взял { $date }
+chart-empty = Пока нечего рисовать: транзакций нет
+chart-history-title = Баланс с контактами
+chart-balances-title = Кто кому должен: плюс — тебе, минус — ты
+
+# Ежемесячная выписка
+
+statement-title =
+    Выписка за { $month ->
+        [1] январь
+        [2] февраль
+        [3] март
+        [4] апрель
+        [5] май
+        [6] июнь
+        [7] июль
+        [8] август
+        [9] сентябрь
+        [10] октябрь
+        [11] ноябрь
+       *[12] декабрь
+    } { $year }
+statement-line = { $name }: { $opening } → { $closing } (новые долги { $new_debts }, погашено { $repaid })
+statement-opening = Баланс на начало: { $amount } { $currency }
+statement-new-debts = Новые долги: { $amount } { $currency }
+statement-repaid = Погашено: { $amount } { $currency }
+statement-closing = Баланс на конец: { $amount } { $currency }
+statement-biggest-changes = Самые большие изменения:
+statement-footer = Плюс — тебе должны, минус — должен ты. Отключить выписки можно в /settings
+statement-caption = Выписка за прошлый месяц
+
+# PDF-выписка по контакту
+
+pdf-title = Выписка: { $user } и { $contact }
+pdf-column-date = Дата
+pdf-column-operation = Операция
+pdf-column-amount = Сумма
+pdf-column-balance = Баланс
+pdf-column-comment = Комментарий
+pdf-total-lent = Всего дал: { $amount } { $currency }
+pdf-total-borrowed = Всего взял: { $amount } { $currency }
+pdf-result-owed-to-you = Итог: { $name } должен тебе { $amount } { $currency }
+pdf-result-you-owe = Итог: ты должен { $name } { $amount } { $currency }
+pdf-result-settled = Итог: долгов нет
+pdf-signature = Сформировано ботом @{ $bot } { $date } UTC
@@ -0,0 +1,37 @@
+ALTER TABLE transactions
+    ADD COLUMN created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    ADD COLUMN deleted_at TIMESTAMPTZ NULL;
+
+CREATE TYPE audit_action AS ENUM (
+    'insert',
+    'update',
+    'delete'
+    );
+
+CREATE TABLE IF NOT EXISTS audit_log
+(
+    id         SERIAL PRIMARY KEY,
+    actor_id   INTEGER      NULL REFERENCES users_t (id),
+    table_name TEXT         NOT NULL,
+    record_id  INTEGER      NOT NULL,
+    action     audit_action NOT NULL,
+    before     JSONB        NULL,
+    after      JSONB        NULL,
+    created_at TIMESTAMPTZ  NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS audit_log_record_idx ON audit_log (table_name, record_id);
+
+-- Журнал только дописывается: любые UPDATE/DELETE по нему запрещены
+CREATE OR REPLACE FUNCTION audit_log_append_only() RETURNS trigger AS
+$$
+BEGIN
+    RAISE EXCEPTION 'audit_log is append-only';
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER audit_log_append_only
+    BEFORE UPDATE OR DELETE
+    ON audit_log
+    FOR EACH ROW
+EXECUTE FUNCTION audit_log_append_only();
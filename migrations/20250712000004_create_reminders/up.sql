@@ -0,0 +1,27 @@
+ALTER TYPE inputting_status ADD VALUE IF NOT EXISTS 'transaction_due_date';
+
+CREATE TYPE reminder_frequency AS ENUM (
+    'off',
+    'daily',
+    'weekly'
+    );
+
+ALTER TABLE users_t
+    ADD COLUMN selected_transaction_id INTEGER            NULL,
+    ADD COLUMN reminder_frequency      reminder_frequency NOT NULL DEFAULT 'daily',
+    ADD COLUMN quiet_hours_start       SMALLINT           NULL,
+    ADD COLUMN quiet_hours_end         SMALLINT           NULL;
+
+ALTER TABLE transactions
+    ADD COLUMN due_date DATE NULL;
+
+CREATE TABLE IF NOT EXISTS reminders
+(
+    id             SERIAL PRIMARY KEY,
+    transaction_id INTEGER     NOT NULL REFERENCES transactions (id) ON DELETE CASCADE,
+    user_id        INTEGER     NOT NULL REFERENCES users_t (id) ON DELETE CASCADE,
+    remind_at      TIMESTAMPTZ NOT NULL,
+    UNIQUE (transaction_id, user_id)
+);
+
+CREATE INDEX IF NOT EXISTS reminders_remind_at_idx ON reminders (remind_at);
@@ -0,0 +1,16 @@
+CREATE TABLE IF NOT EXISTS nudges
+(
+    id           SERIAL PRIMARY KEY,
+    from_user_id INTEGER     NOT NULL REFERENCES users_t (id) ON DELETE CASCADE,
+    to_user_id   INTEGER     NOT NULL REFERENCES users_t (id) ON DELETE CASCADE,
+    sent_at      TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS nudges_pair_idx ON nudges (from_user_id, to_user_id, sent_at);
+
+CREATE TABLE IF NOT EXISTS nudge_mutes
+(
+    user_id       INTEGER NOT NULL REFERENCES users_t (id) ON DELETE CASCADE,
+    muted_user_id INTEGER NOT NULL REFERENCES users_t (id) ON DELETE CASCADE,
+    PRIMARY KEY (user_id, muted_user_id)
+);
@@ -0,0 +1,25 @@
+ALTER TYPE inputting_status ADD VALUE IF NOT EXISTS 'recurring_transaction';
+
+ALTER TABLE transactions
+    ADD COLUMN comment TEXT NULL;
+
+CREATE TYPE recurrence_period AS ENUM (
+    'weekly',
+    'monthly'
+    );
+
+CREATE TABLE IF NOT EXISTS recurring_transactions
+(
+    id           SERIAL PRIMARY KEY,
+    owner_id     INTEGER           NOT NULL REFERENCES users_t (id) ON DELETE CASCADE,
+    from_user_id INTEGER           NOT NULL REFERENCES users_t (id) ON DELETE CASCADE,
+    to_user_id   INTEGER           NOT NULL REFERENCES users_t (id) ON DELETE CASCADE,
+    amount       NUMERIC           NOT NULL,
+    comment      TEXT              NULL,
+    period       recurrence_period NOT NULL,
+    period_day   SMALLINT          NOT NULL,
+    next_run_at  TIMESTAMPTZ       NOT NULL,
+    paused       BOOLEAN           NOT NULL DEFAULT FALSE
+);
+
+CREATE INDEX IF NOT EXISTS recurring_transactions_next_run_at_idx ON recurring_transactions (next_run_at);
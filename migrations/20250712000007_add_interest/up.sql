@@ -0,0 +1,13 @@
+ALTER TYPE inputting_status ADD VALUE IF NOT EXISTS 'transaction_interest';
+
+CREATE TYPE interest_kind AS ENUM (
+    'none',
+    'simple',
+    'compound',
+    'late_fee'
+    );
+
+ALTER TABLE transactions
+    ADD COLUMN interest_kind interest_kind NOT NULL DEFAULT 'none',
+    ADD COLUMN interest_rate NUMERIC       NULL,
+    ADD COLUMN late_fee      NUMERIC       NULL;
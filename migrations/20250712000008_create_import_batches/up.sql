@@ -0,0 +1,19 @@
+ALTER TYPE inputting_status ADD VALUE IF NOT EXISTS 'import_file';
+
+CREATE TYPE import_status AS ENUM (
+    'pending',
+    'imported',
+    'reverted'
+    );
+
+CREATE TABLE IF NOT EXISTS import_batches
+(
+    id         SERIAL PRIMARY KEY,
+    user_id    INTEGER       NOT NULL REFERENCES users_t (id) ON DELETE CASCADE,
+    status     import_status NOT NULL DEFAULT 'pending',
+    rows       JSONB         NOT NULL,
+    created_at TIMESTAMPTZ   NOT NULL DEFAULT now()
+);
+
+ALTER TABLE transactions
+    ADD COLUMN import_batch_id INTEGER NULL REFERENCES import_batches (id);
@@ -0,0 +1 @@
+ALTER TYPE inputting_status ADD VALUE IF NOT EXISTS 'restore_file';
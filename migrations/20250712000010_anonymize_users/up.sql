@@ -0,0 +1,12 @@
+-- Пользователи больше не удаляются, а обезличиваются, поэтому их транзакции
+-- остаются у контрагентов. ON DELETE SET NULL противоречил NOT NULL и падал бы.
+ALTER TABLE users_t
+    ADD COLUMN deleted_at TIMESTAMPTZ NULL;
+
+ALTER TABLE transactions
+    DROP CONSTRAINT IF EXISTS transactions_from_user_id_fkey,
+    DROP CONSTRAINT IF EXISTS transactions_to_user_id_fkey,
+    ADD CONSTRAINT transactions_from_user_id_fkey
+        FOREIGN KEY (from_user_id) REFERENCES users_t (id) ON DELETE RESTRICT,
+    ADD CONSTRAINT transactions_to_user_id_fkey
+        FOREIGN KEY (to_user_id) REFERENCES users_t (id) ON DELETE RESTRICT;
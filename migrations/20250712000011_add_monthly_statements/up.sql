@@ -0,0 +1,4 @@
+ALTER TABLE users_t
+    ADD COLUMN monthly_statement    BOOLEAN NOT NULL DEFAULT TRUE,
+    -- Первое число месяца, в котором выписка уже отправлена
+    ADD COLUMN last_statement_month DATE    NULL;
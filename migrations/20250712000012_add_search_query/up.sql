@@ -0,0 +1,3 @@
+-- Последний запрос /find: страницы результатов листаются кнопками, а в callback data он не влезает
+ALTER TABLE users_t
+    ADD COLUMN search_query TEXT NULL;
@@ -0,0 +1,19 @@
+ALTER TYPE inputting_status ADD VALUE IF NOT EXISTS 'transaction_tags';
+
+-- Теги личные: одну и ту же транзакцию каждая сторона размечает своими тегами
+CREATE TABLE tags
+(
+    id      SERIAL PRIMARY KEY,
+    user_id INT  NOT NULL REFERENCES users_t (id) ON DELETE CASCADE,
+    name    TEXT NOT NULL,
+    UNIQUE (user_id, name)
+);
+
+CREATE TABLE transaction_tags
+(
+    transaction_id INT NOT NULL REFERENCES transactions (id) ON DELETE CASCADE,
+    tag_id         INT NOT NULL REFERENCES tags (id) ON DELETE CASCADE,
+    PRIMARY KEY (transaction_id, tag_id)
+);
+
+CREATE INDEX transaction_tags_tag_id_idx ON transaction_tags (tag_id);
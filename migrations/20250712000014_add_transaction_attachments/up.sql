@@ -0,0 +1,3 @@
+-- Telegram file_id фото чека: сам файл хранится в Telegram, бот пересылает его по id
+ALTER TABLE transactions
+    ADD COLUMN attachment_file_id TEXT NULL;
@@ -0,0 +1,6 @@
+CREATE TYPE locale AS ENUM ('en', 'pl', 'ru');
+
+-- language_code приходит из Telegram с каждым апдейтом, locale — явный выбор пользователя в /settings
+ALTER TABLE users_t
+    ADD COLUMN language_code TEXT,
+    ADD COLUMN locale        locale;
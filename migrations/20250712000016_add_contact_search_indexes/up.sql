@@ -0,0 +1,6 @@
+-- Поиск контакта по началу имени в inline-режиме: LIKE 'prefix%' по lower(...)
+CREATE INDEX contacts_user_id_lower_name_idx
+    ON contacts (user_id, lower(name) text_pattern_ops);
+
+CREATE INDEX users_t_lower_telegram_username_idx
+    ON users_t (lower(telegram_username) text_pattern_ops);
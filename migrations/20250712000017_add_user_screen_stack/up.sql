@@ -0,0 +1,14 @@
+CREATE TYPE screen AS ENUM (
+    'menu',
+    'select_contact',
+    'contact_actions',
+    'transaction_amount',
+    'add_contact',
+    'edit_contact',
+    'rename_contact',
+    'debts'
+);
+
+-- Экраны, через которые пользователь пришёл к текущему: кнопка «Назад» снимает верхний
+ALTER TABLE users_t
+    ADD COLUMN screen_stack screen[] NOT NULL DEFAULT '{}';
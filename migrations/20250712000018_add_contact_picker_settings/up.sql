@@ -0,0 +1,9 @@
+ALTER TYPE inputting_status ADD VALUE IF NOT EXISTS 'contact_search';
+ALTER TYPE screen ADD VALUE IF NOT EXISTS 'contact_search';
+
+CREATE TYPE contact_sort AS ENUM ('recent', 'balance');
+
+-- Порядок контактов в выборе выбирается один раз и запоминается; поиск действует, пока выбор открыт
+ALTER TABLE users_t
+    ADD COLUMN contact_sort   contact_sort NOT NULL DEFAULT 'recent',
+    ADD COLUMN contact_search TEXT;
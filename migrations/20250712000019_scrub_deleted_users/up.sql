@@ -0,0 +1,52 @@
+-- У обезличенного пользователя telegram_id больше нет. Отрицательные id заняты группами,
+-- а NULL не конфликтует в UNIQUE(telegram_id), поэтому заглушки не пересекаются ни с кем
+ALTER TABLE users_t
+    ALTER COLUMN telegram_id DROP NOT NULL;
+
+UPDATE users_t
+SET telegram_id = NULL
+WHERE deleted_at IS NOT NULL;
+
+-- Журнал по-прежнему только дописывается. Единственное исключение — стирание содержимого
+-- записей через scrub_audit_log: остальные поля при этом меняться не могут
+CREATE OR REPLACE FUNCTION audit_log_append_only() RETURNS trigger AS
+$$
+BEGIN
+    IF TG_OP = 'UPDATE'
+        AND current_setting('grosze.audit_scrub', true) = 'on'
+        AND NEW.before IS NULL
+        AND NEW.after IS NULL
+        AND (NEW.id, NEW.actor_id, NEW.table_name, NEW.record_id, NEW.action, NEW.created_at)
+            IS NOT DISTINCT FROM
+            (OLD.id, OLD.actor_id, OLD.table_name, OLD.record_id, OLD.action, OLD.created_at)
+    THEN
+        RETURN NEW;
+    END IF;
+    RAISE EXCEPTION 'audit_log is append-only';
+END;
+$$ LANGUAGE plpgsql;
+
+-- Стирает before/after у записей удалённого пользователя: его действий, его контактов
+-- и его профиля. Сами записи (кто, что и когда) остаются
+CREATE OR REPLACE FUNCTION scrub_audit_log(p_user_id INTEGER) RETURNS INTEGER
+    SECURITY DEFINER
+    SET search_path = public
+AS
+$$
+DECLARE
+    scrubbed INTEGER;
+BEGIN
+    PERFORM set_config('grosze.audit_scrub', 'on', true);
+    UPDATE audit_log
+    SET before = NULL,
+        after  = NULL
+    WHERE (before IS NOT NULL OR after IS NOT NULL)
+      AND (actor_id = p_user_id
+        OR (table_name = 'users_t' AND record_id = p_user_id)
+        OR (table_name = 'contacts'
+            AND p_user_id IN ((before ->> 'user_id')::INTEGER, (after ->> 'user_id')::INTEGER)));
+    GET DIAGNOSTICS scrubbed = ROW_COUNT;
+    PERFORM set_config('grosze.audit_scrub', 'off', true);
+    RETURN scrubbed;
+END;
+$$ LANGUAGE plpgsql;
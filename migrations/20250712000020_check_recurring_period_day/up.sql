@@ -0,0 +1,5 @@
+-- Вне этих границ день не наступает никогда, и поиск следующего запуска не закончится
+ALTER TABLE recurring_transactions
+    ADD CONSTRAINT recurring_transactions_period_day_check
+        CHECK ((period = 'weekly' AND period_day BETWEEN 1 AND 7)
+            OR (period = 'monthly' AND period_day BETWEEN 1 AND 31));
@@ -0,0 +1,58 @@
+-- История сумм нужна контрагентам в спорах, поэтому у записей журнала о транзакциях
+-- удалённого пользователя стирается только комментарий, а before/after целиком —
+-- лишь у его профиля и контактов
+CREATE OR REPLACE FUNCTION audit_redact_comment(value JSONB) RETURNS JSONB
+    IMMUTABLE
+AS
+$$
+SELECT CASE WHEN value ? 'comment' THEN jsonb_set(value, '{comment}', 'null') ELSE value END;
+$$ LANGUAGE sql;
+
+-- Журнал по-прежнему только дописывается. Исключение — scrub_audit_log: она стирает
+-- before/after целиком или только комментарий в них, остальные поля меняться не могут
+CREATE OR REPLACE FUNCTION audit_log_append_only() RETURNS trigger AS
+$$
+BEGIN
+    IF TG_OP = 'UPDATE'
+        AND current_setting('grosze.audit_scrub', true) = 'on'
+        AND (NEW.before IS NULL OR NEW.before IS NOT DISTINCT FROM audit_redact_comment(OLD.before))
+        AND (NEW.after IS NULL OR NEW.after IS NOT DISTINCT FROM audit_redact_comment(OLD.after))
+        AND (NEW.id, NEW.actor_id, NEW.table_name, NEW.record_id, NEW.action, NEW.created_at)
+            IS NOT DISTINCT FROM
+            (OLD.id, OLD.actor_id, OLD.table_name, OLD.record_id, OLD.action, OLD.created_at)
+    THEN
+        RETURN NEW;
+    END IF;
+    RAISE EXCEPTION 'audit_log is append-only';
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION scrub_audit_log(p_user_id INTEGER) RETURNS INTEGER
+    SECURITY DEFINER
+    SET search_path = public
+AS
+$$
+DECLARE
+    scrubbed INTEGER;
+    redacted INTEGER;
+BEGIN
+    PERFORM set_config('grosze.audit_scrub', 'on', true);
+    UPDATE audit_log
+    SET before = NULL,
+        after  = NULL
+    WHERE (before IS NOT NULL OR after IS NOT NULL)
+      AND ((table_name = 'users_t' AND record_id = p_user_id)
+        OR (table_name = 'contacts'
+            AND p_user_id IN ((before ->> 'user_id')::INTEGER, (after ->> 'user_id')::INTEGER)));
+    GET DIAGNOSTICS scrubbed = ROW_COUNT;
+    UPDATE audit_log
+    SET before = audit_redact_comment(before),
+        after  = audit_redact_comment(after)
+    WHERE table_name = 'transactions'
+      AND actor_id = p_user_id
+      AND (before ->> 'comment' IS NOT NULL OR after ->> 'comment' IS NOT NULL);
+    GET DIAGNOSTICS redacted = ROW_COUNT;
+    PERFORM set_config('grosze.audit_scrub', 'off', true);
+    RETURN scrubbed + redacted;
+END;
+$$ LANGUAGE plpgsql;
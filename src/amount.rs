@@ -0,0 +1,670 @@
+//! Разбор сумм, которые пишут люди («12,50», «1 000», «12.5 zł», «€10»), и вывод сумм
+//! в привычном для языка интерфейса виде. Модуль чистый, без обращения к БД.
+
+use crate::expression;
+use crate::expression::ExpressionError;
+use crate::i18n::t;
+use crate::locale::Locale;
+use bigdecimal::BigDecimal;
+use bigdecimal::RoundingMode;
+use bigdecimal::Signed;
+use std::fmt;
+use std::str::FromStr;
+
+/// Суммы хранятся и показываются с точностью до копеек/грошей
+pub const SCALE: i64 = 2;
+/// Всё, что больше, почти наверняка опечатка (лишние нули или вставленный номер телефона)
+pub const MAX_AMOUNT: i64 = 10_000_000;
+
+/// Обозначения валют, которые можно написать до или после числа. Сама валюта
+/// не сохраняется: бот ведёт долги в одной валюте
+const CURRENCY_MARKERS: [&str; 13] = [
+    "pln", "zł", "zl", "eur", "€", "usd", "$", "rub", "руб.", "руб", "р.", "₽", "£",
+];
+
+/// Разделители разрядов, которые ставят вместо пробела или вместе с ним
+const GROUP_SPACES: [char; 4] = [' ', '\u{a0}', '\u{202f}', '\''];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AmountError {
+    Empty,
+    Invalid,
+    NotPositive,
+    TooLarge,
+    TooPrecise,
+    DivisionByZero,
+}
+
+impl AmountError {
+    pub fn message(&self, locale: Locale) -> String {
+        match self {
+            AmountError::Empty => t!(locale, "amount-error-empty"),
+            AmountError::Invalid => t!(locale, "amount-error-invalid"),
+            AmountError::NotPositive => t!(locale, "amount-error-not-positive"),
+            AmountError::TooLarge => t!(
+                locale,
+                "amount-error-too-large",
+                max = format_amount(&BigDecimal::from(MAX_AMOUNT), locale)
+            ),
+            AmountError::TooPrecise => t!(locale, "amount-error-too-precise"),
+            AmountError::DivisionByZero => t!(locale, "amount-error-division-by-zero"),
+        }
+    }
+}
+
+/// Для журналов и ошибок разбора команд; пользователю показывается `message`
+impl fmt::Display for AmountError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message(Locale::En))
+    }
+}
+
+impl std::error::Error for AmountError {}
+
+/// Положительная сумма не больше `MAX_AMOUNT` и не точнее копейки
+pub fn parse_amount(text: &str, locale: Locale) -> Result<BigDecimal, AmountError> {
+    check_range(parse_number(text, locale)?)
+}
+
+/// Неотрицательное число не точнее копейки, без проверки границ.
+/// Десятичный разделитель — точка или запятая; разряды разделяют пробелом, апострофом
+/// или вторым знаком препинания («1.234,56», «1,234.56»). Одиночный разделитель
+/// перед ровно тремя цифрами считается разрядным, только если это не десятичный знак
+/// языка: «1,000» по-английски — тысяча, а по-польски и по-русски — слишком точная дробь
+pub fn parse_number(text: &str, locale: Locale) -> Result<BigDecimal, AmountError> {
+    let text = strip_currency(&text.trim().to_lowercase());
+    if text.is_empty() {
+        return Err(AmountError::Empty);
+    }
+    if text.starts_with(['-', '−']) {
+        return Err(AmountError::NotPositive);
+    }
+    let text = text.strip_prefix('+').unwrap_or(&text);
+    if !text
+        .chars()
+        .all(|c| c.is_ascii_digit() || c == '.' || c == ',' || GROUP_SPACES.contains(&c))
+    {
+        return Err(AmountError::Invalid);
+    }
+
+    let (integer, fraction) = split_decimal(text, locale)?;
+    let integer = join_groups(integer)?;
+    if fraction.len() > SCALE as usize {
+        return Err(AmountError::TooPrecise);
+    }
+    match fraction {
+        "" => BigDecimal::from_str(&integer),
+        fraction => BigDecimal::from_str(&format!("0{integer}.{fraction}")),
+    }
+    .map_err(|_| AmountError::Invalid)
+}
+
+/// Сумма годится для записи: больше нуля и не больше `MAX_AMOUNT`
+pub fn check_range(amount: BigDecimal) -> Result<BigDecimal, AmountError> {
+    if !amount.is_positive() {
+        Err(AmountError::NotPositive)
+    } else if amount > BigDecimal::from(MAX_AMOUNT) {
+        Err(AmountError::TooLarge)
+    } else {
+        Ok(amount)
+    }
+}
+
+/// Сумма, пришедшая не текстом (например, из файла копии): как `check_range`,
+/// и вдобавок не точнее копейки
+pub fn check_amount(amount: BigDecimal) -> Result<BigDecimal, AmountError> {
+    if amount.normalized().as_bigint_and_exponent().1 > SCALE {
+        return Err(AmountError::TooPrecise);
+    }
+    check_range(amount)
+}
+
+/// Отрезает одно обозначение валюты в начале или в конце
+fn strip_currency(text: &str) -> String {
+    for marker in CURRENCY_MARKERS {
+        if let Some(rest) = text.strip_prefix(marker) {
+            return rest.trim().to_string();
+        }
+        if let Some(rest) = text.strip_suffix(marker) {
+            return rest.trim().to_string();
+        }
+    }
+    text.to_string()
+}
+
+/// Целая часть (ещё с разрядными разделителями, приведёнными к пробелу) и дробная часть
+fn split_decimal(text: &str, locale: Locale) -> Result<(String, &str), AmountError> {
+    let last_dot = text.rfind('.');
+    let last_comma = text.rfind(',');
+    let marks = text.matches(['.', ',']).count();
+    let decimal = match (last_dot, last_comma) {
+        (None, None) => None,
+        // Оба знака: десятичный — последний, и встречается он один раз
+        (Some(dot), Some(comma)) => {
+            let position = dot.max(comma);
+            if text.matches(&text[position..=position]).count() > 1 {
+                return Err(AmountError::Invalid);
+            }
+            Some(position)
+        }
+        (Some(position), None) | (None, Some(position)) => {
+            let after = &text[position + 1..];
+            let before = &text[..position];
+            let looks_like_group = after.len() == 3
+                && !text[position..].starts_with(decimal_mark(locale))
+                && !before.is_empty()
+                && !before.starts_with('0')
+                && !before.contains(GROUP_SPACES);
+            if marks > 1 || looks_like_group {
+                None
+            } else {
+                Some(position)
+            }
+        }
+    };
+    match decimal {
+        None => Ok((text.replace(['.', ','], " "), "")),
+        Some(position) => {
+            let fraction = &text[position + 1..];
+            if fraction.is_empty() || !fraction.chars().all(|c| c.is_ascii_digit()) {
+                return Err(AmountError::Invalid);
+            }
+            Ok((text[..position].replace(['.', ','], " "), fraction))
+        }
+    }
+}
+
+/// Склеивает разряды, проверяя, что после первой группы идут группы по три цифры
+fn join_groups(integer: String) -> Result<String, AmountError> {
+    let groups: Vec<&str> = integer.split(GROUP_SPACES).collect();
+    if groups.len() == 1 {
+        return Ok(integer);
+    }
+    let (first, rest) = groups.split_first().expect("split yields a group");
+    if first.is_empty() || first.len() > 3 || rest.iter().any(|group| group.len() != 3) {
+        return Err(AmountError::Invalid);
+    }
+    Ok(groups.concat())
+}
+
+/// Сумма и комментарий из сообщения вроде «1 000 zł за пиццу». Сумма — самое длинное
+/// начало сообщения из целых слов, которое разбирается как сумма. Если ни одно
+/// не подходит, возвращается ошибка разбора первого слова
+pub fn split_amount_and_comment(
+    text: &str,
+    locale: Locale,
+) -> Result<(BigDecimal, Option<&str>), AmountError> {
+    let text = text.trim();
+    let word_ends = word_ends(text);
+    let mut first_error = AmountError::Empty;
+    for (index, end) in word_ends.iter().enumerate().rev() {
+        match parse_amount(&text[..*end], locale) {
+            Ok(amount) => return Ok((amount, comment_after(text, *end))),
+            Err(error) if index == 0 => first_error = error,
+            Err(_) => {}
+        }
+    }
+    Err(first_error)
+}
+
+/// Сумма, введённая числом или выражением
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AmountInput<'a> {
+    Exact {
+        amount: BigDecimal,
+        comment: Option<&'a str>,
+    },
+    /// «120/3 пицца»: значение посчитано и округлено, его стоит показать перед записью
+    Computed {
+        expression: &'a str,
+        amount: BigDecimal,
+        comment: Option<&'a str>,
+    },
+}
+
+/// Как `split_amount_and_comment`, но в начале сообщения может стоять выражение
+/// («120/3», «45 + 12,5 - 3»). Выражение проверяется первым, чтобы «1 000 + 500»
+/// не превратилось в тысячу с комментарием «+ 500»
+pub fn parse_amount_input(text: &str, locale: Locale) -> Result<AmountInput<'_>, AmountError> {
+    let text = text.trim();
+    for end in word_ends(text).into_iter().rev() {
+        let prefix = text[..end].trim();
+        if !expression::has_operator(prefix) {
+            continue;
+        }
+        match expression::evaluate(prefix, locale) {
+            Ok(amount) => {
+                return Ok(AmountInput::Computed {
+                    expression: prefix,
+                    amount: check_range(amount)?,
+                    comment: comment_after(text, end),
+                });
+            }
+            Err(ExpressionError::DivisionByZero) => return Err(AmountError::DivisionByZero),
+            Err(ExpressionError::Invalid) => {}
+        }
+    }
+    let (amount, comment) = split_amount_and_comment(text, locale)?;
+    Ok(AmountInput::Exact { amount, comment })
+}
+
+/// Позиции концов слов: перед каждым пробельным символом и в конце текста
+fn word_ends(text: &str) -> Vec<usize> {
+    text.char_indices()
+        .filter(|(_, c)| c.is_whitespace())
+        .map(|(position, _)| position)
+        .chain(std::iter::once(text.len()))
+        .collect()
+}
+
+fn comment_after(text: &str, end: usize) -> Option<&str> {
+    Some(text[end..].trim()).filter(|comment| !comment.is_empty())
+}
+
+/// Знак, которым язык отделяет дробную часть
+fn decimal_mark(locale: Locale) -> char {
+    match locale {
+        Locale::En => '.',
+        Locale::Pl | Locale::Ru => ',',
+    }
+}
+
+/// Сумма с двумя знаками после запятой: «1 234,50» для ru/pl и «1,234.50» для en
+pub fn format_amount(amount: &BigDecimal, locale: Locale) -> String {
+    let (group, decimal) = match locale {
+        Locale::En => (",", "."),
+        Locale::Pl | Locale::Ru => ("\u{a0}", ","),
+    };
+    let rounded = amount.with_scale_round(SCALE, RoundingMode::HalfUp);
+    let digits = rounded.abs().as_bigint_and_exponent().0.to_string();
+    let digits = format!("{digits:0>3}");
+    let (integer, fraction) = digits.split_at(digits.len() - SCALE as usize);
+    let mut grouped = String::new();
+    for (index, digit) in integer.chars().enumerate() {
+        if index > 0 && (integer.len() - index) % 3 == 0 {
+            grouped.push_str(group);
+        }
+        grouped.push(digit);
+    }
+    let sign = if rounded.is_negative() { "-" } else { "" };
+    format!("{sign}{grouped}{decimal}{fraction}")
+}
+
+/// Сумма со знаком «+» для положительных значений, как в выписках
+pub fn format_signed(amount: &BigDecimal, locale: Locale) -> String {
+    let text = format_amount(amount, locale);
+    if amount
+        .with_scale_round(SCALE, RoundingMode::HalfUp)
+        .is_positive()
+    {
+        format!("+{text}")
+    } else {
+        text
+    }
+}
+
+/// Как [`format_amount`], но без нулевых копеек: «120» вместо «120,00» — для тесных кнопок
+pub fn format_short(amount: &BigDecimal, locale: Locale) -> String {
+    let text = format_amount(amount, locale);
+    match text
+        .strip_suffix(".00")
+        .or_else(|| text.strip_suffix(",00"))
+    {
+        Some(integer) => integer.to_string(),
+        None => text,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn amount(text: &str) -> BigDecimal {
+        BigDecimal::from_str(text).unwrap()
+    }
+
+    fn parsed(text: &str) -> BigDecimal {
+        parse_amount(text, Locale::En).unwrap_or_else(|error| panic!("{text:?}: {error:?}"))
+    }
+
+    #[test]
+    fn parses_plain_numbers() {
+        assert_eq!(parsed("12"), amount("12"));
+        assert_eq!(parsed("  7 "), amount("7"));
+        assert_eq!(parsed("+5"), amount("5"));
+        assert_eq!(parsed("0.01"), amount("0.01"));
+        assert_eq!(parsed("10000000"), amount("10000000"));
+    }
+
+    #[test]
+    fn parses_comma_and_dot_decimals() {
+        assert_eq!(parsed("12,50"), amount("12.50"));
+        assert_eq!(parsed("12.50"), amount("12.50"));
+        assert_eq!(parsed("12,5"), amount("12.5"));
+        assert_eq!(parsed("12.5"), amount("12.5"));
+        assert_eq!(parsed("0,99"), amount("0.99"));
+        assert_eq!(parsed(",5"), amount("0.5"));
+        assert_eq!(parsed("1234,56"), amount("1234.56"));
+    }
+
+    #[test]
+    fn parses_thousands_separators() {
+        assert_eq!(parsed("1 000"), amount("1000"));
+        assert_eq!(parsed("1\u{a0}000"), amount("1000"));
+        assert_eq!(parsed("1\u{202f}000"), amount("1000"));
+        assert_eq!(parsed("1'000"), amount("1000"));
+        assert_eq!(parsed("1 234 567"), amount("1234567"));
+        assert_eq!(parsed("1 000,50"), amount("1000.50"));
+        assert_eq!(parsed("1 000.50"), amount("1000.50"));
+        assert_eq!(parsed("1,000"), amount("1000"));
+        assert_eq!(parsed("1,000,000"), amount("1000000"));
+        assert_eq!(parsed("1.000.000"), amount("1000000"));
+        assert_eq!(parsed("1.234,56"), amount("1234.56"));
+        assert_eq!(parsed("1,234.56"), amount("1234.56"));
+        assert_eq!(parsed("1.234.567,8"), amount("1234567.8"));
+    }
+
+    #[test]
+    fn single_separator_before_three_digits_depends_on_locale() {
+        // Десятичный знак языка перед тремя цифрами — дробь, а не тысячи: «12,505» по-русски
+        // скорее опечатка в «12,50», чем двенадцать тысяч
+        assert_eq!(parse_amount("12,505", Locale::En), Ok(amount("12505")));
+        assert_eq!(
+            parse_amount("1.001", Locale::En),
+            Err(AmountError::TooPrecise)
+        );
+        for locale in [Locale::Pl, Locale::Ru] {
+            assert_eq!(parse_amount("1.001", locale), Ok(amount("1001")));
+            assert_eq!(parse_amount("12,505", locale), Err(AmountError::TooPrecise));
+            assert_eq!(parse_amount("1.000.000", locale), Ok(amount("1000000")));
+            assert_eq!(parse_amount("1.234,56", locale), Ok(amount("1234.56")));
+        }
+    }
+
+    #[test]
+    fn single_separator_before_three_digits_after_zero_is_decimal() {
+        // «0,125» не может быть ста двадцатью пятью, это слишком точная дробь
+        assert_eq!(
+            parse_amount("0,125", Locale::En),
+            Err(AmountError::TooPrecise)
+        );
+        assert_eq!(
+            parse_amount("1,2345", Locale::En),
+            Err(AmountError::TooPrecise)
+        );
+    }
+
+    #[test]
+    fn strips_currency_markers() {
+        assert_eq!(parsed("12.5 zł"), amount("12.5"));
+        assert_eq!(parsed("12,50zł"), amount("12.50"));
+        assert_eq!(parsed("12 zl"), amount("12"));
+        assert_eq!(parsed("PLN 40"), amount("40"));
+        assert_eq!(parsed("40 pln"), amount("40"));
+        assert_eq!(parsed("€10"), amount("10"));
+        assert_eq!(parsed("€ 10"), amount("10"));
+        assert_eq!(parsed("10€"), amount("10"));
+        assert_eq!(parsed("10 EUR"), amount("10"));
+        assert_eq!(parsed("$9.99"), amount("9.99"));
+        assert_eq!(parsed("9.99 USD"), amount("9.99"));
+        assert_eq!(parsed("500 руб."), amount("500"));
+        assert_eq!(parsed("500 руб"), amount("500"));
+        assert_eq!(parsed("500р."), amount("500"));
+        assert_eq!(parsed("1 500 ₽"), amount("1500"));
+        assert_eq!(parsed("£3"), amount("3"));
+    }
+
+    #[test]
+    fn rejects_empty_input() {
+        assert_eq!(parse_amount("", Locale::En), Err(AmountError::Empty));
+        assert_eq!(parse_amount("   ", Locale::En), Err(AmountError::Empty));
+        assert_eq!(parse_amount("zł", Locale::En), Err(AmountError::Empty));
+    }
+
+    #[test]
+    fn rejects_garbage() {
+        for text in [
+            "abc",
+            "12abc",
+            "1e5",
+            "0x10",
+            "12..5",
+            "12,,5",
+            "12.",
+            "12,",
+            ".",
+            ",",
+            "1 00",
+            "12 3456",
+            "1.2.3",
+            "1,23,456",
+            "1,234.567.8",
+            "1.234.5,6",
+            "€10€",
+            "10 zł zł",
+            "1 000 000 000 000 000 000 000x",
+            "NaN",
+            "inf",
+            "½",
+            "١٢",
+        ] {
+            assert_eq!(
+                parse_amount(text, Locale::En),
+                Err(AmountError::Invalid),
+                "{text:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_negative_and_zero() {
+        for text in ["-5", "−5", "-0,5", "- 5", "0", "0,00", "0.0", "000", "0 zł"] {
+            assert_eq!(
+                parse_amount(text, Locale::En),
+                Err(AmountError::NotPositive),
+                "{text:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_absurd_amounts() {
+        assert_eq!(
+            parse_amount("10000000.01", Locale::En),
+            Err(AmountError::TooLarge)
+        );
+        assert_eq!(
+            parse_amount("100 000 000", Locale::En),
+            Err(AmountError::TooLarge)
+        );
+        assert_eq!(
+            parse_amount("99999999999999999999999999", Locale::En),
+            Err(AmountError::TooLarge)
+        );
+    }
+
+    #[test]
+    fn rejects_fractions_of_a_cent() {
+        assert_eq!(
+            parse_amount("12.5051", Locale::En),
+            Err(AmountError::TooPrecise)
+        );
+        assert_eq!(
+            parse_amount("1 000,505", Locale::En),
+            Err(AmountError::TooPrecise)
+        );
+        assert_eq!(
+            parse_amount("1.234,567", Locale::En),
+            Err(AmountError::TooPrecise)
+        );
+    }
+
+    #[test]
+    fn splits_amount_and_comment() {
+        let split = |text| split_amount_and_comment(text, Locale::En).unwrap();
+        assert_eq!(split("45"), (amount("45"), None));
+        assert_eq!(split("45,50 пицца"), (amount("45.50"), Some("пицца")));
+        assert_eq!(
+            split("1 000 zł за ремонт"),
+            (amount("1000"), Some("за ремонт"))
+        );
+        assert_eq!(split("€10 кофе"), (amount("10"), Some("кофе")));
+        assert_eq!(split("  12.5   такси  "), (amount("12.5"), Some("такси")));
+        // Число в комментарии не приклеивается к сумме, если не образует разряд
+        assert_eq!(split("40 5 билетов"), (amount("40"), Some("5 билетов")));
+        assert_eq!(
+            split_amount_and_comment("пицца 45", Locale::En),
+            Err(AmountError::Invalid)
+        );
+        assert_eq!(
+            split_amount_and_comment("-5 долг", Locale::En),
+            Err(AmountError::NotPositive)
+        );
+        assert_eq!(
+            split_amount_and_comment("", Locale::En),
+            Err(AmountError::Empty)
+        );
+    }
+
+    #[test]
+    fn recognizes_expressions_in_input() {
+        assert_eq!(
+            parse_amount_input("120/3 пицца на троих", Locale::En),
+            Ok(AmountInput::Computed {
+                expression: "120/3",
+                amount: amount("40"),
+                comment: Some("пицца на троих"),
+            })
+        );
+        assert_eq!(
+            parse_amount_input("45 + 12,5 - 3", Locale::En),
+            Ok(AmountInput::Computed {
+                expression: "45 + 12,5 - 3",
+                amount: amount("54.5"),
+                comment: None,
+            })
+        );
+        assert_eq!(
+            parse_amount_input("1 000 + 500 ремонт", Locale::En),
+            Ok(AmountInput::Computed {
+                expression: "1 000 + 500",
+                amount: amount("1500"),
+                comment: Some("ремонт"),
+            })
+        );
+        assert_eq!(
+            parse_amount_input("5 хлебов", Locale::En),
+            Ok(AmountInput::Exact {
+                amount: amount("5"),
+                comment: Some("хлебов"),
+            })
+        );
+        assert_eq!(
+            parse_amount_input("50 - за пиццу", Locale::En),
+            Ok(AmountInput::Exact {
+                amount: amount("50"),
+                comment: Some("- за пиццу"),
+            })
+        );
+        assert_eq!(
+            parse_amount_input("12,5 zł", Locale::En),
+            Ok(AmountInput::Exact {
+                amount: amount("12.5"),
+                comment: None,
+            })
+        );
+    }
+
+    #[test]
+    fn checks_computed_amounts() {
+        assert_eq!(
+            parse_amount_input("5/0", Locale::En),
+            Err(AmountError::DivisionByZero)
+        );
+        assert_eq!(
+            parse_amount_input("10-20", Locale::En),
+            Err(AmountError::NotPositive)
+        );
+        assert_eq!(
+            parse_amount_input("1/1000", Locale::En),
+            Err(AmountError::NotPositive)
+        );
+        assert_eq!(
+            parse_amount_input("5000000*3", Locale::En),
+            Err(AmountError::TooLarge)
+        );
+        assert_eq!(
+            parse_amount_input("-5", Locale::En),
+            Err(AmountError::NotPositive)
+        );
+        assert_eq!(
+            parse_amount_input("пицца", Locale::En),
+            Err(AmountError::Invalid)
+        );
+    }
+
+    #[test]
+    fn formats_per_locale() {
+        assert_eq!(format_amount(&amount("1234.5"), Locale::En), "1,234.50");
+        assert_eq!(
+            format_amount(&amount("1234.5"), Locale::Ru),
+            "1\u{a0}234,50"
+        );
+        assert_eq!(
+            format_amount(&amount("1234.5"), Locale::Pl),
+            "1\u{a0}234,50"
+        );
+        assert_eq!(format_amount(&amount("0"), Locale::En), "0.00");
+        assert_eq!(format_amount(&amount("0.05"), Locale::Ru), "0,05");
+        assert_eq!(format_amount(&amount("999"), Locale::En), "999.00");
+        assert_eq!(
+            format_amount(&amount("1000000"), Locale::En),
+            "1,000,000.00"
+        );
+        assert_eq!(
+            format_amount(&amount("-1500.2"), Locale::Ru),
+            "-1\u{a0}500,20"
+        );
+    }
+
+    #[test]
+    fn formats_with_half_up_rounding() {
+        assert_eq!(format_amount(&amount("2.345"), Locale::En), "2.35");
+        assert_eq!(format_amount(&amount("2.344"), Locale::En), "2.34");
+        assert_eq!(format_amount(&amount("-2.345"), Locale::En), "-2.35");
+        assert_eq!(format_amount(&amount("-0.001"), Locale::En), "0.00");
+    }
+
+    #[test]
+    fn formats_signed() {
+        assert_eq!(format_signed(&amount("120"), Locale::En), "+120.00");
+        assert_eq!(format_signed(&amount("-120"), Locale::En), "-120.00");
+        assert_eq!(format_signed(&amount("0"), Locale::En), "0.00");
+    }
+
+    #[test]
+    fn formats_short() {
+        assert_eq!(format_short(&amount("120"), Locale::En), "120");
+        assert_eq!(format_short(&amount("1234.5"), Locale::En), "1,234.50");
+        assert_eq!(format_short(&amount("1200"), Locale::Ru), "1\u{a0}200");
+        assert_eq!(format_short(&amount("-35.004"), Locale::Pl), "-35");
+    }
+
+    #[test]
+    fn formatted_amounts_parse_back() {
+        for text in ["0.01", "12.5", "999.99", "1000", "1234567.89", "10000000"] {
+            for locale in Locale::ALL {
+                let formatted = format_amount(&amount(text), locale);
+                assert_eq!(parsed(&formatted), amount(text), "{formatted:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn checks_amounts_from_files() {
+        assert_eq!(check_amount(amount("5.000")), Ok(amount("5.000")));
+        assert_eq!(check_amount(amount("0.001")), Err(AmountError::TooPrecise));
+        assert_eq!(check_amount(amount("-10")), Err(AmountError::NotPositive));
+        assert_eq!(check_amount(amount("1e30")), Err(AmountError::TooLarge));
+    }
+}
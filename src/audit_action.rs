@@ -0,0 +1,15 @@
+use diesel_derive_enum::DbEnum;
+use strum_macros::Display;
+use strum_macros::EnumString;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, DbEnum, EnumString, Display)]
+#[ExistingTypePath = "crate::schema::sql_types::AuditAction"]
+#[strum(serialize_all = "snake_case")]
+pub enum AuditAction {
+    #[db_rename = "insert"]
+    Insert,
+    #[db_rename = "update"]
+    Update,
+    #[db_rename = "delete"]
+    Delete,
+}
@@ -0,0 +1,254 @@
+//! Полная выгрузка аккаунта в версионированный JSON и восстановление из него.
+//! Пользователи в файле идентифицируются по `telegram_id`, а не по внутренним id,
+//! поэтому резервную копию можно загрузить в другой экземпляр бота.
+
+use crate::amount;
+use crate::db_util;
+use crate::i18n::t;
+use crate::interest::MAX_ANNUAL_RATE;
+use crate::interest_kind::InterestKind;
+use crate::locale::Locale;
+use crate::models::User;
+use crate::reminder_frequency::ReminderFrequency;
+use bigdecimal::BigDecimal;
+use chrono::DateTime;
+use chrono::NaiveDate;
+use chrono::Utc;
+use serde::Deserialize;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt;
+
+pub const BACKUP_FORMAT: &str = "grosze-backup";
+/// Увеличивается при несовместимых изменениях формата
+pub const BACKUP_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Backup {
+    pub format: String,
+    pub version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub profile: BackupProfile,
+    pub settings: BackupSettings,
+    pub contacts: Vec<BackupContact>,
+    pub transactions: Vec<BackupTransaction>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BackupProfile {
+    pub telegram_id: i64,
+    pub telegram_username: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BackupSettings {
+    pub reminder_frequency: String,
+    pub quiet_hours_start: Option<i16>,
+    pub quiet_hours_end: Option<i16>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BackupContact {
+    pub telegram_id: i64,
+    pub telegram_username: String,
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BackupTransaction {
+    pub counterparty_telegram_id: i64,
+    pub counterparty_username: String,
+    /// `lent` — владелец копии дал в долг, `borrowed` — взял
+    pub direction: String,
+    pub amount: BigDecimal,
+    pub comment: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub due_date: Option<NaiveDate>,
+    pub interest_kind: String,
+    pub interest_rate: Option<BigDecimal>,
+    pub late_fee: Option<BigDecimal>,
+}
+
+/// Почему файл не годится для восстановления
+#[derive(Debug, Clone, PartialEq)]
+pub enum BackupError {
+    NotBackup,
+    NewerVersion(u32),
+    InvalidDirection(String),
+    InvalidInterestKind(String),
+    InvalidAmount(BigDecimal),
+    InvalidInterestRate(BigDecimal),
+    InvalidReminderFrequency(String),
+}
+
+impl BackupError {
+    pub fn message(&self, locale: Locale) -> String {
+        match self {
+            BackupError::NotBackup => t!(locale, "backup-error-not-backup"),
+            BackupError::NewerVersion(version) => t!(
+                locale,
+                "backup-error-version",
+                version = *version,
+                supported = BACKUP_VERSION
+            ),
+            BackupError::InvalidDirection(direction) => {
+                t!(
+                    locale,
+                    "backup-error-direction",
+                    direction = direction.as_str()
+                )
+            }
+            BackupError::InvalidInterestKind(kind) => {
+                t!(locale, "backup-error-interest-kind", kind = kind.as_str())
+            }
+            BackupError::InvalidAmount(amount) => {
+                t!(locale, "backup-error-amount", amount = amount.to_string())
+            }
+            BackupError::InvalidInterestRate(rate) => {
+                t!(
+                    locale,
+                    "backup-error-interest-rate",
+                    rate = rate.to_string()
+                )
+            }
+            BackupError::InvalidReminderFrequency(frequency) => t!(
+                locale,
+                "backup-error-reminder-frequency",
+                frequency = frequency.as_str()
+            ),
+        }
+    }
+}
+
+/// Для журналов; пользователю показывается `message`
+impl fmt::Display for BackupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message(Locale::En))
+    }
+}
+
+impl std::error::Error for BackupError {}
+
+/// Итог восстановления для ответа пользователю
+#[derive(Debug, Default)]
+pub struct RestoreSummary {
+    pub contacts: usize,
+    pub transactions: usize,
+    pub duplicates: usize,
+    /// Контакты и записи с теми, кого нет в боте или в контактах
+    pub skipped: usize,
+}
+
+pub fn build_backup(user: &User) -> Result<Backup, diesel::result::Error> {
+    let contacts = db_util::find_all_contacts_for_user(user);
+    let transactions = db_util::find_transactions_for_export(user, None, None, None)?;
+    let mut user_ids = contacts
+        .iter()
+        .map(|contact| contact.contact_id)
+        .collect::<Vec<_>>();
+    user_ids.extend(
+        transactions
+            .iter()
+            .flat_map(|tx| [tx.from_user_id, tx.to_user_id]),
+    );
+    let users: HashMap<i32, User> = db_util::find_users_by_ids(&user_ids)?
+        .into_iter()
+        .map(|user| (user.id, user))
+        .collect();
+    let contacts = contacts
+        .into_iter()
+        .filter_map(|contact| {
+            let contact_user = users.get(&contact.contact_id)?;
+            Some(BackupContact {
+                telegram_id: contact_user.telegram_id?,
+                telegram_username: contact_user.telegram_username.clone(),
+                name: contact.name,
+            })
+        })
+        .collect();
+    let transactions = transactions
+        .into_iter()
+        .filter_map(|tx| {
+            let lent = tx.from_user_id == user.id;
+            let counterparty_id = if lent { tx.to_user_id } else { tx.from_user_id };
+            let counterparty = users.get(&counterparty_id)?;
+            Some(BackupTransaction {
+                counterparty_telegram_id: counterparty.telegram_id?,
+                counterparty_username: counterparty.telegram_username.clone(),
+                direction: if lent { "lent" } else { "borrowed" }.to_string(),
+                amount: tx.amount,
+                comment: tx.comment,
+                created_at: tx.created_at,
+                deleted_at: tx.deleted_at,
+                due_date: tx.due_date,
+                interest_kind: tx.interest_kind.to_string(),
+                interest_rate: tx.interest_rate,
+                late_fee: tx.late_fee,
+            })
+        })
+        .collect();
+    Ok(Backup {
+        format: BACKUP_FORMAT.to_string(),
+        version: BACKUP_VERSION,
+        exported_at: Utc::now(),
+        profile: BackupProfile {
+            telegram_id: user.telegram_id.unwrap_or_default(),
+            telegram_username: user.telegram_username.clone(),
+        },
+        settings: BackupSettings {
+            reminder_frequency: user.reminder_frequency.to_string(),
+            quiet_hours_start: user.quiet_hours_start,
+            quiet_hours_end: user.quiet_hours_end,
+        },
+        contacts,
+        transactions,
+    })
+}
+
+/// Разбирает и проверяет файл копии
+pub fn parse_backup(content: &[u8]) -> Result<Backup, BackupError> {
+    let backup: Backup = serde_json::from_slice(content).map_err(|_| BackupError::NotBackup)?;
+    if backup.format != BACKUP_FORMAT {
+        return Err(BackupError::NotBackup);
+    }
+    if backup.version > BACKUP_VERSION {
+        return Err(BackupError::NewerVersion(backup.version));
+    }
+    for transaction in &backup.transactions {
+        if transaction.direction != "lent" && transaction.direction != "borrowed" {
+            return Err(BackupError::InvalidDirection(transaction.direction.clone()));
+        }
+        if transaction.interest_kind.parse::<InterestKind>().is_err() {
+            return Err(BackupError::InvalidInterestKind(
+                transaction.interest_kind.clone(),
+            ));
+        }
+        // Файл можно поправить руками, а записи попадут и в книгу контрагента,
+        // поэтому суммы проверяются так же, как при вводе
+        for amount in [Some(&transaction.amount), transaction.late_fee.as_ref()]
+            .into_iter()
+            .flatten()
+        {
+            if amount::check_amount(amount.clone()).is_err() {
+                return Err(BackupError::InvalidAmount(amount.clone()));
+            }
+        }
+        if let Some(rate) = &transaction.interest_rate {
+            if *rate <= BigDecimal::from(0) || *rate > BigDecimal::from(MAX_ANNUAL_RATE) {
+                return Err(BackupError::InvalidInterestRate(rate.clone()));
+            }
+        }
+    }
+    if backup
+        .settings
+        .reminder_frequency
+        .parse::<ReminderFrequency>()
+        .is_err()
+    {
+        return Err(BackupError::InvalidReminderFrequency(
+            backup.settings.reminder_frequency.clone(),
+        ));
+    }
+    Ok(backup)
+}
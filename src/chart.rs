@@ -0,0 +1,226 @@
+use crate::currency;
+use crate::db_util;
+use crate::i18n::t;
+use crate::locale::Locale;
+use crate::models::User;
+use crate::BoxError;
+use bigdecimal::ToPrimitive;
+use chrono::NaiveDate;
+use chrono::Utc;
+use image::ImageFormat;
+use image::RgbImage;
+use plotters::prelude::*;
+use plotters::style::register_font;
+use plotters::style::FontStyle;
+use std::collections::HashMap;
+use std::io::Cursor;
+use std::sync::Once;
+
+const WIDTH: u32 = 1000;
+const HEIGHT: u32 = 900;
+/// На графике баланса линии только для контактов с наибольшими долгами, иначе он нечитаем
+const MAX_LINES: usize = 8;
+const FONT_NAME: &str = "sans-serif";
+/// Шрифт лежит в репозитории: в Docker-образе системных шрифтов может не быть
+const FONT: &[u8] = include_bytes!("../assets/DejaVuSans.ttf");
+
+static REGISTER_FONT: Once = Once::new();
+
+/// Баланс с одним контактом во времени. Плюс — контакт должен пользователю
+struct ContactHistory {
+    name: String,
+    points: Vec<(NaiveDate, f64)>,
+}
+
+impl ContactHistory {
+    fn balance(&self) -> f64 {
+        self.points
+            .last()
+            .map(|(_, balance)| *balance)
+            .unwrap_or(0.0)
+    }
+}
+
+/// PNG с двумя графиками: баланс с контактами во времени и текущие долги столбцами.
+/// `None`, если у пользователя ещё нет ни одной транзакции
+pub fn balance_chart(user: &User) -> Result<Option<Vec<u8>>, BoxError> {
+    let mut histories = load_histories(user)?;
+    if histories.is_empty() {
+        return Ok(None);
+    }
+    histories.sort_by(|a, b| b.balance().abs().total_cmp(&a.balance().abs()));
+
+    REGISTER_FONT.call_once(|| {
+        if register_font(FONT_NAME, FontStyle::Normal, FONT).is_err() {
+            log::error!("Error registering chart font");
+        }
+    });
+    let mut buffer = vec![0u8; (WIDTH * HEIGHT * 3) as usize];
+    {
+        let root = BitMapBackend::with_buffer(&mut buffer, (WIDTH, HEIGHT)).into_drawing_area();
+        root.fill(&WHITE)?;
+        let (upper, lower) = root.split_vertically(HEIGHT / 2);
+        let locale = user.locale();
+        draw_history(&upper, locale, &histories[..histories.len().min(MAX_LINES)])?;
+        draw_balances(&lower, locale, &histories)?;
+        root.present()?;
+    }
+    let image = RgbImage::from_raw(WIDTH, HEIGHT, buffer).ok_or("chart buffer size mismatch")?;
+    let mut png = Vec::new();
+    image.write_to(&mut Cursor::new(&mut png), ImageFormat::Png)?;
+    Ok(Some(png))
+}
+
+fn load_histories(user: &User) -> Result<Vec<ContactHistory>, BoxError> {
+    let mut balances: HashMap<i32, (f64, Vec<(NaiveDate, f64)>)> = HashMap::new();
+    let transactions = db_util::find_transactions_for_export(user, None, None, None)?;
+    for transaction in transactions.iter().filter(|t| t.deleted_at.is_none()) {
+        let amount = transaction.amount.to_f64().unwrap_or(0.0);
+        let (counterparty_id, delta) = if transaction.from_user_id == user.id {
+            (transaction.to_user_id, amount)
+        } else {
+            (transaction.from_user_id, -amount)
+        };
+        let (balance, points) = balances.entry(counterparty_id).or_default();
+        *balance += delta;
+        let date = transaction.created_at.date_naive();
+        // За один день остаётся только итоговый баланс
+        match points.last_mut() {
+            Some((last_date, last_balance)) if *last_date == date => *last_balance = *balance,
+            _ => points.push((date, *balance)),
+        }
+    }
+    let today = Utc::now().date_naive();
+    let mut histories = Vec::with_capacity(balances.len());
+    for (counterparty_id, (balance, mut points)) in balances {
+        if points.last().is_some_and(|(date, _)| *date < today) {
+            points.push((today, balance));
+        }
+        let counterparty = db_util::get_user_by_id(counterparty_id)?;
+        histories.push(ContactHistory {
+            name: db_util::get_contact_display_name(user, &counterparty),
+            points,
+        });
+    }
+    Ok(histories)
+}
+
+fn draw_history<DB: DrawingBackend>(
+    area: &DrawingArea<DB, plotters::coord::Shift>,
+    locale: Locale,
+    histories: &[ContactHistory],
+) -> Result<(), BoxError>
+where
+    DB::ErrorType: 'static,
+{
+    let points = histories.iter().flat_map(|history| history.points.iter());
+    let first_date = points
+        .clone()
+        .map(|(date, _)| *date)
+        .min()
+        .unwrap_or_default();
+    let last_date = points
+        .clone()
+        .map(|(date, _)| *date)
+        .max()
+        .unwrap_or_default();
+    let (min, max) = value_range(points.map(|(_, balance)| *balance));
+    let days = (last_date - first_date).num_days().max(1) as i32;
+
+    let mut chart = ChartBuilder::on(area)
+        .caption(t!(locale, "chart-history-title"), (FONT_NAME, 24))
+        .margin(15)
+        .margin_right(40)
+        .x_label_area_size(35)
+        .y_label_area_size(70)
+        .build_cartesian_2d(0..days, min..max)?;
+    chart
+        .configure_mesh()
+        .x_labels(8)
+        .x_label_formatter(&|day| {
+            (first_date + chrono::Days::new(*day as u64))
+                .format("%d.%m.%y")
+                .to_string()
+        })
+        .y_desc(currency())
+        .label_style((FONT_NAME, 14))
+        .draw()?;
+    for (index, history) in histories.iter().enumerate() {
+        let color = Palette99::pick(index).to_rgba();
+        let series = history
+            .points
+            .iter()
+            .map(|(date, balance)| ((*date - first_date).num_days() as i32, *balance));
+        chart
+            .draw_series(LineSeries::new(series, color.stroke_width(2)))?
+            .label(history.name.as_str())
+            .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], color));
+    }
+    chart
+        .configure_series_labels()
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .label_font((FONT_NAME, 14))
+        .draw()?;
+    Ok(())
+}
+
+fn draw_balances<DB: DrawingBackend>(
+    area: &DrawingArea<DB, plotters::coord::Shift>,
+    locale: Locale,
+    histories: &[ContactHistory],
+) -> Result<(), BoxError>
+where
+    DB::ErrorType: 'static,
+{
+    let (min, max) = value_range(histories.iter().map(ContactHistory::balance));
+    let names: Vec<&str> = histories
+        .iter()
+        .map(|history| history.name.as_str())
+        .collect();
+    let mut chart = ChartBuilder::on(area)
+        .caption(t!(locale, "chart-balances-title"), (FONT_NAME, 24))
+        .margin(15)
+        .x_label_area_size(35)
+        .y_label_area_size(70)
+        // Сегментированная ось включает правую границу, поэтому `len - 1`
+        .build_cartesian_2d((0..histories.len() - 1).into_segmented(), min..max)?;
+    chart
+        .configure_mesh()
+        .disable_x_mesh()
+        .x_labels(histories.len())
+        .x_label_formatter(&|segment| match segment {
+            SegmentValue::CenterOf(index) => names.get(*index).unwrap_or(&"").to_string(),
+            _ => String::new(),
+        })
+        .y_desc(currency())
+        .label_style((FONT_NAME, 14))
+        .draw()?;
+    chart.draw_series(histories.iter().enumerate().map(|(index, history)| {
+        let balance = history.balance();
+        let color = if balance >= 0.0 {
+            RGBColor(46, 160, 67)
+        } else {
+            RGBColor(215, 58, 73)
+        };
+        let mut bar = Rectangle::new(
+            [
+                (SegmentValue::Exact(index), 0.0),
+                (SegmentValue::Exact(index + 1), balance),
+            ],
+            color.mix(0.7).filled(),
+        );
+        bar.set_margin(0, 0, 10, 10);
+        bar
+    }))?;
+    Ok(())
+}
+
+/// Диапазон оси Y с нулём внутри и небольшим запасом сверху и снизу
+fn value_range(values: impl Iterator<Item = f64>) -> (f64, f64) {
+    let (min, max) = values.fold((0.0_f64, 0.0_f64), |(min, max), value| {
+        (min.min(value), max.max(value))
+    });
+    let padding = ((max - min) * 0.1).max(1.0);
+    (min - padding, max + padding)
+}
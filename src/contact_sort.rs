@@ -0,0 +1,14 @@
+use diesel_derive_enum::DbEnum;
+use strum_macros::Display;
+use strum_macros::EnumString;
+
+/// Порядок контактов в выборе: сначала недавние или сначала с крупным долгом в любую сторону
+#[derive(Debug, Clone, Copy, PartialEq, Eq, DbEnum, EnumString, Display)]
+#[ExistingTypePath = "crate::schema::sql_types::ContactSort"]
+#[strum(serialize_all = "snake_case")]
+pub enum ContactSort {
+    #[db_rename = "recent"]
+    Recent,
+    #[db_rename = "balance"]
+    Balance,
+}
@@ -1,23 +1,64 @@
+use crate::audit_action::AuditAction;
+use crate::backup::Backup;
+use crate::backup::RestoreSummary;
+use crate::contact_sort::ContactSort;
 use crate::establish_connection;
+use crate::i18n::t;
+use crate::import_status::ImportStatus;
 use crate::inputting_status::InputtingStatus;
+use crate::interest::InterestRule;
+use crate::interest_kind::InterestKind;
+use crate::locale::Locale;
+use crate::models::AuditEntry;
 use crate::models::Contact;
+use crate::models::ImportBatch;
+use crate::models::NewAuditEntry;
 use crate::models::NewContact;
+use crate::models::NewImportBatch;
+use crate::models::NewImportedTransaction;
+use crate::models::NewNamedContact;
+use crate::models::NewNudge;
+use crate::models::NewNudgeMute;
+use crate::models::NewRecurringTransaction;
+use crate::models::NewReminder;
+use crate::models::NewRestoredTransaction;
+use crate::models::NewTag;
 use crate::models::NewTransaction;
+use crate::models::NewTransactionTag;
 use crate::models::NewUser;
+use crate::models::RecurringTransaction;
+use crate::models::Reminder;
+use crate::models::Tag;
 use crate::models::Transaction;
 use crate::models::User;
+use crate::reminder_frequency::ReminderFrequency;
+use crate::schema::audit_log::dsl as audit_dsl;
 use crate::schema::contacts::dsl as contacts_dsl;
+use crate::schema::import_batches::dsl as imports_dsl;
+use crate::schema::nudge_mutes::dsl as nudge_mutes_dsl;
+use crate::schema::nudges::dsl as nudges_dsl;
+use crate::schema::recurring_transactions::dsl as recurring_dsl;
+use crate::schema::reminders::dsl as reminders_dsl;
+use crate::schema::tags::dsl as tags_dsl;
+use crate::schema::transaction_tags::dsl as transaction_tags_dsl;
 use crate::schema::transactions::dsl as txs_dsl;
 use crate::schema::users_t::dsl as users_dsl;
-use bigdecimal::{BigDecimal, ParseBigDecimalError};
-use diesel::prelude::*;
-use diesel::prelude::*;
+use crate::screen::Screen;
+use crate::search::AmountFilter;
+use crate::search::SearchQuery;
+use crate::statement::StatementRow;
+use bigdecimal::BigDecimal;
+use chrono::DateTime;
+use chrono::NaiveDate;
+use chrono::Utc;
+use diesel::pg::Pg;
 use diesel::prelude::*;
 use diesel::result::QueryResult;
 use diesel::upsert::excluded;
 use diesel::PgConnection;
 use diesel::RunQueryDsl;
-use std::str::FromStr;
+use serde::Serialize;
+use std::collections::HashMap;
 
 /// blablabla
 pub fn get_user_by_telegram_id(tg_id_val: i64) -> QueryResult<User> {
@@ -28,19 +69,27 @@ pub fn get_user_by_telegram_id(tg_id_val: i64) -> QueryResult<User> {
 }
 
 /// blablabla
-pub fn get_user_by_username(tg_username: &String) -> QueryResult<User> {
+pub fn get_user_by_username(tg_username: &str) -> QueryResult<User> {
     let mut conn = establish_connection();
     users_dsl::users_t
         .filter(users_dsl::telegram_username.eq(tg_username))
+        .filter(users_dsl::deleted_at.is_null())
+        .first(&mut conn)
+}
+
+pub fn get_user_by_id(id: i32) -> QueryResult<User> {
+    let mut conn = establish_connection();
+    users_dsl::users_t
+        .filter(users_dsl::id.eq(id))
         .first(&mut conn)
 }
 
 /// Вставляет или обновляет пользователя по telegram_id
-pub fn find_or_create_user(tg_id_val: i64, tg_username_val: &String) -> User {
+pub fn find_or_create_user(tg_id_val: i64, tg_username_val: &str) -> User {
     let mut conn = establish_connection();
     let new_user = NewUser {
         telegram_id: tg_id_val,
-        telegram_username: tg_username_val.clone(),
+        telegram_username: tg_username_val.to_string(),
     };
     diesel::insert_into(users_dsl::users_t)
         .values(&new_user)
@@ -58,23 +107,37 @@ pub fn find_or_create_contact(user: &User, contact: &User) -> Contact {
         user_id: user.id,
         contact_id: contact.id,
     };
-    diesel::insert_into(contacts_dsl::contacts)
-        .values(&new_contact)
-        .on_conflict((contacts_dsl::user_id, contacts_dsl::contact_id))
-        .do_nothing()
-        .get_result(&mut conn)
-        .or_else(|err| {
-            if let diesel::result::Error::NotFound = err {
-                contacts_dsl::contacts
-                    .filter(contacts_dsl::user_id.eq(user.id))
-                    .filter(contacts_dsl::contact_id.eq(contact.id))
-                    .first(&mut conn)
-            } else {
-                log::error!("Error: {:?}", err);
-                Err(err)
+    conn.transaction(|conn| {
+        let inserted: Option<Contact> = diesel::insert_into(contacts_dsl::contacts)
+            .values(&new_contact)
+            .on_conflict((contacts_dsl::user_id, contacts_dsl::contact_id))
+            .do_nothing()
+            .get_result(conn)
+            .optional()?;
+        match inserted {
+            Some(contact) => {
+                write_audit(
+                    conn,
+                    user,
+                    "contacts",
+                    contact.id,
+                    AuditAction::Insert,
+                    None,
+                    Some(&contact),
+                )?;
+                Ok(contact)
             }
-        })
-        .expect("Error creating or fetching contact")
+            None => contacts_dsl::contacts
+                .filter(contacts_dsl::user_id.eq(user.id))
+                .filter(contacts_dsl::contact_id.eq(contact.id))
+                .first(conn),
+        }
+    })
+    .map_err(|err: diesel::result::Error| {
+        log::error!("Error: {:?}", err);
+        err
+    })
+    .expect("Error creating or fetching contact")
 }
 
 /// Возвращает все контакты для данного user_id
@@ -86,29 +149,147 @@ pub fn find_all_contacts_for_user(user: &User) -> Vec<Contact> {
         .expect("Error loading contacts")
 }
 
-/// Создаёт транзакцию между двумя пользователями
+/// Создаёт транзакцию между двумя пользователями; `actor` — тот, кто её записал
 pub fn create_transaction(
+    actor: &User,
     from: &User,
     to: &User,
-    amount_str: &str,
-) -> Result<Transaction, ParseBigDecimalError> {
+    amount: BigDecimal,
+    comment: Option<&str>,
+    attachment_file_id: Option<&str>,
+) -> Transaction {
     let mut conn = establish_connection();
-    let amount = BigDecimal::from_str(amount_str);
-    match amount {
-        Ok(amount) => {
-            let new_tx = NewTransaction {
-                from_user_id: from.id,
-                to_user_id: to.id,
-                amount,
-            };
-            let transaction = diesel::insert_into(txs_dsl::transactions)
-                .values(&new_tx)
-                .get_result::<Transaction>(&mut conn)
-                .expect("Error creating transaction");
-            Ok(transaction)
-        }
-        Err(e) => Err(e),
-    }
+    let new_tx = NewTransaction {
+        from_user_id: from.id,
+        to_user_id: to.id,
+        amount,
+        comment: comment.map(str::to_string),
+        attachment_file_id: attachment_file_id.map(str::to_string),
+    };
+    conn.transaction(|conn| {
+        let transaction = diesel::insert_into(txs_dsl::transactions)
+            .values(&new_tx)
+            .get_result::<Transaction>(conn)?;
+        write_audit(
+            conn,
+            actor,
+            "transactions",
+            transaction.id,
+            AuditAction::Insert,
+            None,
+            Some(&transaction),
+        )?;
+        QueryResult::Ok(transaction)
+    })
+    .expect("Error creating transaction")
+}
+
+/// Помечает транзакцию удалённой; удалить может только одна из её сторон
+pub fn soft_delete_transaction(actor: &User, transaction_id: i32) -> QueryResult<Transaction> {
+    let mut conn = establish_connection();
+    conn.transaction(|conn| {
+        let before: Transaction = txs_dsl::transactions
+            .filter(txs_dsl::id.eq(transaction_id))
+            .filter(
+                txs_dsl::from_user_id
+                    .eq(actor.id)
+                    .or(txs_dsl::to_user_id.eq(actor.id)),
+            )
+            .filter(txs_dsl::deleted_at.is_null())
+            .first(conn)?;
+        let after: Transaction =
+            diesel::update(txs_dsl::transactions.filter(txs_dsl::id.eq(before.id)))
+                .set(txs_dsl::deleted_at.eq(diesel::dsl::now))
+                .get_result(conn)?;
+        write_audit(
+            conn,
+            actor,
+            "transactions",
+            after.id,
+            AuditAction::Delete,
+            Some(&before),
+            Some(&after),
+        )?;
+        Ok(after)
+    })
+}
+
+/// Неудалённые транзакции между `user` и `contact`, новые сверху
+pub fn find_transactions_between(
+    user: &User,
+    contact: &User,
+    limit: i64,
+) -> QueryResult<Vec<Transaction>> {
+    let mut conn = establish_connection();
+    txs_dsl::transactions
+        .filter(
+            (txs_dsl::from_user_id
+                .eq(user.id)
+                .and(txs_dsl::to_user_id.eq(contact.id)))
+            .or(txs_dsl::from_user_id
+                .eq(contact.id)
+                .and(txs_dsl::to_user_id.eq(user.id))),
+        )
+        .filter(txs_dsl::deleted_at.is_null())
+        .order(txs_dsl::created_at.desc())
+        .limit(limit)
+        .load(&mut conn)
+}
+
+/// Транзакция по id, если `user` — одна из её сторон
+pub fn find_transaction_for_user(user: &User, transaction_id: i32) -> QueryResult<Transaction> {
+    let mut conn = establish_connection();
+    txs_dsl::transactions
+        .filter(txs_dsl::id.eq(transaction_id))
+        .filter(
+            txs_dsl::from_user_id
+                .eq(user.id)
+                .or(txs_dsl::to_user_id.eq(user.id)),
+        )
+        .first(&mut conn)
+}
+
+/// Записи журнала изменений по строке таблицы вместе с username автора, по порядку
+pub fn find_audit_entries(
+    table_name: &str,
+    record_id: i32,
+) -> QueryResult<Vec<(AuditEntry, Option<String>)>> {
+    let mut conn = establish_connection();
+    audit_dsl::audit_log
+        .left_join(users_dsl::users_t.on(audit_dsl::actor_id.eq(users_dsl::id.nullable())))
+        .filter(audit_dsl::table_name.eq(table_name))
+        .filter(audit_dsl::record_id.eq(record_id))
+        .order(audit_dsl::id.asc())
+        .select((
+            AuditEntry::as_select(),
+            users_dsl::telegram_username.nullable(),
+        ))
+        .load(&mut conn)
+}
+
+/// Дописывает строку в журнал изменений; вызывается в той же DB-транзакции, что и само изменение
+fn write_audit<T: Serialize>(
+    conn: &mut PgConnection,
+    actor: &User,
+    table_name: &str,
+    record_id: i32,
+    action: AuditAction,
+    before: Option<&T>,
+    after: Option<&T>,
+) -> QueryResult<()> {
+    let to_json = |value: Option<&T>| value.and_then(|v| serde_json::to_value(v).ok());
+    let entry = NewAuditEntry {
+        actor_id: Some(actor.id),
+        table_name,
+        record_id,
+        action,
+        before: to_json(before),
+        after: to_json(after),
+    };
+    diesel::insert_into(audit_dsl::audit_log)
+        .values(&entry)
+        .execute(conn)?;
+    Ok(())
 }
 
 pub fn set_user_status(user: &User, new_status: &InputtingStatus) -> QueryResult<User> {
@@ -118,19 +299,28 @@ pub fn set_user_status(user: &User, new_status: &InputtingStatus) -> QueryResult
         .get_result(&mut conn)
 }
 
-pub fn edit_contact(
-    user: &User,
-    contact: &User,
-    contact_new_name: &String,
-) -> QueryResult<Contact> {
+pub fn edit_contact(user: &User, contact: &User, contact_new_name: &str) -> QueryResult<Contact> {
     let mut conn = establish_connection();
-    diesel::update(
-        contacts_dsl::contacts
+    conn.transaction(|conn| {
+        let before: Contact = contacts_dsl::contacts
             .filter(contacts_dsl::user_id.eq(user.id))
-            .filter(contacts_dsl::contact_id.eq(contact.id)),
-    )
-    .set(contacts_dsl::name.eq(contact_new_name))
-    .get_result(&mut conn)
+            .filter(contacts_dsl::contact_id.eq(contact.id))
+            .first(conn)?;
+        let after: Contact =
+            diesel::update(contacts_dsl::contacts.filter(contacts_dsl::id.eq(before.id)))
+                .set(contacts_dsl::name.eq(contact_new_name))
+                .get_result(conn)?;
+        write_audit(
+            conn,
+            user,
+            "contacts",
+            after.id,
+            AuditAction::Update,
+            Some(&before),
+            Some(&after),
+        )?;
+        Ok(after)
+    })
 }
 
 pub fn set_selected_contact(user: &User, contact_id: i32) -> QueryResult<User> {
@@ -151,18 +341,20 @@ pub fn get_selected_contact(user: &User) -> QueryResult<User> {
     }
 }
 
-pub fn find_user_by_contact_name(user: &User, contact_name: &str) -> QueryResult<User> {
+/// Пользователь-контакт `user` по его id; чужой или удалённый контакт не находится
+pub fn find_contact_user_by_id(user: &User, contact_id: i32) -> QueryResult<User> {
     let mut conn = establish_connection();
     contacts_dsl::contacts
         .inner_join(users_dsl::users_t.on(contacts_dsl::contact_id.eq(users_dsl::id)))
         .filter(contacts_dsl::user_id.eq(user.id))
-        .filter(contacts_dsl::name.eq(contact_name))
-        .select(users_dsl::users_t::all_columns())
-        .first::<User>(&mut conn)
+        .filter(contacts_dsl::contact_id.eq(contact_id))
+        .filter(users_dsl::deleted_at.is_null())
+        .select(User::as_select())
+        .first(&mut conn)
 }
 
 pub fn set_selected_transaction_duration(user: &User, direction: i32) -> QueryResult<User> {
-    let mut conn: PgConnection = establish_connection();
+    let mut conn = establish_connection();
     diesel::update(users_dsl::users_t.filter(users_dsl::id.eq(user.id)))
         .set(users_dsl::selected_transaction_duration.eq(direction))
         .get_result(&mut conn)
@@ -170,21 +362,24 @@ pub fn set_selected_transaction_duration(user: &User, direction: i32) -> QueryRe
 
 /// Возвращает сводку сумм, которые пользователь `user` перевёл каждому контакту,
 /// где ключ — имя контакта, а значение — сумма переводов (BigDecimal).
-pub fn get_debit(user: &User) -> QueryResult<Vec<(String, BigDecimal)>> {
+/// С `tag` учитываются только транзакции, помеченные этим тегом.
+pub fn get_debit(user: &User, tag: Option<&Tag>) -> QueryResult<Vec<(String, BigDecimal)>> {
     let mut conn = establish_connection();
+    let tagged = transaction_tags_dsl::transaction_tags
+        .filter(transaction_tags_dsl::tag_id.eq(tag.map(|tag| tag.id).unwrap_or_default()))
+        .select(transaction_tags_dsl::transaction_id);
     let rows: Vec<(Option<String>, Option<BigDecimal>)> = contacts_dsl::contacts
         .filter(contacts_dsl::user_id.eq(user.id))
-        .inner_join(
-            txs_dsl::transactions.on(
-                contacts_dsl::contact_id.eq(txs_dsl::to_user_id),
-            )
-        )
+        .inner_join(txs_dsl::transactions.on(contacts_dsl::contact_id.eq(txs_dsl::to_user_id)))
         .filter(txs_dsl::from_user_id.eq(user.id))
+        .filter(txs_dsl::deleted_at.is_null())
+        .filter(
+            tag.is_none()
+                .into_sql::<diesel::sql_types::Bool>()
+                .or(txs_dsl::id.eq_any(tagged)),
+        )
         .group_by(contacts_dsl::name)
-        .select((
-            contacts_dsl::name,
-            diesel::dsl::sum(txs_dsl::amount),
-        ))
+        .select((contacts_dsl::name, diesel::dsl::sum(txs_dsl::amount)))
         .load(&mut conn)?;
     let summary = rows
         .into_iter()
@@ -196,3 +391,1400 @@ pub fn get_debit(user: &User) -> QueryResult<Vec<(String, BigDecimal)>> {
         .collect();
     Ok(summary)
 }
+
+pub fn set_selected_transaction(user: &User, transaction_id: i32) -> QueryResult<User> {
+    let mut conn = establish_connection();
+    diesel::update(users_dsl::users_t.filter(users_dsl::id.eq(user.id)))
+        .set(users_dsl::selected_transaction_id.eq(transaction_id))
+        .get_result(&mut conn)
+}
+
+/// Имя, под которым `contact` записан у `user`, либо его telegram username
+pub fn get_contact_display_name(user: &User, contact: &User) -> String {
+    let mut conn = establish_connection();
+    contacts_dsl::contacts
+        .filter(contacts_dsl::user_id.eq(user.id))
+        .filter(contacts_dsl::contact_id.eq(contact.id))
+        .select(contacts_dsl::name)
+        .first::<Option<String>>(&mut conn)
+        .ok()
+        .flatten()
+        .unwrap_or_else(|| match contact.deleted_at {
+            Some(_) => t!(user.locale(), "deleted-user"),
+            None => contact.telegram_username.clone(),
+        })
+}
+
+/// Чистый баланс с контактом: сколько `contact` должен `user` (отрицательный — должен сам `user`)
+pub fn get_balance(user: &User, contact: &User) -> QueryResult<BigDecimal> {
+    let mut conn = establish_connection();
+    let sum_between = |conn: &mut PgConnection, from: i32, to: i32| {
+        txs_dsl::transactions
+            .filter(txs_dsl::from_user_id.eq(from))
+            .filter(txs_dsl::to_user_id.eq(to))
+            .filter(txs_dsl::deleted_at.is_null())
+            .select(diesel::dsl::sum(txs_dsl::amount))
+            .first::<Option<BigDecimal>>(conn)
+            .map(|sum| sum.unwrap_or_else(|| BigDecimal::from(0)))
+    };
+    let lent = sum_between(&mut conn, user.id, contact.id)?;
+    let borrowed = sum_between(&mut conn, contact.id, user.id)?;
+    Ok(lent - borrowed)
+}
+
+/// Ставит срок возврата и заново планирует напоминания обеим сторонам на `remind_at`.
+/// Срок определяет и пени, поэтому менять его может только кредитор
+pub fn set_transaction_due_date(
+    actor: &User,
+    transaction_id: i32,
+    due_date: NaiveDate,
+    remind_at: DateTime<Utc>,
+) -> QueryResult<Transaction> {
+    let mut conn = establish_connection();
+    conn.transaction(|conn| {
+        let before: Transaction = txs_dsl::transactions
+            .filter(txs_dsl::id.eq(transaction_id))
+            .filter(txs_dsl::from_user_id.eq(actor.id))
+            .filter(txs_dsl::deleted_at.is_null())
+            .first(conn)?;
+        let after: Transaction =
+            diesel::update(txs_dsl::transactions.filter(txs_dsl::id.eq(before.id)))
+                .set(txs_dsl::due_date.eq(due_date))
+                .get_result(conn)?;
+        write_audit(
+            conn,
+            actor,
+            "transactions",
+            after.id,
+            AuditAction::Update,
+            Some(&before),
+            Some(&after),
+        )?;
+        diesel::delete(reminders_dsl::reminders.filter(reminders_dsl::transaction_id.eq(after.id)))
+            .execute(conn)?;
+        let new_reminders = [after.from_user_id, after.to_user_id].map(|user_id| NewReminder {
+            transaction_id: after.id,
+            user_id,
+            remind_at,
+        });
+        diesel::insert_into(reminders_dsl::reminders)
+            .values(&new_reminders[..])
+            .execute(conn)?;
+        Ok(after)
+    })
+}
+
+/// Напоминания, время которых уже наступило, вместе с их транзакциями
+pub fn find_due_reminders(now: DateTime<Utc>) -> QueryResult<Vec<(Reminder, Transaction)>> {
+    let mut conn = establish_connection();
+    reminders_dsl::reminders
+        .inner_join(txs_dsl::transactions)
+        .filter(reminders_dsl::remind_at.le(now))
+        .order(reminders_dsl::remind_at.asc())
+        .select((Reminder::as_select(), Transaction::as_select()))
+        .load(&mut conn)
+}
+
+pub fn reschedule_reminder(reminder: &Reminder, remind_at: DateTime<Utc>) -> QueryResult<usize> {
+    let mut conn = establish_connection();
+    diesel::update(reminders_dsl::reminders.filter(reminders_dsl::id.eq(reminder.id)))
+        .set(reminders_dsl::remind_at.eq(remind_at))
+        .execute(&mut conn)
+}
+
+pub fn delete_reminder(reminder: &Reminder) -> QueryResult<usize> {
+    let mut conn = establish_connection();
+    diesel::delete(reminders_dsl::reminders.filter(reminders_dsl::id.eq(reminder.id)))
+        .execute(&mut conn)
+}
+
+pub fn set_reminder_frequency(user: &User, frequency: ReminderFrequency) -> QueryResult<User> {
+    let mut conn = establish_connection();
+    diesel::update(users_dsl::users_t.filter(users_dsl::id.eq(user.id)))
+        .set(users_dsl::reminder_frequency.eq(frequency))
+        .get_result(&mut conn)
+}
+
+/// Тихие часы по UTC; `None` отключает их
+pub fn set_quiet_hours(user: &User, quiet_hours: Option<(i16, i16)>) -> QueryResult<User> {
+    let mut conn = establish_connection();
+    diesel::update(users_dsl::users_t.filter(users_dsl::id.eq(user.id)))
+        .set((
+            users_dsl::quiet_hours_start.eq(quiet_hours.map(|(start, _)| start)),
+            users_dsl::quiet_hours_end.eq(quiet_hours.map(|(_, end)| end)),
+        ))
+        .get_result(&mut conn)
+}
+
+#[derive(QueryableByName)]
+struct OpenDebt {
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    id: i32,
+    #[diesel(sql_type = diesel::sql_types::Numeric)]
+    remaining: BigDecimal,
+}
+
+/// Непогашенные долги, которые `lender` дал `borrower`, старые сверху, с остатком каждого.
+/// Возвраты гасят долги по порядку (FIFO), как в [`get_average_repay_days`]
+pub fn find_open_debts(
+    lender: &User,
+    borrower: &User,
+) -> QueryResult<Vec<(Transaction, BigDecimal)>> {
+    let mut conn = establish_connection();
+    let open_debts = diesel::sql_query(format!(
+        "{DEBT_FLOWS_CTE} \
+         SELECT debt.id, LEAST(transactions.amount, debt.cumulative - repaid.total) AS remaining \
+         FROM debts debt JOIN transactions ON transactions.id = debt.id \
+         CROSS JOIN ( \
+             SELECT COALESCE(SUM(amount), 0) AS total FROM transactions \
+             WHERE from_user_id = $2 AND to_user_id = $1 AND deleted_at IS NULL \
+         ) repaid \
+         WHERE debt.from_user_id = $1 AND debt.to_user_id = $2 \
+           AND debt.cumulative - repaid.total > 0 \
+         ORDER BY debt.created_at, debt.id"
+    ))
+    .bind::<diesel::sql_types::Integer, _>(lender.id)
+    .bind::<diesel::sql_types::Integer, _>(borrower.id)
+    .load::<OpenDebt>(&mut conn)?;
+    let mut remaining: HashMap<i32, BigDecimal> = open_debts
+        .into_iter()
+        .map(|debt| (debt.id, debt.remaining))
+        .collect();
+    let transactions: Vec<Transaction> = txs_dsl::transactions
+        .filter(txs_dsl::id.eq_any(remaining.keys().copied().collect::<Vec<_>>()))
+        .order((txs_dsl::created_at.asc(), txs_dsl::id.asc()))
+        .load(&mut conn)?;
+    Ok(transactions
+        .into_iter()
+        .filter_map(|transaction| {
+            let remaining = remaining.remove(&transaction.id)?;
+            Some((transaction, remaining))
+        })
+        .collect())
+}
+
+/// Записывает напоминание от `from` к `to`, если предыдущее было не позже `since`, и
+/// возвращает его id; иначе — `Err` со временем предыдущего. Строка `from` блокируется
+/// до конца транзакции, поэтому из двух быстрых нажатий проходит только одно
+pub fn claim_nudge(
+    from: &User,
+    to: &User,
+    since: DateTime<Utc>,
+) -> QueryResult<Result<i32, DateTime<Utc>>> {
+    let mut conn = establish_connection();
+    conn.transaction(|conn| {
+        users_dsl::users_t
+            .filter(users_dsl::id.eq(from.id))
+            .select(users_dsl::id)
+            .for_update()
+            .first::<i32>(conn)?;
+        let last_nudge_at: Option<DateTime<Utc>> = nudges_dsl::nudges
+            .filter(nudges_dsl::from_user_id.eq(from.id))
+            .filter(nudges_dsl::to_user_id.eq(to.id))
+            .select(diesel::dsl::max(nudges_dsl::sent_at))
+            .first(conn)?;
+        if let Some(last_nudge_at) = last_nudge_at.filter(|sent_at| *sent_at > since) {
+            return Ok(Err(last_nudge_at));
+        }
+        let nudge_id = diesel::insert_into(nudges_dsl::nudges)
+            .values(&NewNudge {
+                from_user_id: from.id,
+                to_user_id: to.id,
+            })
+            .returning(nudges_dsl::id)
+            .get_result(conn)?;
+        Ok(Ok(nudge_id))
+    })
+}
+
+/// Снимает напоминание, которое не удалось доставить, чтобы можно было повторить сразу
+pub fn release_nudge(nudge_id: i32) -> QueryResult<usize> {
+    let mut conn = establish_connection();
+    diesel::delete(nudges_dsl::nudges.filter(nudges_dsl::id.eq(nudge_id))).execute(&mut conn)
+}
+
+/// Отключил ли `user` напоминания от `from`
+pub fn is_nudge_muted(user: &User, from: &User) -> QueryResult<bool> {
+    let mut conn = establish_connection();
+    diesel::select(diesel::dsl::exists(
+        nudge_mutes_dsl::nudge_mutes
+            .filter(nudge_mutes_dsl::user_id.eq(user.id))
+            .filter(nudge_mutes_dsl::muted_user_id.eq(from.id)),
+    ))
+    .get_result(&mut conn)
+}
+
+pub fn mute_nudges(user: &User, muted_user_id: i32) -> QueryResult<usize> {
+    let mut conn = establish_connection();
+    diesel::insert_into(nudge_mutes_dsl::nudge_mutes)
+        .values(&NewNudgeMute {
+            user_id: user.id,
+            muted_user_id,
+        })
+        .on_conflict_do_nothing()
+        .execute(&mut conn)
+}
+
+pub fn unmute_nudges(user: &User, muted_user_id: i32) -> QueryResult<usize> {
+    let mut conn = establish_connection();
+    diesel::delete(
+        nudge_mutes_dsl::nudge_mutes
+            .filter(nudge_mutes_dsl::user_id.eq(user.id))
+            .filter(nudge_mutes_dsl::muted_user_id.eq(muted_user_id)),
+    )
+    .execute(&mut conn)
+}
+
+pub fn create_recurring_transaction(
+    new_recurring: &NewRecurringTransaction,
+) -> QueryResult<RecurringTransaction> {
+    let mut conn = establish_connection();
+    diesel::insert_into(recurring_dsl::recurring_transactions)
+        .values(new_recurring)
+        .get_result(&mut conn)
+}
+
+/// Регулярные платежи, заведённые пользователем
+pub fn find_recurring_transactions_for_user(user: &User) -> QueryResult<Vec<RecurringTransaction>> {
+    let mut conn = establish_connection();
+    recurring_dsl::recurring_transactions
+        .filter(recurring_dsl::owner_id.eq(user.id))
+        .order(recurring_dsl::id.asc())
+        .load(&mut conn)
+}
+
+pub fn find_recurring_transaction_for_user(
+    user: &User,
+    recurring_id: i32,
+) -> QueryResult<RecurringTransaction> {
+    let mut conn = establish_connection();
+    recurring_dsl::recurring_transactions
+        .filter(recurring_dsl::id.eq(recurring_id))
+        .filter(recurring_dsl::owner_id.eq(user.id))
+        .first(&mut conn)
+}
+
+/// Ставит на паузу или возобновляет; при возобновлении `next_run_at` пересчитывается вызывающим
+pub fn set_recurring_transaction_paused(
+    user: &User,
+    recurring_id: i32,
+    paused: bool,
+    next_run_at: DateTime<Utc>,
+) -> QueryResult<RecurringTransaction> {
+    let mut conn = establish_connection();
+    diesel::update(
+        recurring_dsl::recurring_transactions
+            .filter(recurring_dsl::id.eq(recurring_id))
+            .filter(recurring_dsl::owner_id.eq(user.id)),
+    )
+    .set((
+        recurring_dsl::paused.eq(paused),
+        recurring_dsl::next_run_at.eq(next_run_at),
+    ))
+    .get_result(&mut conn)
+}
+
+pub fn delete_recurring_transaction(user: &User, recurring_id: i32) -> QueryResult<usize> {
+    let mut conn = establish_connection();
+    diesel::delete(
+        recurring_dsl::recurring_transactions
+            .filter(recurring_dsl::id.eq(recurring_id))
+            .filter(recurring_dsl::owner_id.eq(user.id)),
+    )
+    .execute(&mut conn)
+}
+
+pub fn find_due_recurring_transactions(
+    now: DateTime<Utc>,
+) -> QueryResult<Vec<RecurringTransaction>> {
+    let mut conn = establish_connection();
+    recurring_dsl::recurring_transactions
+        .filter(recurring_dsl::paused.eq(false))
+        .filter(recurring_dsl::next_run_at.le(now))
+        .load(&mut conn)
+}
+
+/// Создаёт очередную транзакцию по шаблону и сдвигает его `next_run_at` — атомарно,
+/// чтобы после падения одна и та же транзакция не создалась дважды
+pub fn run_recurring_transaction(
+    recurring: &RecurringTransaction,
+    next_run_at: DateTime<Utc>,
+) -> QueryResult<Transaction> {
+    let mut conn = establish_connection();
+    conn.transaction(|conn| {
+        let owner: User = users_dsl::users_t
+            .filter(users_dsl::id.eq(recurring.owner_id))
+            .first(conn)?;
+        let new_tx = NewTransaction {
+            from_user_id: recurring.from_user_id,
+            to_user_id: recurring.to_user_id,
+            amount: recurring.amount.clone(),
+            comment: recurring.comment.clone(),
+            attachment_file_id: None,
+        };
+        // Дата записи — плановая, даже если бот догоняет пропущенные запуски
+        let transaction = diesel::insert_into(txs_dsl::transactions)
+            .values((&new_tx, txs_dsl::created_at.eq(recurring.next_run_at)))
+            .get_result::<Transaction>(conn)?;
+        write_audit(
+            conn,
+            &owner,
+            "transactions",
+            transaction.id,
+            AuditAction::Insert,
+            None,
+            Some(&transaction),
+        )?;
+        diesel::update(
+            recurring_dsl::recurring_transactions.filter(recurring_dsl::id.eq(recurring.id)),
+        )
+        .set(recurring_dsl::next_run_at.eq(next_run_at))
+        .execute(conn)?;
+        Ok(transaction)
+    })
+}
+
+/// Меняет условия процентов; доступно только кредитору
+pub fn set_transaction_interest(
+    actor: &User,
+    transaction_id: i32,
+    rule: &InterestRule,
+) -> QueryResult<Transaction> {
+    let mut conn = establish_connection();
+    let (kind, rate, fee) = rule.to_columns();
+    conn.transaction(|conn| {
+        let before: Transaction = txs_dsl::transactions
+            .filter(txs_dsl::id.eq(transaction_id))
+            .filter(txs_dsl::from_user_id.eq(actor.id))
+            .filter(txs_dsl::deleted_at.is_null())
+            .first(conn)?;
+        let after: Transaction =
+            diesel::update(txs_dsl::transactions.filter(txs_dsl::id.eq(before.id)))
+                .set((
+                    txs_dsl::interest_kind.eq(kind),
+                    txs_dsl::interest_rate.eq(rate),
+                    txs_dsl::late_fee.eq(fee),
+                ))
+                .get_result(conn)?;
+        write_audit(
+            conn,
+            actor,
+            "transactions",
+            after.id,
+            AuditAction::Update,
+            Some(&before),
+            Some(&after),
+        )?;
+        Ok(after)
+    })
+}
+
+#[derive(QueryableByName)]
+struct DebtRepaidAt {
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    id: i32,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Timestamptz>)]
+    repaid_at: Option<DateTime<Utc>>,
+}
+
+pub struct InterestBearingDebt {
+    /// Имя контакта
+    pub name: Option<String>,
+    pub transaction: Transaction,
+    /// Когда долг погашен (FIFO, как в [`get_average_repay_days`]); после этого
+    /// проценты не начисляются
+    pub repaid_at: Option<DateTime<Utc>>,
+}
+
+/// Неудалённые долги `user` перед контактами с процентами или пеней.
+/// Возвраты встречного долга в список не попадают
+pub fn find_interest_bearing_debts(
+    user: &User,
+    tag: Option<&Tag>,
+) -> QueryResult<Vec<InterestBearingDebt>> {
+    let mut conn = establish_connection();
+    let tagged = transaction_tags_dsl::transaction_tags
+        .filter(transaction_tags_dsl::tag_id.eq(tag.map(|tag| tag.id).unwrap_or_default()))
+        .select(transaction_tags_dsl::transaction_id);
+    let debts: Vec<(Option<String>, Transaction)> = contacts_dsl::contacts
+        .filter(contacts_dsl::user_id.eq(user.id))
+        .inner_join(txs_dsl::transactions.on(contacts_dsl::contact_id.eq(txs_dsl::to_user_id)))
+        .filter(txs_dsl::from_user_id.eq(user.id))
+        .filter(txs_dsl::deleted_at.is_null())
+        .filter(txs_dsl::interest_kind.ne(InterestKind::None))
+        .filter(
+            tag.is_none()
+                .into_sql::<diesel::sql_types::Bool>()
+                .or(txs_dsl::id.eq_any(tagged)),
+        )
+        .select((contacts_dsl::name, Transaction::as_select()))
+        .load(&mut conn)?;
+    let mut repaid_at: HashMap<i32, Option<DateTime<Utc>>> = diesel::sql_query(format!(
+        "{DEBT_FLOWS_CTE} \
+         SELECT debt.id, repaid.at AS repaid_at FROM debts debt {REPAID_AT_LATERAL} \
+         WHERE debt.from_user_id = $1"
+    ))
+    .bind::<diesel::sql_types::Integer, _>(user.id)
+    .load::<DebtRepaidAt>(&mut conn)?
+    .into_iter()
+    .map(|debt| (debt.id, debt.repaid_at))
+    .collect();
+    Ok(debts
+        .into_iter()
+        .filter_map(|(name, transaction)| {
+            Some(InterestBearingDebt {
+                repaid_at: repaid_at.remove(&transaction.id)?,
+                name,
+                transaction,
+            })
+        })
+        .collect())
+}
+
+/// Все транзакции пользователя, включая удалённые, старые сверху.
+/// Можно ограничить контактом и полуинтервалом дат `[since, until)`.
+pub fn find_transactions_for_export(
+    user: &User,
+    contact: Option<&User>,
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+) -> QueryResult<Vec<Transaction>> {
+    let mut conn = establish_connection();
+    let mut query = txs_dsl::transactions
+        .filter(
+            txs_dsl::from_user_id
+                .eq(user.id)
+                .or(txs_dsl::to_user_id.eq(user.id)),
+        )
+        .into_boxed();
+    if let Some(contact) = contact {
+        query = query.filter(
+            txs_dsl::from_user_id
+                .eq(contact.id)
+                .or(txs_dsl::to_user_id.eq(contact.id)),
+        );
+    }
+    if let Some(since) = since {
+        query = query.filter(txs_dsl::created_at.ge(since));
+    }
+    if let Some(until) = until {
+        query = query.filter(txs_dsl::created_at.lt(until));
+    }
+    query.order(txs_dsl::created_at.asc()).load(&mut conn)
+}
+
+/// Ищет контакт пользователя по внутреннему имени или telegram username (с «@» или без)
+pub fn find_contact_user(user: &User, name_or_username: &str) -> QueryResult<User> {
+    let mut conn = establish_connection();
+    let username = name_or_username.trim_start_matches('@');
+    contacts_dsl::contacts
+        .inner_join(users_dsl::users_t.on(contacts_dsl::contact_id.eq(users_dsl::id)))
+        .filter(contacts_dsl::user_id.eq(user.id))
+        .filter(
+            contacts_dsl::name
+                .eq(name_or_username)
+                .or(users_dsl::telegram_username.eq(username)),
+        )
+        .select(User::as_select())
+        .first(&mut conn)
+}
+
+/// Контакты пользователя вместе с внутренними именами, кроме удалённых аккаунтов
+pub fn find_named_contacts(user: &User) -> QueryResult<Vec<(User, Option<String>)>> {
+    let mut conn = establish_connection();
+    contacts_dsl::contacts
+        .inner_join(users_dsl::users_t.on(contacts_dsl::contact_id.eq(users_dsl::id)))
+        .filter(contacts_dsl::user_id.eq(user.id))
+        .filter(users_dsl::deleted_at.is_null())
+        .select((User::as_select(), contacts_dsl::name))
+        .load(&mut conn)
+}
+
+/// Контакты, у которых имя или @username начинается с `prefix`, без учёта регистра,
+/// вместе с балансами — одним запросом, как в [`find_contacts_page`]. Условие совпадает
+/// с индексами на `lower(...)`, поэтому подходит для inline-запросов на каждый символ
+pub fn search_contacts_by_prefix(
+    user: &User,
+    prefix: &str,
+    limit: i64,
+) -> QueryResult<Vec<ContactListEntry>> {
+    let mut conn = establish_connection();
+    let escaped = escape_like(&prefix.trim().trim_start_matches('@').to_lowercase());
+    diesel::sql_query(contact_entries_query(
+        CONTACT_PREFIX_WHERE,
+        ContactSort::Recent,
+    ))
+    .bind::<diesel::sql_types::Integer, _>(user.id)
+    .bind::<diesel::sql_types::Text, _>(format!("{escaped}%"))
+    .bind::<diesel::sql_types::BigInt, _>(0)
+    .bind::<diesel::sql_types::BigInt, _>(limit)
+    .load(&mut conn)
+}
+
+pub fn find_users_by_ids(ids: &[i32]) -> QueryResult<Vec<User>> {
+    let mut conn = establish_connection();
+    users_dsl::users_t
+        .filter(users_dsl::id.eq_any(ids))
+        .load(&mut conn)
+}
+
+/// Сохраняет проверенные строки импорта до подтверждения пользователем
+pub fn create_import_batch(
+    user: &User,
+    transactions: &[NewImportedTransaction],
+) -> QueryResult<ImportBatch> {
+    let mut conn = establish_connection();
+    let rows = serde_json::to_value(transactions)
+        .map_err(|err| diesel::result::Error::SerializationError(Box::new(err)))?;
+    diesel::insert_into(imports_dsl::import_batches)
+        .values(&NewImportBatch {
+            user_id: user.id,
+            rows,
+        })
+        .get_result(&mut conn)
+}
+
+/// Вставляет все строки ожидающего импорта одной DB-транзакцией: либо все, либо ни одной
+pub fn apply_import_batch(user: &User, batch_id: i32) -> QueryResult<usize> {
+    let mut conn = establish_connection();
+    conn.transaction(|conn| {
+        let batch: ImportBatch = imports_dsl::import_batches
+            .filter(imports_dsl::id.eq(batch_id))
+            .filter(imports_dsl::user_id.eq(user.id))
+            .filter(imports_dsl::status.eq(ImportStatus::Pending))
+            .for_update()
+            .first(conn)?;
+        let mut rows: Vec<NewImportedTransaction> = serde_json::from_value(batch.rows)
+            .map_err(|err| diesel::result::Error::DeserializationError(Box::new(err)))?;
+        for row in rows.iter_mut() {
+            row.import_batch_id = Some(batch.id);
+        }
+        let transactions: Vec<Transaction> = diesel::insert_into(txs_dsl::transactions)
+            .values(&rows)
+            .get_results(conn)?;
+        for transaction in &transactions {
+            write_audit(
+                conn,
+                user,
+                "transactions",
+                transaction.id,
+                AuditAction::Insert,
+                None,
+                Some(transaction),
+            )?;
+        }
+        diesel::update(imports_dsl::import_batches.filter(imports_dsl::id.eq(batch.id)))
+            .set(imports_dsl::status.eq(ImportStatus::Imported))
+            .execute(conn)?;
+        Ok(transactions.len())
+    })
+}
+
+/// Удаляет ещё не подтверждённый импорт
+pub fn discard_import_batch(user: &User, batch_id: i32) -> QueryResult<usize> {
+    let mut conn = establish_connection();
+    diesel::delete(
+        imports_dsl::import_batches
+            .filter(imports_dsl::id.eq(batch_id))
+            .filter(imports_dsl::user_id.eq(user.id))
+            .filter(imports_dsl::status.eq(ImportStatus::Pending)),
+    )
+    .execute(&mut conn)
+}
+
+/// Удаляет импорты, которые создали раньше `before` и так и не подтвердили и не отменили
+pub fn delete_stale_import_batches(before: DateTime<Utc>) -> QueryResult<usize> {
+    let mut conn = establish_connection();
+    diesel::delete(
+        imports_dsl::import_batches
+            .filter(imports_dsl::status.eq(ImportStatus::Pending))
+            .filter(imports_dsl::created_at.lt(before)),
+    )
+    .execute(&mut conn)
+}
+
+/// Откатывает импорт целиком: все его транзакции помечаются удалёнными
+pub fn revert_import_batch(user: &User, batch_id: i32) -> QueryResult<usize> {
+    let mut conn = establish_connection();
+    conn.transaction(|conn| {
+        let batch: ImportBatch = imports_dsl::import_batches
+            .filter(imports_dsl::id.eq(batch_id))
+            .filter(imports_dsl::user_id.eq(user.id))
+            .filter(imports_dsl::status.eq(ImportStatus::Imported))
+            .for_update()
+            .first(conn)?;
+        let before: Vec<Transaction> = txs_dsl::transactions
+            .filter(txs_dsl::import_batch_id.eq(batch.id))
+            .filter(txs_dsl::deleted_at.is_null())
+            .load(conn)?;
+        let after: Vec<Transaction> = diesel::update(
+            txs_dsl::transactions
+                .filter(txs_dsl::import_batch_id.eq(batch.id))
+                .filter(txs_dsl::deleted_at.is_null()),
+        )
+        .set(txs_dsl::deleted_at.eq(diesel::dsl::now))
+        .get_results(conn)?;
+        for transaction in &after {
+            let previous = before.iter().find(|old| old.id == transaction.id);
+            write_audit(
+                conn,
+                user,
+                "transactions",
+                transaction.id,
+                AuditAction::Delete,
+                previous,
+                Some(transaction),
+            )?;
+        }
+        diesel::update(imports_dsl::import_batches.filter(imports_dsl::id.eq(batch.id)))
+            .set(imports_dsl::status.eq(ImportStatus::Reverted))
+            .execute(conn)?;
+        Ok(after.len())
+    })
+}
+
+/// Последние применённые импорты пользователя
+pub fn find_imported_batches(user: &User, limit: i64) -> QueryResult<Vec<ImportBatch>> {
+    let mut conn = establish_connection();
+    imports_dsl::import_batches
+        .filter(imports_dsl::user_id.eq(user.id))
+        .filter(imports_dsl::status.eq(ImportStatus::Imported))
+        .order(imports_dsl::created_at.desc())
+        .limit(limit)
+        .load(&mut conn)
+}
+
+/// Восстанавливает резервную копию одной DB-транзакцией. Повторная загрузка безопасна:
+/// существующие контакты не трогаются, совпадающие транзакции считаются дублями.
+pub fn restore_backup(user: &User, backup: &Backup) -> QueryResult<RestoreSummary> {
+    let mut conn = establish_connection();
+    conn.transaction(|conn| {
+        let mut summary = RestoreSummary::default();
+        let frequency = backup
+            .settings
+            .reminder_frequency
+            .parse::<ReminderFrequency>()
+            .unwrap_or(ReminderFrequency::Daily);
+        diesel::update(users_dsl::users_t.filter(users_dsl::id.eq(user.id)))
+            .set((
+                users_dsl::reminder_frequency.eq(frequency),
+                users_dsl::quiet_hours_start.eq(backup.settings.quiet_hours_start),
+                users_dsl::quiet_hours_end.eq(backup.settings.quiet_hours_end),
+            ))
+            .execute(conn)?;
+        // Пользователей из файла не создаём: контакт восстанавливается, только если
+        // такой пользователь уже есть в боте, иначе файл мог бы завести кого угодно
+        for backup_contact in &backup.contacts {
+            let Some(contact_user) = find_active_user(conn, backup_contact.telegram_id)? else {
+                summary.skipped += 1;
+                continue;
+            };
+            let inserted: Option<Contact> = diesel::insert_into(contacts_dsl::contacts)
+                .values(&NewNamedContact {
+                    user_id: user.id,
+                    contact_id: contact_user.id,
+                    name: backup_contact.name.clone(),
+                })
+                .on_conflict((contacts_dsl::user_id, contacts_dsl::contact_id))
+                .do_nothing()
+                .get_result(conn)
+                .optional()?;
+            if let Some(contact) = inserted {
+                write_audit(
+                    conn,
+                    user,
+                    "contacts",
+                    contact.id,
+                    AuditAction::Insert,
+                    None,
+                    Some(&contact),
+                )?;
+                summary.contacts += 1;
+            }
+        }
+        // Долги — только с теми, кто уже в контактах у владельца копии
+        for backup_tx in &backup.transactions {
+            let counterparty = find_active_user(conn, backup_tx.counterparty_telegram_id)?;
+            let Some(counterparty) = counterparty else {
+                summary.skipped += 1;
+                continue;
+            };
+            let is_contact: bool = diesel::select(diesel::dsl::exists(
+                contacts_dsl::contacts
+                    .filter(contacts_dsl::user_id.eq(user.id))
+                    .filter(contacts_dsl::contact_id.eq(counterparty.id)),
+            ))
+            .get_result(conn)?;
+            if !is_contact {
+                summary.skipped += 1;
+                continue;
+            }
+            let (from_user_id, to_user_id) = if backup_tx.direction == "lent" {
+                (user.id, counterparty.id)
+            } else {
+                (counterparty.id, user.id)
+            };
+            let duplicate: bool = diesel::select(diesel::dsl::exists(
+                txs_dsl::transactions
+                    .filter(txs_dsl::from_user_id.eq(from_user_id))
+                    .filter(txs_dsl::to_user_id.eq(to_user_id))
+                    .filter(txs_dsl::amount.eq(&backup_tx.amount))
+                    .filter(txs_dsl::created_at.eq(backup_tx.created_at)),
+            ))
+            .get_result(conn)?;
+            if duplicate {
+                summary.duplicates += 1;
+                continue;
+            }
+            let transaction: Transaction = diesel::insert_into(txs_dsl::transactions)
+                .values(&NewRestoredTransaction {
+                    from_user_id,
+                    to_user_id,
+                    amount: backup_tx.amount.clone(),
+                    comment: backup_tx.comment.clone(),
+                    created_at: backup_tx.created_at,
+                    deleted_at: backup_tx.deleted_at,
+                    due_date: backup_tx.due_date,
+                    interest_kind: backup_tx
+                        .interest_kind
+                        .parse()
+                        .unwrap_or(InterestKind::None),
+                    interest_rate: backup_tx.interest_rate.clone(),
+                    late_fee: backup_tx.late_fee.clone(),
+                })
+                .get_result(conn)?;
+            write_audit(
+                conn,
+                user,
+                "transactions",
+                transaction.id,
+                AuditAction::Insert,
+                None,
+                Some(&transaction),
+            )?;
+            summary.transactions += 1;
+        }
+        Ok(summary)
+    })
+}
+
+/// Существующий и не удалённый пользователь с таким telegram_id
+fn find_active_user(conn: &mut PgConnection, telegram_id: i64) -> QueryResult<Option<User>> {
+    users_dsl::users_t
+        .filter(users_dsl::telegram_id.eq(telegram_id))
+        .filter(users_dsl::deleted_at.is_null())
+        .first(conn)
+        .optional()
+}
+
+/// Username, который получает обезличенный пользователь
+pub const DELETED_USERNAME: &str = "deleted_user";
+
+/// Удаляет персональные данные пользователя. Строка `users_t` остаётся обезличенной заглушкой,
+/// чтобы транзакции и контакты его контрагентов не ломались.
+pub fn anonymize_user(user: &User) -> QueryResult<()> {
+    let mut conn = establish_connection();
+    conn.transaction(|conn| {
+        diesel::delete(contacts_dsl::contacts.filter(contacts_dsl::user_id.eq(user.id)))
+            .execute(conn)?;
+        diesel::delete(tags_dsl::tags.filter(tags_dsl::user_id.eq(user.id))).execute(conn)?;
+        diesel::delete(reminders_dsl::reminders.filter(reminders_dsl::user_id.eq(user.id)))
+            .execute(conn)?;
+        diesel::delete(
+            nudges_dsl::nudges.filter(
+                nudges_dsl::from_user_id
+                    .eq(user.id)
+                    .or(nudges_dsl::to_user_id.eq(user.id)),
+            ),
+        )
+        .execute(conn)?;
+        diesel::delete(
+            nudge_mutes_dsl::nudge_mutes.filter(
+                nudge_mutes_dsl::user_id
+                    .eq(user.id)
+                    .or(nudge_mutes_dsl::muted_user_id.eq(user.id)),
+            ),
+        )
+        .execute(conn)?;
+        diesel::delete(
+            recurring_dsl::recurring_transactions.filter(recurring_dsl::owner_id.eq(user.id)),
+        )
+        .execute(conn)?;
+        // Чужие регулярные платежи с удалённым пользователем больше не должны создавать долги
+        diesel::update(
+            recurring_dsl::recurring_transactions.filter(
+                recurring_dsl::from_user_id
+                    .eq(user.id)
+                    .or(recurring_dsl::to_user_id.eq(user.id)),
+            ),
+        )
+        .set(recurring_dsl::paused.eq(true))
+        .execute(conn)?;
+        let batch_ids = imports_dsl::import_batches
+            .filter(imports_dsl::user_id.eq(user.id))
+            .select(imports_dsl::id)
+            .load::<i32>(conn)?;
+        diesel::update(txs_dsl::transactions.filter(txs_dsl::import_batch_id.eq_any(batch_ids)))
+            .set(txs_dsl::import_batch_id.eq(None::<i32>))
+            .execute(conn)?;
+        diesel::delete(imports_dsl::import_batches.filter(imports_dsl::user_id.eq(user.id)))
+            .execute(conn)?;
+        diesel::update(users_dsl::users_t.filter(users_dsl::id.eq(user.id)))
+            .set((
+                users_dsl::telegram_id.eq(None::<i64>),
+                users_dsl::telegram_username.eq(DELETED_USERNAME),
+                users_dsl::status.eq(InputtingStatus::None),
+                users_dsl::selected_contact_id.eq(None::<i32>),
+                users_dsl::selected_transaction_id.eq(None::<i32>),
+                users_dsl::reminder_frequency.eq(ReminderFrequency::Off),
+                users_dsl::quiet_hours_start.eq(None::<i16>),
+                users_dsl::quiet_hours_end.eq(None::<i16>),
+                users_dsl::monthly_statement.eq(false),
+                users_dsl::search_query.eq(None::<String>),
+                users_dsl::language_code.eq(None::<String>),
+                users_dsl::locale.eq(None::<Locale>),
+                users_dsl::screen_stack.eq(Vec::<Screen>::new()),
+                users_dsl::contact_search.eq(None::<String>),
+                users_dsl::deleted_at.eq(diesel::dsl::now),
+            ))
+            .execute(conn)?;
+        write_audit::<serde_json::Value>(
+            conn,
+            user,
+            "users_t",
+            user.id,
+            AuditAction::Delete,
+            None,
+            None,
+        )?;
+        // Имена в журнале стираем, а в записях о транзакциях — только комментарии:
+        // суммы остаются контрагентам на случай спора
+        diesel::sql_query("SELECT scrub_audit_log($1)")
+            .bind::<diesel::sql_types::Integer, _>(user.id)
+            .execute(conn)?;
+        Ok(())
+    })
+}
+
+pub fn set_monthly_statement(user: &User, enabled: bool) -> QueryResult<User> {
+    let mut conn = establish_connection();
+    diesel::update(users_dsl::users_t.filter(users_dsl::id.eq(user.id)))
+        .set(users_dsl::monthly_statement.eq(enabled))
+        .get_result(&mut conn)
+}
+
+/// Запоминает язык клиента Telegram. Пишет в базу, только если язык сменился
+pub fn set_language_code(tg_id_val: i64, code: &str) -> QueryResult<usize> {
+    let mut conn = establish_connection();
+    diesel::update(
+        users_dsl::users_t
+            .filter(users_dsl::telegram_id.eq(tg_id_val))
+            .filter(users_dsl::language_code.is_distinct_from(code)),
+    )
+    .set(users_dsl::language_code.eq(code))
+    .execute(&mut conn)
+}
+
+/// `None` — снова брать язык из Telegram
+pub fn set_locale(user: &User, locale: Option<Locale>) -> QueryResult<User> {
+    let mut conn = establish_connection();
+    diesel::update(users_dsl::users_t.filter(users_dsl::id.eq(user.id)))
+        .set(users_dsl::locale.eq(locale))
+        .get_result(&mut conn)
+}
+
+/// Подписанные на выписку пользователи, которым ещё не отправлена выписка за месяц `month_start`
+pub fn find_users_due_statement(month_start: NaiveDate) -> QueryResult<Vec<User>> {
+    let mut conn = establish_connection();
+    users_dsl::users_t
+        .filter(users_dsl::monthly_statement.eq(true))
+        .filter(users_dsl::deleted_at.is_null())
+        .filter(
+            users_dsl::last_statement_month
+                .is_null()
+                .or(users_dsl::last_statement_month.lt(month_start)),
+        )
+        .load(&mut conn)
+}
+
+pub fn mark_statement_sent(user: &User, month_start: NaiveDate) -> QueryResult<User> {
+    let mut conn = establish_connection();
+    diesel::update(users_dsl::users_t.filter(users_dsl::id.eq(user.id)))
+        .set(users_dsl::last_statement_month.eq(month_start))
+        .get_result(&mut conn)
+}
+
+/// Неудалённые транзакции `user` ($1) по контрагентам: `lent` — выдал он, `signed_amount` —
+/// сумма со знаком, как в балансе
+const COUNTERPARTY_FLOWS: &str = "( \
+        SELECT to_user_id AS counterparty_id, amount, amount AS signed_amount, created_at, \
+               TRUE AS lent \
+        FROM transactions WHERE from_user_id = $1 AND deleted_at IS NULL \
+        UNION ALL \
+        SELECT from_user_id, amount, -amount, created_at, FALSE \
+        FROM transactions WHERE to_user_id = $1 AND deleted_at IS NULL \
+    ) flows";
+
+/// Обороты `user` с каждым контрагентом: баланс на `since` и суммы в обе стороны за `[since, until)`.
+/// Удалённые транзакции не учитываются ни в одном из периодов
+pub fn get_statement_rows(
+    user: &User,
+    since: DateTime<Utc>,
+    until: DateTime<Utc>,
+) -> QueryResult<Vec<StatementRow>> {
+    let mut conn = establish_connection();
+    diesel::sql_query(format!(
+        "SELECT counterparty_id, \
+                COALESCE(SUM(signed_amount) FILTER (WHERE created_at < $2), 0) AS opening, \
+                COALESCE(SUM(amount) FILTER (WHERE lent AND created_at >= $2), 0) AS lent, \
+                COALESCE(SUM(amount) FILTER (WHERE NOT lent AND created_at >= $2), 0) AS borrowed \
+         FROM {COUNTERPARTY_FLOWS} \
+         WHERE created_at < $3 \
+         GROUP BY counterparty_id \
+         ORDER BY counterparty_id"
+    ))
+    .bind::<diesel::sql_types::Integer, _>(user.id)
+    .bind::<diesel::sql_types::Timestamptz, _>(since)
+    .bind::<diesel::sql_types::Timestamptz, _>(until)
+    .load(&mut conn)
+}
+
+/// Сколько `user` дал и взял в долг с момента `since` (`None` — за всё время)
+pub fn get_period_totals(
+    user: &User,
+    since: Option<DateTime<Utc>>,
+) -> QueryResult<(BigDecimal, BigDecimal)> {
+    let mut conn = establish_connection();
+    let since = since.unwrap_or(DateTime::UNIX_EPOCH);
+    let lent = txs_dsl::transactions
+        .filter(txs_dsl::from_user_id.eq(user.id))
+        .filter(txs_dsl::deleted_at.is_null())
+        .filter(txs_dsl::created_at.ge(since))
+        .select(diesel::dsl::sum(txs_dsl::amount))
+        .first::<Option<BigDecimal>>(&mut conn)?;
+    let borrowed = txs_dsl::transactions
+        .filter(txs_dsl::to_user_id.eq(user.id))
+        .filter(txs_dsl::deleted_at.is_null())
+        .filter(txs_dsl::created_at.ge(since))
+        .select(diesel::dsl::sum(txs_dsl::amount))
+        .first::<Option<BigDecimal>>(&mut conn)?;
+    let zero = || BigDecimal::from(0);
+    Ok((lent.unwrap_or_else(zero), borrowed.unwrap_or_else(zero)))
+}
+
+#[derive(QueryableByName)]
+struct CounterpartyCount {
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    counterparty_id: i32,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    count: i64,
+}
+
+/// Контрагент, с которым у `user` больше всего транзакций с момента `since`, и их число
+pub fn find_most_frequent_counterparty(
+    user: &User,
+    since: Option<DateTime<Utc>>,
+) -> QueryResult<Option<(i32, i64)>> {
+    let mut conn = establish_connection();
+    let most_frequent = diesel::sql_query(format!(
+        "SELECT counterparty_id, COUNT(*) AS count \
+         FROM {COUNTERPARTY_FLOWS} \
+         WHERE created_at >= $2 \
+         GROUP BY counterparty_id \
+         ORDER BY count DESC, counterparty_id \
+         LIMIT 1"
+    ))
+    .bind::<diesel::sql_types::Integer, _>(user.id)
+    .bind::<diesel::sql_types::Timestamptz, _>(since.unwrap_or(DateTime::UNIX_EPOCH))
+    .get_result::<CounterpartyCount>(&mut conn)
+    .optional()?;
+    Ok(most_frequent.map(|row| (row.counterparty_id, row.count)))
+}
+
+/// Сколько контрагентов сейчас с ненулевым балансом
+pub fn count_open_debts(user: &User) -> QueryResult<usize> {
+    let mut conn = establish_connection();
+    let open_debts = diesel::sql_query(format!(
+        "SELECT COUNT(*) FILTER (WHERE net <> 0) AS count \
+         FROM ( \
+             SELECT SUM(signed_amount) AS net FROM {COUNTERPARTY_FLOWS} \
+             GROUP BY counterparty_id \
+         ) balances"
+    ))
+    .bind::<diesel::sql_types::Integer, _>(user.id)
+    .get_result::<Count>(&mut conn)?;
+    Ok(open_debts.count as usize)
+}
+
+/// Транзакции `user` ($1) с нарастающей суммой в своём направлении и балансом с контрагентом
+/// до неё. Транзакция — новый долг, а не возврат, если баланс до неё не был в её пользу
+const DEBT_FLOWS_CTE: &str = "WITH txs AS ( \
+        SELECT id, from_user_id, to_user_id, created_at, \
+               SUM(amount) OVER (PARTITION BY from_user_id, to_user_id \
+                                 ORDER BY created_at, id) AS cumulative, \
+               COALESCE(SUM(CASE WHEN from_user_id = $1 THEN amount ELSE -amount END) \
+                   OVER (PARTITION BY CASE WHEN from_user_id = $1 THEN to_user_id ELSE from_user_id END \
+                         ORDER BY created_at, id \
+                         ROWS BETWEEN UNBOUNDED PRECEDING AND 1 PRECEDING), 0) AS balance_before \
+        FROM transactions \
+        WHERE deleted_at IS NULL AND (from_user_id = $1 OR to_user_id = $1) \
+    ), debts AS ( \
+        SELECT * FROM txs \
+        WHERE (from_user_id = $1 AND balance_before >= 0) \
+           OR (to_user_id = $1 AND balance_before <= 0) \
+    ) ";
+
+/// `repaid.at` — когда долг `debt` из [`DEBT_FLOWS_CTE`] погашен (FIFO): встречные платежи
+/// покрыли его вместе со всеми более ранними. `NULL`, если ещё не погашен
+const REPAID_AT_LATERAL: &str = "CROSS JOIN LATERAL ( \
+        SELECT MIN(back.created_at) AS at FROM txs back \
+        WHERE back.from_user_id = debt.to_user_id AND back.to_user_id = debt.from_user_id \
+          AND back.cumulative >= debt.cumulative AND back.created_at >= debt.created_at \
+    ) repaid";
+
+#[derive(QueryableByName)]
+struct TransactionId {
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    id: i32,
+}
+
+/// Самый крупный новый долг `user` в любую сторону с момента `since`, возвраты не учитываются
+pub fn find_largest_debt(
+    user: &User,
+    since: Option<DateTime<Utc>>,
+) -> QueryResult<Option<Transaction>> {
+    let mut conn = establish_connection();
+    let largest = diesel::sql_query(format!(
+        "{DEBT_FLOWS_CTE} \
+         SELECT debts.id FROM debts JOIN transactions ON transactions.id = debts.id \
+         WHERE debts.created_at >= $2 \
+         ORDER BY transactions.amount DESC, debts.created_at DESC LIMIT 1"
+    ))
+    .bind::<diesel::sql_types::Integer, _>(user.id)
+    .bind::<diesel::sql_types::Timestamptz, _>(since.unwrap_or(DateTime::UNIX_EPOCH))
+    .get_result::<TransactionId>(&mut conn)
+    .optional()?;
+    match largest {
+        Some(TransactionId { id }) => txs_dsl::transactions.find(id).first(&mut conn).optional(),
+        None => Ok(None),
+    }
+}
+
+#[derive(QueryableByName)]
+struct AverageDays {
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Double>)]
+    days: Option<f64>,
+}
+
+/// Среднее число дней до возврата долгов, выданных или взятых `user` с момента `since`.
+/// Долги гасятся по порядку (FIFO): долг возвращён, когда встречные платежи покрыли его
+/// вместе со всеми более ранними
+pub fn get_average_repay_days(
+    user: &User,
+    since: Option<DateTime<Utc>>,
+) -> QueryResult<Option<f64>> {
+    let mut conn = establish_connection();
+    let query = diesel::sql_query(format!(
+        "{DEBT_FLOWS_CTE} \
+         SELECT (AVG(EXTRACT(EPOCH FROM repaid.at - debt.created_at)) / 86400)::float8 AS days \
+         FROM debts debt {REPAID_AT_LATERAL} \
+         WHERE repaid.at IS NOT NULL AND debt.created_at >= $2"
+    ))
+    .bind::<diesel::sql_types::Integer, _>(user.id)
+    .bind::<diesel::sql_types::Timestamptz, _>(since.unwrap_or(DateTime::UNIX_EPOCH));
+    Ok(query.get_result::<AverageDays>(&mut conn)?.days)
+}
+
+pub fn set_screen_stack(user: &User, stack: &[Screen]) -> QueryResult<User> {
+    let mut conn = establish_connection();
+    diesel::update(users_dsl::users_t.filter(users_dsl::id.eq(user.id)))
+        .set(users_dsl::screen_stack.eq(stack))
+        .get_result(&mut conn)
+}
+
+pub fn set_contact_sort(user: &User, sort: ContactSort) -> QueryResult<User> {
+    let mut conn = establish_connection();
+    diesel::update(users_dsl::users_t.filter(users_dsl::id.eq(user.id)))
+        .set(users_dsl::contact_sort.eq(sort))
+        .get_result(&mut conn)
+}
+
+pub fn set_contact_search(user: &User, search: Option<&str>) -> QueryResult<User> {
+    let mut conn = establish_connection();
+    diesel::update(users_dsl::users_t.filter(users_dsl::id.eq(user.id)))
+        .set(users_dsl::contact_search.eq(search))
+        .get_result(&mut conn)
+}
+
+const CONTACT_PICKER_FROM: &str = "FROM contacts JOIN users_t ON users_t.id = contacts.contact_id";
+/// Контакты `$1` из выбора: имя или @username подходит под `$2` (ILIKE-шаблон)
+const CONTACT_PICKER_WHERE: &str = "\
+    WHERE contacts.user_id = $1 AND users_t.deleted_at IS NULL \
+      AND (contacts.name ILIKE $2 OR users_t.telegram_username ILIKE $2)";
+/// Контакты `$1`, у которых имя или @username начинается с `$2` (LIKE-шаблон в нижнем регистре)
+const CONTACT_PREFIX_WHERE: &str = "\
+    WHERE contacts.user_id = $1 AND users_t.deleted_at IS NULL \
+      AND (lower(contacts.name) LIKE $2 OR lower(users_t.telegram_username) LIKE $2)";
+
+#[derive(QueryableByName)]
+pub struct ContactListEntry {
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub contact_id: i32,
+    /// Имя из контактов, а без него — @username
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub name: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub telegram_username: String,
+    /// Как в [`get_balance`]: больше нуля — контакт должен `user`
+    #[diesel(sql_type = diesel::sql_types::Numeric)]
+    pub balance: BigDecimal,
+}
+
+#[derive(QueryableByName)]
+struct Count {
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    count: i64,
+}
+
+/// Страница контактов для выбора и общее число подходящих под `search`.
+/// Балансы считаются одним запросом вместе со списком
+pub fn find_contacts_page(
+    user: &User,
+    sort: ContactSort,
+    search: Option<&str>,
+    offset: i64,
+    limit: i64,
+) -> QueryResult<(Vec<ContactListEntry>, i64)> {
+    let mut conn = establish_connection();
+    let escaped = escape_like(search.unwrap_or_default().trim_start_matches('@'));
+    let pattern = format!("%{escaped}%");
+    let contacts = diesel::sql_query(contact_entries_query(CONTACT_PICKER_WHERE, sort))
+        .bind::<diesel::sql_types::Integer, _>(user.id)
+        .bind::<diesel::sql_types::Text, _>(&pattern)
+        .bind::<diesel::sql_types::BigInt, _>(offset)
+        .bind::<diesel::sql_types::BigInt, _>(limit)
+        .load::<ContactListEntry>(&mut conn)?;
+    let total = diesel::sql_query(format!(
+        "SELECT COUNT(*) AS count {CONTACT_PICKER_FROM} {CONTACT_PICKER_WHERE}"
+    ))
+    .bind::<diesel::sql_types::Integer, _>(user.id)
+    .bind::<diesel::sql_types::Text, _>(&pattern)
+    .get_result::<Count>(&mut conn)?
+    .count;
+    Ok((contacts, total))
+}
+
+/// Контакты `$1`, отобранные условием `filter` по `$2`, с балансами и сортировкой `sort`;
+/// `$3` и `$4` — OFFSET и LIMIT
+fn contact_entries_query(filter: &str, sort: ContactSort) -> String {
+    let order = match sort {
+        ContactSort::Recent => "last_activity DESC NULLS LAST",
+        ContactSort::Balance => "ABS(balance) DESC",
+    };
+    format!(
+        "SELECT * FROM ( \
+             SELECT contacts.contact_id, \
+                    COALESCE(NULLIF(contacts.name, ''), users_t.telegram_username) AS name, \
+                    users_t.telegram_username, \
+                    COALESCE(SUM(CASE WHEN txs.from_user_id = $1 THEN txs.amount \
+                                      ELSE -txs.amount END), 0) AS balance, \
+                    MAX(txs.created_at) AS last_activity \
+             {CONTACT_PICKER_FROM} \
+             LEFT JOIN transactions txs ON txs.deleted_at IS NULL \
+                  AND ((txs.from_user_id = $1 AND txs.to_user_id = contacts.contact_id) \
+                    OR (txs.from_user_id = contacts.contact_id AND txs.to_user_id = $1)) \
+             {filter} \
+             GROUP BY contacts.id, users_t.id \
+         ) picker \
+         ORDER BY {order}, name, contact_id \
+         OFFSET $3 LIMIT $4"
+    )
+}
+
+/// Экранирует `\`, `%` и `_` для шаблонов LIKE
+fn escape_like(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+pub fn set_search_query(user: &User, query: &str) -> QueryResult<User> {
+    let mut conn = establish_connection();
+    diesel::update(users_dsl::users_t.filter(users_dsl::id.eq(user.id)))
+        .set(users_dsl::search_query.eq(query))
+        .get_result(&mut conn)
+}
+
+/// Действующие транзакции `user`, подходящие под запрос `/find`, начиная с новых,
+/// и общее число найденных. `contact` — уже найденный контакт из `query.contact`
+pub fn search_transactions(
+    user: &User,
+    contact: Option<&User>,
+    query: &SearchQuery,
+    offset: i64,
+    limit: i64,
+) -> QueryResult<(Vec<Transaction>, i64)> {
+    let mut conn = establish_connection();
+    let filtered = || {
+        let mut boxed = txs_dsl::transactions
+            .filter(
+                txs_dsl::from_user_id
+                    .eq(user.id)
+                    .or(txs_dsl::to_user_id.eq(user.id)),
+            )
+            .filter(txs_dsl::deleted_at.is_null())
+            .into_boxed::<Pg>();
+        if let Some(contact) = contact {
+            boxed = boxed.filter(
+                txs_dsl::from_user_id
+                    .eq(contact.id)
+                    .or(txs_dsl::to_user_id.eq(contact.id)),
+            );
+        }
+        for word in &query.words {
+            let escaped = escape_like(word);
+            boxed = boxed.filter(txs_dsl::comment.ilike(format!("%{escaped}%")));
+        }
+        for amount in &query.amounts {
+            boxed = match amount.clone() {
+                AmountFilter::Greater(value) => boxed.filter(txs_dsl::amount.gt(value)),
+                AmountFilter::GreaterOrEqual(value) => boxed.filter(txs_dsl::amount.ge(value)),
+                AmountFilter::Less(value) => boxed.filter(txs_dsl::amount.lt(value)),
+                AmountFilter::LessOrEqual(value) => boxed.filter(txs_dsl::amount.le(value)),
+                AmountFilter::Equal(value) => boxed.filter(txs_dsl::amount.eq(value)),
+            };
+        }
+        let start_of = |date: NaiveDate| date.and_hms_opt(0, 0, 0).expect("valid time").and_utc();
+        if let Some(since) = query.since {
+            boxed = boxed.filter(txs_dsl::created_at.ge(start_of(since)));
+        }
+        if let Some(until) = query.until {
+            boxed = boxed.filter(txs_dsl::created_at.lt(start_of(until)));
+        }
+        boxed
+    };
+    let total = filtered().count().get_result(&mut conn)?;
+    let transactions = filtered()
+        .order((txs_dsl::created_at.desc(), txs_dsl::id.desc()))
+        .offset(offset)
+        .limit(limit)
+        .load(&mut conn)?;
+    Ok((transactions, total))
+}
+
+pub fn find_tags_for_user(user: &User) -> QueryResult<Vec<Tag>> {
+    let mut conn = establish_connection();
+    tags_dsl::tags
+        .filter(tags_dsl::user_id.eq(user.id))
+        .order(tags_dsl::name.asc())
+        .load(&mut conn)
+}
+
+pub fn find_tag_for_user(user: &User, tag_id: i32) -> QueryResult<Tag> {
+    let mut conn = establish_connection();
+    tags_dsl::tags
+        .filter(tags_dsl::id.eq(tag_id))
+        .filter(tags_dsl::user_id.eq(user.id))
+        .first(&mut conn)
+}
+
+pub fn find_or_create_tag(user: &User, name: &str) -> QueryResult<Tag> {
+    let mut conn = establish_connection();
+    diesel::insert_into(tags_dsl::tags)
+        .values(&NewTag {
+            user_id: user.id,
+            name,
+        })
+        .on_conflict_do_nothing()
+        .execute(&mut conn)?;
+    tags_dsl::tags
+        .filter(tags_dsl::user_id.eq(user.id))
+        .filter(tags_dsl::name.eq(name))
+        .first(&mut conn)
+}
+
+/// Id тегов `user`, которыми помечена транзакция
+pub fn find_transaction_tag_ids(user: &User, transaction_id: i32) -> QueryResult<Vec<i32>> {
+    let mut conn = establish_connection();
+    transaction_tags_dsl::transaction_tags
+        .inner_join(tags_dsl::tags)
+        .filter(transaction_tags_dsl::transaction_id.eq(transaction_id))
+        .filter(tags_dsl::user_id.eq(user.id))
+        .select(tags_dsl::id)
+        .load(&mut conn)
+}
+
+/// Ставит или снимает тег с транзакции; `true`, если тег теперь стоит
+pub fn toggle_transaction_tag(user: &User, transaction_id: i32, tag_id: i32) -> QueryResult<bool> {
+    let tag = find_tag_for_user(user, tag_id)?;
+    let transaction = find_transaction_for_user(user, transaction_id)?;
+    let mut conn = establish_connection();
+    let removed = diesel::delete(
+        transaction_tags_dsl::transaction_tags
+            .filter(transaction_tags_dsl::transaction_id.eq(transaction.id))
+            .filter(transaction_tags_dsl::tag_id.eq(tag.id)),
+    )
+    .execute(&mut conn)?;
+    if removed > 0 {
+        return Ok(false);
+    }
+    diesel::insert_into(transaction_tags_dsl::transaction_tags)
+        .values(&NewTransactionTag {
+            transaction_id: transaction.id,
+            tag_id: tag.id,
+        })
+        .execute(&mut conn)?;
+    Ok(true)
+}
+
+pub fn add_transaction_tag(user: &User, transaction_id: i32, tag: &Tag) -> QueryResult<()> {
+    let transaction = find_transaction_for_user(user, transaction_id)?;
+    let mut conn = establish_connection();
+    diesel::insert_into(transaction_tags_dsl::transaction_tags)
+        .values(&NewTransactionTag {
+            transaction_id: transaction.id,
+            tag_id: tag.id,
+        })
+        .on_conflict_do_nothing()
+        .execute(&mut conn)?;
+    Ok(())
+}
+
+/// Суммы по тегам `user`: сколько он дал и взял в долг в транзакциях с каждым тегом
+pub fn get_tag_summary(user: &User) -> QueryResult<Vec<(String, BigDecimal, BigDecimal)>> {
+    let mut conn = establish_connection();
+    let tagged = || {
+        transaction_tags_dsl::transaction_tags
+            .inner_join(tags_dsl::tags)
+            .inner_join(txs_dsl::transactions)
+            .filter(tags_dsl::user_id.eq(user.id))
+            .filter(txs_dsl::deleted_at.is_null())
+    };
+    let lent: Vec<(String, Option<BigDecimal>)> = tagged()
+        .filter(txs_dsl::from_user_id.eq(user.id))
+        .group_by(tags_dsl::name)
+        .select((tags_dsl::name, diesel::dsl::sum(txs_dsl::amount)))
+        .load(&mut conn)?;
+    let borrowed: Vec<(String, Option<BigDecimal>)> = tagged()
+        .filter(txs_dsl::to_user_id.eq(user.id))
+        .group_by(tags_dsl::name)
+        .select((tags_dsl::name, diesel::dsl::sum(txs_dsl::amount)))
+        .load(&mut conn)?;
+    let zero = || BigDecimal::from(0);
+    let mut summary: Vec<(String, BigDecimal, BigDecimal)> = lent
+        .into_iter()
+        .map(|(name, sum)| (name, sum.unwrap_or_else(zero), zero()))
+        .collect();
+    for (name, sum) in borrowed {
+        let sum = sum.unwrap_or_else(zero);
+        match summary
+            .iter_mut()
+            .find(|(existing, _, _)| *existing == name)
+        {
+            Some((_, _, total)) => *total += sum,
+            None => summary.push((name, zero(), sum)),
+        }
+    }
+    summary.sort_by(|(a, _, _), (b, _, _)| a.cmp(b));
+    Ok(summary)
+}
@@ -0,0 +1,129 @@
+use crate::amount;
+use crate::currency;
+use crate::db_util;
+use crate::models::Transaction;
+use crate::models::User;
+use crate::BoxError;
+use bigdecimal::RoundingMode;
+use chrono::DateTime;
+use chrono::NaiveDate;
+use chrono::Utc;
+use std::collections::HashMap;
+
+/// Фильтры `/export`: контакт и полуинтервал дат `[since, until)`
+#[derive(Debug, Default)]
+pub struct ExportFilter {
+    pub contact: Option<String>,
+    pub since: Option<NaiveDate>,
+    pub until: Option<NaiveDate>,
+}
+
+impl ExportFilter {
+    /// Разбирает аргументы вида `alice since:2026-01-01 until:2026-02-01`; `None` — если дата не разобралась
+    pub fn parse(args: &str) -> Option<ExportFilter> {
+        let mut filter = ExportFilter::default();
+        let mut contact_words = vec![];
+        for word in args.split_whitespace() {
+            if let Some(date) = word.strip_prefix("since:") {
+                filter.since = Some(NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?);
+            } else if let Some(date) = word.strip_prefix("until:") {
+                filter.until = Some(NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?);
+            } else {
+                contact_words.push(word);
+            }
+        }
+        if !contact_words.is_empty() {
+            filter.contact = Some(contact_words.join(" "));
+        }
+        Some(filter)
+    }
+}
+
+/// CSV со всеми транзакциями пользователя, включая удалённые (их `status` — `deleted`)
+pub fn transactions_csv(user: &User, filter: &ExportFilter) -> Result<Vec<u8>, BoxError> {
+    let contact = match &filter.contact {
+        Some(name) => Some(db_util::find_contact_user(user, name)?),
+        None => None,
+    };
+    let start_of =
+        |date: NaiveDate| -> DateTime<Utc> { date.and_hms_opt(0, 0, 0).unwrap().and_utc() };
+    let transactions = db_util::find_transactions_for_export(
+        user,
+        contact.as_ref(),
+        filter.since.map(start_of),
+        filter.until.map(start_of),
+    )?;
+    let counterparty_ids = transactions
+        .iter()
+        .map(|transaction| counterparty_id(user, transaction))
+        .collect::<Vec<_>>();
+    let counterparties: HashMap<i32, User> = db_util::find_users_by_ids(&counterparty_ids)?
+        .into_iter()
+        .map(|counterparty| (counterparty.id, counterparty))
+        .collect();
+    let names: HashMap<i32, String> = db_util::find_all_contacts_for_user(user)
+        .into_iter()
+        .filter_map(|contact| contact.name.map(|name| (contact.contact_id, name)))
+        .collect();
+
+    let mut writer = csv::Writer::from_writer(vec![]);
+    writer.write_record([
+        "date",
+        "counterparty_name",
+        "counterparty_username",
+        "direction",
+        "amount",
+        "currency",
+        "comment",
+        "status",
+    ])?;
+    for transaction in &transactions {
+        let counterparty_id = counterparty_id(user, transaction);
+        let username = counterparties
+            .get(&counterparty_id)
+            .map(|counterparty| counterparty.telegram_username.as_str())
+            .unwrap_or_default();
+        let name = names
+            .get(&counterparty_id)
+            .map(String::as_str)
+            .unwrap_or(username);
+        let direction = if transaction.from_user_id == user.id {
+            "lent"
+        } else {
+            "borrowed"
+        };
+        let status = if transaction.deleted_at.is_some() {
+            "deleted"
+        } else {
+            "active"
+        };
+        writer.write_record([
+            transaction
+                .created_at
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string()
+                .as_str(),
+            name,
+            username,
+            direction,
+            // Ровно два знака: «5.000» из NUMERIC импорт на pl/ru прочёл бы как пять тысяч
+            transaction
+                .amount
+                .with_scale_round(amount::SCALE, RoundingMode::HalfUp)
+                .to_string()
+                .as_str(),
+            currency(),
+            transaction.comment.as_deref().unwrap_or_default(),
+            status,
+        ])?;
+    }
+    Ok(writer.into_inner()?)
+}
+
+fn counterparty_id(user: &User, transaction: &Transaction) -> i32 {
+    if transaction.from_user_id == user.id {
+        transaction.to_user_id
+    } else {
+        transaction.from_user_id
+    }
+}
@@ -0,0 +1,311 @@
+//! Арифметика в поле суммы: «120/3», «45+12,5-3», «(30+15)*2». Считается точно
+//! в `BigDecimal`, без чисел с плавающей точкой; результат округляется до копеек.
+
+use crate::amount;
+use crate::locale::Locale;
+use bigdecimal::BigDecimal;
+use bigdecimal::RoundingMode;
+use bigdecimal::Zero;
+
+/// Глубже скобки не вкладывают; ограничение защищает стек от «((((((…»
+const MAX_NESTING: usize = 16;
+/// Знаки, по которым ввод узнаётся как выражение. «x» и кириллическая «х» — умножение
+const OPERATORS: [char; 12] = ['+', '-', '−', '*', '×', 'x', 'х', '/', '÷', ':', '(', ')'];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExpressionError {
+    Invalid,
+    DivisionByZero,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(BigDecimal),
+    Plus,
+    Minus,
+    Times,
+    Divide,
+    Open,
+    Close,
+}
+
+/// Есть ли в тексте действие. Минус в самом начале — это знак, а не действие
+pub fn has_operator(text: &str) -> bool {
+    text.trim().chars().skip(1).any(|c| OPERATORS.contains(&c))
+}
+
+/// Значение выражения, округлённое до копеек по правилу «половина вверх».
+/// Промежуточные результаты деления не округляются, поэтому `1/3*3` даёт ровно 1
+pub fn evaluate(text: &str, locale: Locale) -> Result<BigDecimal, ExpressionError> {
+    let tokens = tokenize(text, locale)?;
+    let mut parser = Parser {
+        tokens: &tokens,
+        position: 0,
+        depth: 0,
+    };
+    let value = parser.expression()?;
+    if parser.position != tokens.len() {
+        return Err(ExpressionError::Invalid);
+    }
+    Ok(value.with_scale_round(amount::SCALE, RoundingMode::HalfUp))
+}
+
+fn tokenize(text: &str, locale: Locale) -> Result<Vec<Token>, ExpressionError> {
+    let chars: Vec<char> = text.trim().to_lowercase().chars().collect();
+    let mut tokens = vec![];
+    let mut index = 0;
+    while index < chars.len() {
+        let c = chars[index];
+        let token = match c {
+            c if c.is_whitespace() => {
+                index += 1;
+                continue;
+            }
+            '0'..='9' | '.' | ',' => {
+                // Число вместе с разрядными пробелами: «1 000 + 500»
+                let start = index;
+                while index < chars.len() {
+                    let c = chars[index];
+                    let is_group_space =
+                        c.is_whitespace() && chars.get(index + 1).is_some_and(char::is_ascii_digit);
+                    if !(c.is_ascii_digit() || c == '.' || c == ',' || is_group_space) {
+                        break;
+                    }
+                    index += 1;
+                }
+                let number: String = chars[start..index].iter().collect();
+                let value =
+                    amount::parse_number(&number, locale).map_err(|_| ExpressionError::Invalid)?;
+                tokens.push(Token::Number(value));
+                continue;
+            }
+            '+' => Token::Plus,
+            '-' | '−' => Token::Minus,
+            '*' | '×' | 'x' | 'х' => Token::Times,
+            '/' | '÷' | ':' => Token::Divide,
+            '(' => Token::Open,
+            ')' => Token::Close,
+            _ => return Err(ExpressionError::Invalid),
+        };
+        tokens.push(token);
+        index += 1;
+    }
+    Ok(tokens)
+}
+
+/// Рекурсивный спуск: сумма из произведений, произведение из множителей
+struct Parser<'a> {
+    tokens: &'a [Token],
+    position: usize,
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn next_if(&mut self, expected: &Token) -> bool {
+        if self.tokens.get(self.position) == Some(expected) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expression(&mut self) -> Result<BigDecimal, ExpressionError> {
+        let mut value = self.term()?;
+        loop {
+            if self.next_if(&Token::Plus) {
+                value += self.term()?;
+            } else if self.next_if(&Token::Minus) {
+                value -= self.term()?;
+            } else {
+                return Ok(value);
+            }
+        }
+    }
+
+    fn term(&mut self) -> Result<BigDecimal, ExpressionError> {
+        let mut value = self.factor()?;
+        loop {
+            if self.next_if(&Token::Times) {
+                value *= self.factor()?;
+            } else if self.next_if(&Token::Divide) {
+                let divisor = self.factor()?;
+                if divisor.is_zero() {
+                    return Err(ExpressionError::DivisionByZero);
+                }
+                value = value / divisor;
+            } else {
+                return Ok(value);
+            }
+        }
+    }
+
+    fn factor(&mut self) -> Result<BigDecimal, ExpressionError> {
+        if self.next_if(&Token::Minus) {
+            return self.nested(|parser| parser.factor()).map(|value| -value);
+        }
+        if self.next_if(&Token::Open) {
+            let value = self.nested(|parser| parser.expression())?;
+            if !self.next_if(&Token::Close) {
+                return Err(ExpressionError::Invalid);
+            }
+            return Ok(value);
+        }
+        match self.tokens.get(self.position) {
+            Some(Token::Number(value)) => {
+                self.position += 1;
+                Ok(value.clone())
+            }
+            _ => Err(ExpressionError::Invalid),
+        }
+    }
+
+    fn nested(
+        &mut self,
+        parse: impl FnOnce(&mut Self) -> Result<BigDecimal, ExpressionError>,
+    ) -> Result<BigDecimal, ExpressionError> {
+        if self.depth >= MAX_NESTING {
+            return Err(ExpressionError::Invalid);
+        }
+        self.depth += 1;
+        let value = parse(self);
+        self.depth -= 1;
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn value(text: &str) -> BigDecimal {
+        BigDecimal::from_str(text).unwrap()
+    }
+
+    fn evaluated(text: &str) -> BigDecimal {
+        evaluate(text, Locale::En).unwrap_or_else(|error| panic!("{text:?}: {error:?}"))
+    }
+
+    #[test]
+    fn evaluates_simple_operations() {
+        assert_eq!(evaluated("120/3"), value("40"));
+        assert_eq!(evaluated("45+12.5-3"), value("54.5"));
+        assert_eq!(evaluated("45 + 12,5 - 3"), value("54.5"));
+        assert_eq!(evaluated("3*40"), value("120"));
+        assert_eq!(evaluated("3 x 40"), value("120"));
+        assert_eq!(evaluated("3х40"), value("120"));
+        assert_eq!(evaluated("3×40"), value("120"));
+        assert_eq!(evaluated("90÷4"), value("22.5"));
+        assert_eq!(evaluated("90:4"), value("22.5"));
+        assert_eq!(evaluated("50−20"), value("30"));
+    }
+
+    #[test]
+    fn multiplication_and_division_bind_tighter() {
+        assert_eq!(evaluated("2+3*4"), value("14"));
+        assert_eq!(evaluated("2*3+4"), value("10"));
+        assert_eq!(evaluated("100-60/3"), value("80"));
+        assert_eq!(evaluated("10+20/2*3"), value("40"));
+        assert_eq!(evaluated("(2+3)*4"), value("20"));
+        assert_eq!(evaluated("((10+20))/(1+2)"), value("10"));
+    }
+
+    #[test]
+    fn operators_are_left_associative() {
+        assert_eq!(evaluated("10-2-3"), value("5"));
+        assert_eq!(evaluated("100/5/2"), value("10"));
+        assert_eq!(evaluated("8/4*2"), value("4"));
+    }
+
+    #[test]
+    fn supports_unary_minus() {
+        assert_eq!(evaluated("-5+20"), value("15"));
+        assert_eq!(evaluated("20*-1"), value("-20"));
+        assert_eq!(evaluated("--5"), value("5"));
+    }
+
+    #[test]
+    fn division_rounds_half_up_to_cents() {
+        assert_eq!(evaluated("100/3"), value("33.33"));
+        assert_eq!(evaluated("200/3"), value("66.67"));
+        assert_eq!(evaluated("10/4"), value("2.5"));
+        assert_eq!(evaluated("1/8"), value("0.13"));
+        assert_eq!(evaluated("0.05/2"), value("0.03"));
+        assert_eq!(evaluated("-1/8"), value("-0.13"));
+    }
+
+    #[test]
+    fn rounds_only_the_result() {
+        assert_eq!(evaluated("1/3*3"), value("1"));
+        assert_eq!(evaluated("100/3+100/3+100/3"), value("100"));
+        assert_eq!(evaluated("0.1+0.2"), value("0.3"));
+        assert_eq!(evaluated("10/3*3"), value("10"));
+    }
+
+    #[test]
+    fn accepts_amount_style_numbers() {
+        assert_eq!(evaluated("1 000 + 500"), value("1500"));
+        assert_eq!(evaluated("1,000/4"), value("250"));
+        assert_eq!(evaluated("1.234,56*2"), value("2469.12"));
+    }
+
+    #[test]
+    fn rejects_division_by_zero() {
+        assert_eq!(
+            evaluate("5/0", Locale::En),
+            Err(ExpressionError::DivisionByZero)
+        );
+        assert_eq!(
+            evaluate("5/(2-2)", Locale::En),
+            Err(ExpressionError::DivisionByZero)
+        );
+        assert_eq!(
+            evaluate("5/0,00", Locale::En),
+            Err(ExpressionError::DivisionByZero)
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_expressions() {
+        for text in [
+            "", "+", "5+", "*5", "5**2", "5//2", "(5+2", "5+2)", "()", "5 5", "5+abc", "2^3",
+            "1e3*2", "12..5+1", "1.2345*2",
+        ] {
+            assert_eq!(
+                evaluate(text, Locale::En),
+                Err(ExpressionError::Invalid),
+                "{text:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn limits_nesting() {
+        let nested = format!("{}1{}", "(".repeat(MAX_NESTING), ")".repeat(MAX_NESTING));
+        assert_eq!(evaluated(&nested), value("1"));
+        let too_deep = format!(
+            "{}1{}",
+            "(".repeat(MAX_NESTING + 1),
+            ")".repeat(MAX_NESTING + 1)
+        );
+        assert_eq!(
+            evaluate(&too_deep, Locale::En),
+            Err(ExpressionError::Invalid)
+        );
+        assert_eq!(
+            evaluate(&"-".repeat(1000), Locale::En),
+            Err(ExpressionError::Invalid)
+        );
+    }
+
+    #[test]
+    fn detects_operators() {
+        assert!(has_operator("120/3"));
+        assert!(has_operator("45 + 12"));
+        assert!(has_operator("3x40"));
+        assert!(!has_operator("-5"));
+        assert!(!has_operator("1 000,50"));
+        assert!(!has_operator("12.5 zł"));
+    }
+}
@@ -0,0 +1,161 @@
+use crate::locale::Locale;
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::FluentArgs;
+use fluent_bundle::FluentResource;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// Каталоги сообщений встраиваются в бинарник, как и миграции
+const CATALOGS: [(Locale, &str); 3] = [
+    (Locale::En, include_str!("../locales/en.ftl")),
+    (Locale::Pl, include_str!("../locales/pl.ftl")),
+    (Locale::Ru, include_str!("../locales/ru.ftl")),
+];
+
+static BUNDLES: LazyLock<HashMap<Locale, FluentBundle<FluentResource>>> = LazyLock::new(|| {
+    CATALOGS
+        .iter()
+        .map(|(locale, source)| (*locale, build_bundle(*locale, source)))
+        .collect()
+});
+
+fn build_bundle(locale: Locale, source: &str) -> FluentBundle<FluentResource> {
+    let resource = FluentResource::try_new(source.to_string())
+        .unwrap_or_else(|(_, errors)| panic!("ERROR parsing {locale}.ftl: {errors:?}"));
+    let language = locale.to_string().parse().expect("ERROR invalid locale");
+    let mut bundle = FluentBundle::new_concurrent(vec![language]);
+    // Невидимые символы изоляции направления текста Telegram показал бы как мусор
+    bundle.set_use_isolating(false);
+    bundle
+        .add_resource(resource)
+        .unwrap_or_else(|errors| panic!("ERROR loading {locale}.ftl: {errors:?}"));
+    bundle
+}
+
+/// Сообщение `key` на языке `locale`. Если ключа нет, возвращается сам ключ:
+/// бот продолжает работать, а пропуск ловит тест каталогов
+pub fn translate(locale: Locale, key: &str, args: Option<&FluentArgs>) -> String {
+    let bundle = &BUNDLES[&locale];
+    let Some(pattern) = bundle.get_message(key).and_then(|message| message.value()) else {
+        log::error!("Missing message {key} in {locale}.ftl");
+        return key.to_string();
+    };
+    let mut errors = vec![];
+    let text = bundle.format_pattern(pattern, args, &mut errors);
+    if !errors.is_empty() {
+        log::error!("Error formatting {key} in {locale}.ftl: {errors:?}");
+    }
+    text.into_owned()
+}
+
+/// `t!(locale, "key")` или `t!(locale, "key", name = value, ...)`.
+/// Ключ — всегда строковый литерал, чтобы тест мог найти его в исходниках
+macro_rules! t {
+    ($locale:expr, $key:literal) => {
+        $crate::i18n::translate($locale, $key, None)
+    };
+    ($locale:expr, $key:literal, $($name:ident = $value:expr),+ $(,)?) => {{
+        let mut args = fluent_bundle::FluentArgs::new();
+        $(args.set(stringify!($name), fluent_bundle::FluentValue::from($value));)+
+        $crate::i18n::translate($locale, $key, Some(&args))
+    }};
+}
+
+pub(crate) use t;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fluent_syntax::ast;
+    use fluent_syntax::parser;
+    use std::collections::BTreeSet;
+    use std::fs;
+
+    fn message_ids(source: &str) -> BTreeSet<String> {
+        let resource = parser::parse(source).expect("catalog parses");
+        resource
+            .body
+            .iter()
+            .filter_map(|entry| match entry {
+                ast::Entry::Message(message) => Some(message.id.name.to_string()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Все ключи из вызовов `t!(locale, "key"...)` в `src/`
+    fn used_keys() -> BTreeSet<String> {
+        let mut keys = BTreeSet::new();
+        for entry in fs::read_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/src")).unwrap() {
+            let source = fs::read_to_string(entry.unwrap().path()).unwrap();
+            for (position, _) in source.match_indices("t!(") {
+                // format!( и прочие макросы, оканчивающиеся на «t!(», не считаются
+                let previous = source[..position].chars().next_back();
+                if previous.is_some_and(|c| c.is_alphanumeric() || c == '_') {
+                    continue;
+                }
+                let call = &source[position..];
+                let Some(start) = call.find('"') else {
+                    continue;
+                };
+                let key = &call[start + 1..];
+                let key = &key[..key.find('"').unwrap()];
+                let is_key = key
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+                // Вызовы в комментариях и сам этот сканер ключей не содержат
+                if !key.is_empty() && is_key && key != "key" {
+                    keys.insert(key.to_string());
+                }
+            }
+        }
+        keys
+    }
+
+    #[test]
+    fn catalogs_load() {
+        for locale in Locale::ALL {
+            assert!(BUNDLES.contains_key(&locale));
+        }
+    }
+
+    #[test]
+    fn every_used_key_is_translated() {
+        let used = used_keys();
+        assert!(!used.is_empty());
+        for (locale, source) in CATALOGS {
+            let ids = message_ids(source);
+            let missing: Vec<_> = used.difference(&ids).collect();
+            assert!(missing.is_empty(), "{locale}.ftl is missing {missing:?}");
+        }
+    }
+
+    #[test]
+    fn catalogs_have_the_same_keys() {
+        let (_, reference) = CATALOGS[2];
+        let reference = message_ids(reference);
+        for (locale, source) in CATALOGS {
+            let ids = message_ids(source);
+            let missing: Vec<_> = reference.difference(&ids).collect();
+            let extra: Vec<_> = ids.difference(&reference).collect();
+            assert!(missing.is_empty(), "{locale}.ftl is missing {missing:?}");
+            assert!(extra.is_empty(), "{locale}.ftl has unknown {extra:?}");
+        }
+    }
+
+    #[test]
+    fn formats_arguments_and_plurals() {
+        assert_eq!(t!(Locale::Ru, "import-rows", count = 1), "1 строка");
+        assert_eq!(t!(Locale::Ru, "import-rows", count = 3), "3 строки");
+        assert_eq!(t!(Locale::Ru, "import-rows", count = 11), "11 строк");
+        assert_eq!(t!(Locale::Pl, "import-rows", count = 2), "2 wiersze");
+        assert_eq!(t!(Locale::Pl, "import-rows", count = 5), "5 wierszy");
+        assert_eq!(t!(Locale::En, "import-rows", count = 1), "1 row");
+        assert_eq!(t!(Locale::En, "import-rows", count = 7), "7 rows");
+    }
+
+    #[test]
+    fn unknown_key_falls_back_to_key() {
+        assert_eq!(translate(Locale::En, "no-such-key", None), "no-such-key");
+    }
+}
@@ -0,0 +1,266 @@
+//! Импорт исторических долгов из CSV или JSON.
+//! Формат совпадает с `/export`: колонки `date`, `counterparty_name` или `counterparty_username`
+//! (либо просто `counterparty`), `direction` (`lent`/`borrowed` или «дал»/«взял»), `amount`, `comment`.
+//! Строки со `status` = `deleted` пропускаются.
+
+use crate::amount;
+use crate::amount::AmountError;
+use crate::db_util;
+use crate::i18n::t;
+use crate::locale::Locale;
+use crate::models::NewImportedTransaction;
+use crate::models::User;
+use bigdecimal::BigDecimal;
+use chrono::DateTime;
+use chrono::NaiveDate;
+use chrono::NaiveDateTime;
+use chrono::Utc;
+use std::collections::HashMap;
+use std::fmt;
+
+pub const MAX_FILE_SIZE: u32 = 1024 * 1024;
+const MAX_ROWS: usize = 1000;
+
+/// Что не так с файлом целиком или с одной его строкой
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImportErrorKind {
+    TooManyRows,
+    CsvHeader,
+    CsvRecord { line: usize },
+    NotJsonArray,
+    NoCounterparty,
+    NoDirection,
+    InvalidDirection(String),
+    NoAmount,
+    NotPositiveAmount,
+    InvalidAmount(String),
+    InvalidDate(String),
+    UnknownContact(String),
+}
+
+impl ImportErrorKind {
+    pub fn message(&self, locale: Locale) -> String {
+        match self {
+            ImportErrorKind::TooManyRows => {
+                t!(locale, "import-error-too-many-rows", max = MAX_ROWS)
+            }
+            ImportErrorKind::CsvHeader => t!(locale, "import-error-csv-header"),
+            ImportErrorKind::CsvRecord { line } => t!(locale, "import-error-csv-row", line = *line),
+            ImportErrorKind::NotJsonArray => t!(locale, "import-error-not-json"),
+            ImportErrorKind::NoCounterparty => t!(locale, "import-error-no-counterparty"),
+            ImportErrorKind::NoDirection => t!(locale, "import-error-no-direction"),
+            ImportErrorKind::InvalidDirection(direction) => {
+                t!(
+                    locale,
+                    "import-error-direction",
+                    direction = direction.as_str()
+                )
+            }
+            ImportErrorKind::NoAmount => t!(locale, "import-error-no-amount"),
+            ImportErrorKind::NotPositiveAmount => t!(locale, "import-error-not-positive"),
+            ImportErrorKind::InvalidAmount(amount) => {
+                t!(locale, "import-error-amount", amount = amount.as_str())
+            }
+            ImportErrorKind::InvalidDate(date) => {
+                t!(locale, "import-error-date", date = date.as_str())
+            }
+            ImportErrorKind::UnknownContact(name) => {
+                t!(locale, "import-error-no-contact", name = name.as_str())
+            }
+        }
+    }
+}
+
+/// Для журналов; пользователю показывается `message`
+impl fmt::Display for ImportErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message(Locale::En))
+    }
+}
+
+impl std::error::Error for ImportErrorKind {}
+
+#[derive(Debug)]
+pub struct ImportError {
+    /// Номер строки CSV (с заголовком) или порядковый номер записи JSON, с 1
+    pub line: usize,
+    pub kind: ImportErrorKind,
+}
+
+/// Результат проверки файла: готовые к вставке транзакции и ошибки по строкам
+#[derive(Debug, Default)]
+pub struct ImportPreview {
+    pub total: usize,
+    pub skipped: usize,
+    pub transactions: Vec<NewImportedTransaction>,
+    pub errors: Vec<ImportError>,
+}
+
+/// Строка файла: номер и значения по именам колонок в нижнем регистре
+type Record = (usize, HashMap<String, String>);
+
+struct ImportRow {
+    counterparty: String,
+    lent: bool,
+    amount: BigDecimal,
+    date: Option<DateTime<Utc>>,
+    comment: Option<String>,
+}
+
+/// Разбирает файл и сопоставляет контрагентов с контактами `user`; в БД ничего не пишет
+pub fn preview(
+    user: &User,
+    file_name: &str,
+    content: &[u8],
+) -> Result<ImportPreview, ImportErrorKind> {
+    let records = if file_name.to_lowercase().ends_with(".json") {
+        read_json(content)?
+    } else {
+        read_csv(content)?
+    };
+    if records.len() > MAX_ROWS {
+        return Err(ImportErrorKind::TooManyRows);
+    }
+    let mut preview = ImportPreview {
+        total: records.len(),
+        ..ImportPreview::default()
+    };
+    let mut contacts: HashMap<String, Option<User>> = HashMap::new();
+    let now = Utc::now();
+    for (line, record) in records {
+        if record.get("status").map(String::as_str) == Some("deleted") {
+            preview.skipped += 1;
+            continue;
+        }
+        let row = match parse_record(&record, user.locale()) {
+            Ok(row) => row,
+            Err(kind) => {
+                preview.errors.push(ImportError { line, kind });
+                continue;
+            }
+        };
+        let contact = contacts
+            .entry(row.counterparty.clone())
+            .or_insert_with(|| db_util::find_contact_user(user, &row.counterparty).ok());
+        let Some(contact) = contact else {
+            preview.errors.push(ImportError {
+                line,
+                kind: ImportErrorKind::UnknownContact(row.counterparty.clone()),
+            });
+            continue;
+        };
+        let (from_user_id, to_user_id) = if row.lent {
+            (user.id, contact.id)
+        } else {
+            (contact.id, user.id)
+        };
+        preview.transactions.push(NewImportedTransaction {
+            from_user_id,
+            to_user_id,
+            amount: row.amount,
+            comment: row.comment,
+            created_at: row.date.unwrap_or(now),
+            import_batch_id: None,
+        });
+    }
+    Ok(preview)
+}
+
+fn read_csv(content: &[u8]) -> Result<Vec<Record>, ImportErrorKind> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(content);
+    let headers = reader
+        .headers()
+        .map_err(|_| ImportErrorKind::CsvHeader)?
+        .iter()
+        .map(|header| header.to_lowercase())
+        .collect::<Vec<_>>();
+    let mut records = vec![];
+    for (index, record) in reader.records().enumerate() {
+        let record = record.map_err(|_| ImportErrorKind::CsvRecord { line: index + 2 })?;
+        let values = headers
+            .iter()
+            .cloned()
+            .zip(record.iter().map(str::to_string))
+            .collect();
+        records.push((index + 2, values));
+    }
+    Ok(records)
+}
+
+fn read_json(content: &[u8]) -> Result<Vec<Record>, ImportErrorKind> {
+    let items: Vec<serde_json::Map<String, serde_json::Value>> =
+        serde_json::from_slice(content).map_err(|_| ImportErrorKind::NotJsonArray)?;
+    Ok(items
+        .into_iter()
+        .enumerate()
+        .map(|(index, item)| {
+            let values = item
+                .into_iter()
+                .filter_map(|(key, value)| {
+                    let value = match value {
+                        serde_json::Value::String(text) => text,
+                        serde_json::Value::Number(number) => number.to_string(),
+                        _ => return None,
+                    };
+                    Some((key.to_lowercase(), value.trim().to_string()))
+                })
+                .collect();
+            (index + 1, values)
+        })
+        .collect())
+}
+
+fn parse_record(
+    record: &HashMap<String, String>,
+    locale: Locale,
+) -> Result<ImportRow, ImportErrorKind> {
+    let field = |name: &str| {
+        record
+            .get(name)
+            .map(String::as_str)
+            .filter(|value| !value.is_empty())
+    };
+    let counterparty = field("counterparty_username")
+        .map(|username| format!("@{}", username.trim_start_matches('@')))
+        .or_else(|| field("counterparty_name").map(str::to_string))
+        .or_else(|| field("counterparty").map(str::to_string))
+        .ok_or(ImportErrorKind::NoCounterparty)?;
+    let lent = match field("direction").map(str::to_lowercase).as_deref() {
+        Some("lent") | Some("дал") => true,
+        Some("borrowed") | Some("взял") => false,
+        Some(other) => return Err(ImportErrorKind::InvalidDirection(other.to_string())),
+        None => return Err(ImportErrorKind::NoDirection),
+    };
+    let amount_text = field("amount").ok_or(ImportErrorKind::NoAmount)?;
+    let amount = amount::parse_amount(amount_text, locale).map_err(|error| match error {
+        AmountError::NotPositive => ImportErrorKind::NotPositiveAmount,
+        _ => ImportErrorKind::InvalidAmount(amount_text.to_string()),
+    })?;
+    let date = match field("date") {
+        Some(text) => {
+            Some(parse_date(text).ok_or_else(|| ImportErrorKind::InvalidDate(text.to_string()))?)
+        }
+        None => None,
+    };
+    Ok(ImportRow {
+        counterparty,
+        lent,
+        amount,
+        date,
+        comment: field("comment").map(str::to_string),
+    })
+}
+
+fn parse_date(text: &str) -> Option<DateTime<Utc>> {
+    if let Ok(date_time) = NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S") {
+        return Some(date_time.and_utc());
+    }
+    ["%Y-%m-%d", "%d.%m.%Y"]
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(text, format).ok())
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|date_time| date_time.and_utc())
+}
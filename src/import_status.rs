@@ -0,0 +1,16 @@
+use diesel_derive_enum::DbEnum;
+use strum_macros::Display;
+use strum_macros::EnumString;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, DbEnum, EnumString, Display)]
+#[ExistingTypePath = "crate::schema::sql_types::ImportStatus"]
+#[strum(serialize_all = "snake_case")]
+pub enum ImportStatus {
+    /// Файл проверен и показан пользователю, ждёт подтверждения
+    #[db_rename = "pending"]
+    Pending,
+    #[db_rename = "imported"]
+    Imported,
+    #[db_rename = "reverted"]
+    Reverted,
+}
@@ -1,11 +1,9 @@
-use diesel::deserialize::FromSql;
-use diesel::serialize::ToSql;
 use diesel_derive_enum::DbEnum;
 use strum_macros::Display;
 use strum_macros::EnumString;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, DbEnum, EnumString, Display)]
-#[ExistingTypePath = "crate::schema::sql_types::Inputting_status"]
+#[ExistingTypePath = "crate::schema::sql_types::InputtingStatus"]
 #[strum(serialize_all = "snake_case")]
 pub enum InputtingStatus {
     #[db_rename = "none"]
@@ -22,4 +20,18 @@ pub enum InputtingStatus {
     SelectContactForTransaction,
     #[db_rename = "transaction_amount"]
     TransactionAmount,
+    #[db_rename = "transaction_due_date"]
+    TransactionDueDate,
+    #[db_rename = "recurring_transaction"]
+    RecurringTransaction,
+    #[db_rename = "transaction_interest"]
+    TransactionInterest,
+    #[db_rename = "import_file"]
+    ImportFile,
+    #[db_rename = "restore_file"]
+    RestoreFile,
+    #[db_rename = "transaction_tags"]
+    TransactionTags,
+    #[db_rename = "contact_search"]
+    ContactSearch,
 }
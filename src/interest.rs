@@ -0,0 +1,297 @@
+//! Начисление процентов и пени по долгам. Модуль чистый: всё считается в `BigDecimal`
+//! от переданных дат, без обращения к БД и текущему времени.
+
+use crate::amount;
+use crate::interest_kind::InterestKind;
+use crate::locale::Locale;
+use bigdecimal::BigDecimal;
+use bigdecimal::RoundingMode;
+use bigdecimal::Zero;
+use chrono::NaiveDate;
+
+const DAYS_IN_YEAR: i64 = 365;
+/// Точность промежуточных вычислений сложных процентов, знаков после запятой
+const INTERMEDIATE_SCALE: i64 = 30;
+/// Итоговые суммы округляются до копеек/грошей
+const MONEY_SCALE: i64 = 2;
+/// Годовая ставка выше почти наверняка опечатка
+pub const MAX_ANNUAL_RATE: i64 = 1000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InterestRule {
+    None,
+    /// Простые проценты, годовая ставка в процентах
+    Simple {
+        annual_rate: BigDecimal,
+    },
+    /// Сложные проценты с ежедневной капитализацией, годовая ставка в процентах
+    Compound {
+        annual_rate: BigDecimal,
+    },
+    /// Фиксированная пеня, если долг не вернули к сроку
+    LateFee {
+        fee: BigDecimal,
+    },
+}
+
+impl InterestRule {
+    pub fn from_columns(
+        kind: InterestKind,
+        interest_rate: Option<&BigDecimal>,
+        late_fee: Option<&BigDecimal>,
+    ) -> InterestRule {
+        match (kind, interest_rate, late_fee) {
+            (InterestKind::Simple, Some(rate), _) => InterestRule::Simple {
+                annual_rate: rate.clone(),
+            },
+            (InterestKind::Compound, Some(rate), _) => InterestRule::Compound {
+                annual_rate: rate.clone(),
+            },
+            (InterestKind::LateFee, _, Some(fee)) => InterestRule::LateFee { fee: fee.clone() },
+            _ => InterestRule::None,
+        }
+    }
+
+    /// Значения колонок `interest_kind`, `interest_rate`, `late_fee`
+    pub fn to_columns(&self) -> (InterestKind, Option<BigDecimal>, Option<BigDecimal>) {
+        match self {
+            InterestRule::None => (InterestKind::None, None, None),
+            InterestRule::Simple { annual_rate } => {
+                (InterestKind::Simple, Some(annual_rate.clone()), None)
+            }
+            InterestRule::Compound { annual_rate } => {
+                (InterestKind::Compound, Some(annual_rate.clone()), None)
+            }
+            InterestRule::LateFee { fee } => (InterestKind::LateFee, None, Some(fee.clone())),
+        }
+    }
+
+    /// Сколько набежало сверх `principal` к дате `on` для долга, выданного `start`.
+    /// Проценты идут с даты выдачи, пеня — только после `due_date`.
+    pub fn accrued(
+        &self,
+        principal: &BigDecimal,
+        start: NaiveDate,
+        due_date: Option<NaiveDate>,
+        on: NaiveDate,
+    ) -> BigDecimal {
+        let days = (on - start).num_days().max(0);
+        let accrued = match self {
+            InterestRule::None => BigDecimal::zero(),
+            InterestRule::Simple { annual_rate } => {
+                principal * annual_rate * BigDecimal::from(days)
+                    / BigDecimal::from(100 * DAYS_IN_YEAR)
+            }
+            InterestRule::Compound { annual_rate } => {
+                let daily_rate = annual_rate / BigDecimal::from(100 * DAYS_IN_YEAR);
+                let factor = pow(&(BigDecimal::from(1) + daily_rate), days as u64);
+                principal * factor - principal
+            }
+            InterestRule::LateFee { fee } => match due_date {
+                Some(due_date) if on > due_date => fee.clone(),
+                _ => BigDecimal::zero(),
+            },
+        };
+        accrued.with_scale_round(MONEY_SCALE, RoundingMode::HalfUp)
+    }
+}
+
+/// Разбирает условие, присланное пользователем:
+/// «5%» — простые, «5% сложные» — сложные, «пеня 50» — пеня, «нет» — без процентов.
+/// Ключевые слова понимаются на всех языках интерфейса, числа пишутся как суммы на `locale`
+pub fn parse_rule(text: &str, locale: Locale) -> Option<InterestRule> {
+    const NONE_WORDS: [&str; 6] = ["нет", "none", "no", "nie", "0", "0%"];
+    const FEE_WORDS: [&str; 3] = ["пеня", "fee", "kara"];
+    let text = text.trim().to_lowercase();
+    if NONE_WORDS.contains(&text.as_str()) {
+        return Some(InterestRule::None);
+    }
+    if let Some(fee) = FEE_WORDS.iter().find_map(|word| text.strip_prefix(word)) {
+        let fee = amount::parse_amount(fee, locale).ok()?;
+        return Some(InterestRule::LateFee { fee });
+    }
+    let (rate, rest) = text.split_once('%')?;
+    let annual_rate = amount::parse_number(rate, locale).ok()?;
+    if annual_rate <= BigDecimal::zero() || annual_rate > BigDecimal::from(MAX_ANNUAL_RATE) {
+        return None;
+    }
+    match rest.trim() {
+        "" | "простые" | "simple" | "proste" => Some(InterestRule::Simple { annual_rate }),
+        "сложные" | "compound" | "składane" => Some(InterestRule::Compound { annual_rate }),
+        _ => None,
+    }
+}
+
+/// Возведение в целую степень с округлением промежуточных результатов
+fn pow(base: &BigDecimal, mut exponent: u64) -> BigDecimal {
+    let mut result = BigDecimal::from(1);
+    let mut base = base.clone();
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = (&result * &base).with_scale_round(INTERMEDIATE_SCALE, RoundingMode::HalfEven);
+        }
+        base = (&base * &base).with_scale_round(INTERMEDIATE_SCALE, RoundingMode::HalfEven);
+        exponent >>= 1;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn dec(value: &str) -> BigDecimal {
+        BigDecimal::from_str(value).unwrap()
+    }
+
+    #[test]
+    fn simple_interest_for_full_year() {
+        let rule = InterestRule::Simple {
+            annual_rate: dec("10"),
+        };
+        let accrued = rule.accrued(&dec("1000"), date(2025, 1, 1), None, date(2026, 1, 1));
+        assert_eq!(accrued, dec("100.00"));
+    }
+
+    #[test]
+    fn simple_interest_for_partial_period_is_rounded_half_up() {
+        let rule = InterestRule::Simple {
+            annual_rate: dec("7.5"),
+        };
+        // 1234.56 * 7.5% * 45 / 365 = 11.41504...
+        let accrued = rule.accrued(&dec("1234.56"), date(2026, 3, 1), None, date(2026, 4, 15));
+        assert_eq!(accrued, dec("11.42"));
+    }
+
+    #[test]
+    fn compound_interest_exceeds_simple() {
+        let compound = InterestRule::Compound {
+            annual_rate: dec("10"),
+        };
+        // 1000 * ((1 + 0.1/365)^365 - 1) = 105.155...
+        let accrued = compound.accrued(&dec("1000"), date(2025, 1, 1), None, date(2026, 1, 1));
+        assert_eq!(accrued, dec("105.16"));
+    }
+
+    #[test]
+    fn compound_interest_for_zero_days_is_zero() {
+        let rule = InterestRule::Compound {
+            annual_rate: dec("25"),
+        };
+        let accrued = rule.accrued(&dec("500"), date(2026, 5, 5), None, date(2026, 5, 5));
+        assert_eq!(accrued, dec("0.00"));
+    }
+
+    #[test]
+    fn interest_before_start_is_zero() {
+        let rule = InterestRule::Simple {
+            annual_rate: dec("10"),
+        };
+        let accrued = rule.accrued(&dec("1000"), date(2026, 5, 5), None, date(2026, 5, 1));
+        assert_eq!(accrued, dec("0.00"));
+    }
+
+    #[test]
+    fn late_fee_applies_only_after_due_date() {
+        let rule = InterestRule::LateFee { fee: dec("50") };
+        let due = Some(date(2026, 6, 1));
+        let principal = dec("300");
+        let start = date(2026, 5, 1);
+        assert_eq!(
+            rule.accrued(&principal, start, due, date(2026, 5, 31)),
+            dec("0.00")
+        );
+        assert_eq!(
+            rule.accrued(&principal, start, due, date(2026, 6, 1)),
+            dec("0.00")
+        );
+        assert_eq!(
+            rule.accrued(&principal, start, due, date(2026, 6, 2)),
+            dec("50.00")
+        );
+    }
+
+    #[test]
+    fn late_fee_without_due_date_is_zero() {
+        let rule = InterestRule::LateFee { fee: dec("50") };
+        let accrued = rule.accrued(&dec("300"), date(2026, 1, 1), None, date(2027, 1, 1));
+        assert_eq!(accrued, dec("0.00"));
+    }
+
+    #[test]
+    fn parses_rules() {
+        assert_eq!(
+            parse_rule("5%", Locale::En),
+            Some(InterestRule::Simple {
+                annual_rate: dec("5")
+            })
+        );
+        assert_eq!(
+            parse_rule(" 12,5 % сложные ", Locale::Ru),
+            Some(InterestRule::Compound {
+                annual_rate: dec("12.5")
+            })
+        );
+        assert_eq!(
+            parse_rule("Пеня 100", Locale::Ru),
+            Some(InterestRule::LateFee { fee: dec("100") })
+        );
+        assert_eq!(parse_rule("нет", Locale::Ru), Some(InterestRule::None));
+        assert_eq!(parse_rule("None", Locale::En), Some(InterestRule::None));
+        assert_eq!(
+            parse_rule("5% compound", Locale::En),
+            Some(InterestRule::Compound {
+                annual_rate: dec("5")
+            })
+        );
+        assert_eq!(
+            parse_rule("5% składane", Locale::Pl),
+            Some(InterestRule::Compound {
+                annual_rate: dec("5")
+            })
+        );
+        assert_eq!(
+            parse_rule("kara 20", Locale::Pl),
+            Some(InterestRule::LateFee { fee: dec("20") })
+        );
+        assert_eq!(parse_rule("5", Locale::En), None);
+        assert_eq!(parse_rule("-5%", Locale::En), None);
+        assert_eq!(parse_rule("пеня -1", Locale::Ru), None);
+        assert_eq!(parse_rule("5% иногда", Locale::Ru), None);
+    }
+
+    #[test]
+    fn parses_rule_numbers_like_amounts() {
+        assert_eq!(
+            parse_rule("fee 1,000", Locale::En),
+            Some(InterestRule::LateFee { fee: dec("1000") })
+        );
+        assert_eq!(
+            parse_rule("kara 1 000,50", Locale::Pl),
+            Some(InterestRule::LateFee {
+                fee: dec("1000.50")
+            })
+        );
+        assert_eq!(parse_rule("fee 0.001", Locale::En), None);
+        assert_eq!(parse_rule("fee 1e9", Locale::En), None);
+        assert_eq!(parse_rule("fee 100000000", Locale::En), None);
+        assert_eq!(parse_rule("1001%", Locale::En), None);
+    }
+
+    #[test]
+    fn columns_round_trip() {
+        let rule = InterestRule::Compound {
+            annual_rate: dec("3.25"),
+        };
+        let (kind, rate, fee) = rule.to_columns();
+        assert_eq!(
+            InterestRule::from_columns(kind, rate.as_ref(), fee.as_ref()),
+            rule
+        );
+    }
+}
@@ -0,0 +1,19 @@
+use diesel_derive_enum::DbEnum;
+use serde::Serialize;
+use strum_macros::Display;
+use strum_macros::EnumString;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, DbEnum, EnumString, Display, Serialize)]
+#[ExistingTypePath = "crate::schema::sql_types::InterestKind"]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum InterestKind {
+    #[db_rename = "none"]
+    None,
+    #[db_rename = "simple"]
+    Simple,
+    #[db_rename = "compound"]
+    Compound,
+    #[db_rename = "late_fee"]
+    LateFee,
+}
@@ -0,0 +1,43 @@
+use diesel_derive_enum::DbEnum;
+use strum_macros::Display;
+use strum_macros::EnumString;
+
+/// Язык интерфейса. Переводы лежат в `locales/<locale>.ftl`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, DbEnum, EnumString, Display)]
+#[ExistingTypePath = "crate::schema::sql_types::Locale"]
+#[strum(serialize_all = "snake_case")]
+pub enum Locale {
+    #[db_rename = "en"]
+    En,
+    #[db_rename = "pl"]
+    Pl,
+    #[db_rename = "ru"]
+    Ru,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 3] = [Locale::En, Locale::Pl, Locale::Ru];
+
+    /// Язык, которым пользуются, пока Telegram не сообщил `language_code`
+    pub const DEFAULT: Locale = Locale::Ru;
+
+    /// `language_code` из Telegram (IETF-тег вроде `en-US`) в поддерживаемую локаль.
+    /// Украинский и белорусский интерфейсы получают русский, прочие — английский
+    pub fn from_language_code(code: &str) -> Locale {
+        let language = code.split(['-', '_']).next().unwrap_or_default();
+        match language.to_lowercase().as_str() {
+            "pl" => Locale::Pl,
+            "ru" | "uk" | "be" => Locale::Ru,
+            _ => Locale::En,
+        }
+    }
+
+    /// Название языка на нём самом — для кнопок выбора языка
+    pub fn native_name(self) -> &'static str {
+        match self {
+            Locale::En => "English",
+            Locale::Pl => "Polski",
+            Locale::Ru => "Русский",
+        }
+    }
+}
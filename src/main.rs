@@ -4,19 +4,49 @@ use diesel_migrations::embed_migrations;
 use diesel_migrations::EmbeddedMigrations;
 use diesel_migrations::MigrationHarness;
 use std::env;
+use std::sync::OnceLock;
 use teloxide::dispatching::Dispatcher;
 use teloxide::Bot;
 
+mod amount;
+mod audit_action;
+mod backup;
+mod chart;
+mod contact_sort;
 mod db_util;
+mod export;
+mod expression;
+mod i18n;
+mod import;
+mod import_status;
 mod inputting_status;
+mod interest;
+mod interest_kind;
+mod locale;
 mod models;
+mod quick_entry;
+mod recurrence_period;
+mod reminder_frequency;
+mod scheduler;
 mod schema;
+mod screen;
+mod search;
+mod statement;
+mod statement_pdf;
+mod stats;
 mod telegram_util;
 
+/// Валюта, в которой ведутся все суммы: переменная окружения `CURRENCY`, по умолчанию PLN
+pub fn currency() -> &'static str {
+    static CURRENCY: OnceLock<String> = OnceLock::new();
+    CURRENCY.get_or_init(|| env::var("CURRENCY").unwrap_or_else(|_| "PLN".to_string()))
+}
+
 /// Встраиваем все миграции из каталога `migrations/`
 pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("./migrations");
 
-type HandlerResult = Result<(), Box<dyn std::error::Error + Send + Sync>>;
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+type HandlerResult = Result<(), BoxError>;
 
 #[tokio::main]
 async fn main() -> HandlerResult {
@@ -27,6 +57,11 @@ async fn main() -> HandlerResult {
         .expect("Error applying migrations");
 
     let bot = Bot::from_env();
+    // Без меню команд бот всё равно работает, поэтому сбой здесь не останавливает запуск
+    if let Err(err) = telegram_util::register_commands(&bot).await {
+        log::warn!("Error registering bot commands: {:?}", err);
+    }
+    tokio::spawn(scheduler::run(bot.clone()));
     Dispatcher::builder(bot, telegram_util::message_handler_schema())
         .enable_ctrlc_handler()
         .build()
@@ -37,6 +72,5 @@ async fn main() -> HandlerResult {
 
 pub fn establish_connection() -> PgConnection {
     let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
-    let mut conn = PgConnection::establish(&database_url).expect("Error connecting to database");
-    conn
+    PgConnection::establish(&database_url).expect("Error connecting to database")
 }
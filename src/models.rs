@@ -1,20 +1,68 @@
+use super::schema::audit_log;
 use super::schema::contacts;
+use super::schema::import_batches;
+use super::schema::nudge_mutes;
+use super::schema::nudges;
+use super::schema::recurring_transactions;
+use super::schema::reminders;
+use super::schema::tags;
+use super::schema::transaction_tags;
 use super::schema::transactions;
 use super::schema::users_t;
+use crate::audit_action::AuditAction;
+use crate::contact_sort::ContactSort;
+use crate::import_status::ImportStatus;
 use crate::inputting_status::InputtingStatus;
+use crate::interest::InterestRule;
+use crate::interest_kind::InterestKind;
+use crate::locale::Locale;
+use crate::recurrence_period::RecurrencePeriod;
+use crate::reminder_frequency::ReminderFrequency;
+use crate::screen::Screen;
 use bigdecimal::BigDecimal;
+use chrono::DateTime;
+use chrono::NaiveDate;
+use chrono::Utc;
 use diesel::prelude::*;
 use diesel::{Identifiable, Queryable};
+use serde::Deserialize;
+use serde::Serialize;
 
-#[derive(Debug, Queryable, Selectable, Identifiable)]
+#[derive(Debug, Clone, Queryable, Selectable, Identifiable)]
 #[diesel(table_name = users_t)]
 pub struct User {
     pub id: i32,
-    pub telegram_id: i64,
+    pub telegram_id: Option<i64>, // нет у удалённых пользователей
     pub telegram_username: String,
     pub status: InputtingStatus,
     pub selected_contact_id: Option<i32>,
     pub selected_transaction_duration: Option<i32>, // 0 - дал, 1 - взял; TODO заменить на enum
+    pub selected_transaction_id: Option<i32>,
+    pub reminder_frequency: ReminderFrequency,
+    pub quiet_hours_start: Option<i16>, // час по UTC
+    pub quiet_hours_end: Option<i16>,
+    pub deleted_at: Option<DateTime<Utc>>, // пользователь удалил аккаунт, данные обезличены
+    pub monthly_statement: bool,
+    pub last_statement_month: Option<NaiveDate>,
+    pub search_query: Option<String>,
+    pub language_code: Option<String>, // из последнего апдейта Telegram
+    pub locale: Option<Locale>,        // выбран вручную в /settings
+    pub screen_stack: Vec<Screen>,
+    pub contact_sort: ContactSort,
+    pub contact_search: Option<String>, // поиск в выборе контакта
+}
+
+impl User {
+    /// Язык интерфейса: выбранный вручную, иначе язык клиента Telegram
+    pub fn locale(&self) -> Locale {
+        self.locale
+            .or_else(|| {
+                self.language_code
+                    .as_deref()
+                    .map(Locale::from_language_code)
+            })
+            .unwrap_or(Locale::DEFAULT)
+    }
 }
 
 #[derive(Debug, Insertable)]
@@ -24,7 +72,7 @@ pub struct NewUser {
     pub telegram_username: String,
 }
 
-#[derive(Debug, Queryable, Identifiable, Associations)]
+#[derive(Debug, Queryable, Identifiable, Associations, Serialize)]
 #[diesel(table_name = contacts)]
 #[diesel(belongs_to(User, foreign_key = user_id))]
 pub struct Contact {
@@ -41,7 +89,7 @@ pub struct NewContact {
     pub contact_id: i32,
 }
 
-#[derive(Debug, Queryable, Selectable, Identifiable, Associations)]
+#[derive(Debug, Queryable, Selectable, Identifiable, Associations, Serialize)]
 #[diesel(table_name = transactions)]
 #[diesel(belongs_to(User, foreign_key = from_user_id))]
 pub struct Transaction {
@@ -49,6 +97,35 @@ pub struct Transaction {
     pub from_user_id: i32,
     pub to_user_id: i32,
     pub amount: BigDecimal,
+    pub created_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub due_date: Option<NaiveDate>,
+    pub comment: Option<String>,
+    pub interest_kind: InterestKind,
+    pub interest_rate: Option<BigDecimal>,
+    pub late_fee: Option<BigDecimal>,
+    pub import_batch_id: Option<i32>,
+    pub attachment_file_id: Option<String>, // фото чека
+}
+
+impl Transaction {
+    pub fn interest_rule(&self) -> InterestRule {
+        InterestRule::from_columns(
+            self.interest_kind,
+            self.interest_rate.as_ref(),
+            self.late_fee.as_ref(),
+        )
+    }
+
+    /// Начисленные проценты или пеня на дату `on`
+    pub fn accrued_interest(&self, on: NaiveDate) -> BigDecimal {
+        self.interest_rule().accrued(
+            &self.amount,
+            self.created_at.date_naive(),
+            self.due_date,
+            on,
+        )
+    }
 }
 
 #[derive(Debug, Insertable)]
@@ -57,4 +134,165 @@ pub struct NewTransaction {
     pub from_user_id: i32,
     pub to_user_id: i32,
     pub amount: BigDecimal,
+    pub comment: Option<String>,
+    pub attachment_file_id: Option<String>,
+}
+
+/// Транзакция из импорта: дата берётся из файла
+#[derive(Debug, Insertable, Serialize, Deserialize)]
+#[diesel(table_name = transactions)]
+pub struct NewImportedTransaction {
+    pub from_user_id: i32,
+    pub to_user_id: i32,
+    pub amount: BigDecimal,
+    pub comment: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub import_batch_id: Option<i32>,
+}
+
+/// Транзакция из резервной копии со всеми сохранёнными полями
+#[derive(Debug, Insertable)]
+#[diesel(table_name = transactions)]
+pub struct NewRestoredTransaction {
+    pub from_user_id: i32,
+    pub to_user_id: i32,
+    pub amount: BigDecimal,
+    pub comment: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub due_date: Option<NaiveDate>,
+    pub interest_kind: InterestKind,
+    pub interest_rate: Option<BigDecimal>,
+    pub late_fee: Option<BigDecimal>,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = contacts)]
+pub struct NewNamedContact {
+    pub user_id: i32,
+    pub contact_id: i32,
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Queryable, Selectable, Identifiable)]
+#[diesel(table_name = audit_log)]
+pub struct AuditEntry {
+    pub id: i32,
+    pub actor_id: Option<i32>,
+    pub table_name: String,
+    pub record_id: i32,
+    pub action: AuditAction,
+    pub before: Option<serde_json::Value>,
+    pub after: Option<serde_json::Value>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = audit_log)]
+pub struct NewAuditEntry<'a> {
+    pub actor_id: Option<i32>,
+    pub table_name: &'a str,
+    pub record_id: i32,
+    pub action: AuditAction,
+    pub before: Option<serde_json::Value>,
+    pub after: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Queryable, Selectable, Identifiable, Associations)]
+#[diesel(table_name = reminders)]
+#[diesel(belongs_to(Transaction, foreign_key = transaction_id))]
+pub struct Reminder {
+    pub id: i32,
+    pub transaction_id: i32,
+    pub user_id: i32,
+    pub remind_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = reminders)]
+pub struct NewReminder {
+    pub transaction_id: i32,
+    pub user_id: i32,
+    pub remind_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = nudges)]
+pub struct NewNudge {
+    pub from_user_id: i32,
+    pub to_user_id: i32,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = nudge_mutes)]
+pub struct NewNudgeMute {
+    pub user_id: i32,
+    pub muted_user_id: i32,
+}
+
+#[derive(Debug, Queryable, Selectable, Identifiable)]
+#[diesel(table_name = recurring_transactions)]
+pub struct RecurringTransaction {
+    pub id: i32,
+    pub owner_id: i32,
+    pub from_user_id: i32,
+    pub to_user_id: i32,
+    pub amount: BigDecimal,
+    pub comment: Option<String>,
+    pub period: RecurrencePeriod,
+    pub period_day: i16,
+    pub next_run_at: DateTime<Utc>,
+    pub paused: bool,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = recurring_transactions)]
+pub struct NewRecurringTransaction {
+    pub owner_id: i32,
+    pub from_user_id: i32,
+    pub to_user_id: i32,
+    pub amount: BigDecimal,
+    pub comment: Option<String>,
+    pub period: RecurrencePeriod,
+    pub period_day: i16,
+    pub next_run_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Queryable, Selectable, Identifiable)]
+#[diesel(table_name = import_batches)]
+pub struct ImportBatch {
+    pub id: i32,
+    pub user_id: i32,
+    pub status: ImportStatus,
+    pub rows: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = import_batches)]
+pub struct NewImportBatch {
+    pub user_id: i32,
+    pub rows: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Queryable, Selectable, Identifiable)]
+#[diesel(table_name = tags)]
+pub struct Tag {
+    pub id: i32,
+    pub user_id: i32,
+    pub name: String,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = tags)]
+pub struct NewTag<'a> {
+    pub user_id: i32,
+    pub name: &'a str,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = transaction_tags)]
+pub struct NewTransactionTag {
+    pub transaction_id: i32,
+    pub tag_id: i32,
 }
@@ -0,0 +1,228 @@
+//! Быстрый ввод долга одной фразой без меню: «дал Пете 50 за пиццу»,
+//! «borrowed 20 from @alice», «alice owes me 30». Модуль только разбирает текст,
+//! контакт по найденному имени ищет вызывающий код.
+
+use crate::amount;
+use crate::amount::AmountInput;
+use crate::locale::Locale;
+use bigdecimal::BigDecimal;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuickEntry<'a> {
+    /// `true` — пользователь дал в долг, `false` — взял
+    pub lent: bool,
+    /// Имя или @username контакта как написано, возможно в косвенном падеже
+    pub contact: &'a str,
+    pub amount: BigDecimal,
+    pub comment: Option<&'a str>,
+}
+
+const LENT_VERBS: [&str; 11] = [
+    "дал",
+    "дала",
+    "одолжил",
+    "одолжила",
+    "lent",
+    "gave",
+    "loaned",
+    "dałem",
+    "dałam",
+    "pożyczyłem",
+    "pożyczyłam",
+];
+const BORROWED_VERBS: [&str; 8] = [
+    "взял",
+    "взяла",
+    "занял",
+    "заняла",
+    "borrowed",
+    "took",
+    "wziąłem",
+    "wzięłam",
+];
+/// Предлог перед тем, у кого взяли: «взял у Пети», «pożyczyłem od Ani»
+const FROM_WORDS: [&str; 4] = ["у", "от", "from", "od"];
+/// Предлог перед тем, кому дали: «lent 20 to alice»
+const TO_WORDS: [&str; 3] = ["to", "для", "dla"];
+/// «alice owes me 30», «Петя должен мне 30», «Ala jest mi winna 30»
+const OWES_ME: [&[&str]; 6] = [
+    &["owes", "me"],
+    &["должен", "мне"],
+    &["должна", "мне"],
+    &["jest", "mi", "winien"],
+    &["jest", "mi", "winna"],
+    &["wisi", "mi"],
+];
+/// «i owe alice 30», «я должен Пете 30», «jestem winien Ali 30»
+const I_OWE: [&[&str]; 5] = [
+    &["i", "owe"],
+    &["я", "должен"],
+    &["я", "должна"],
+    &["jestem", "winien"],
+    &["jestem", "winna"],
+];
+
+/// Слова фразы вместе с их позициями в тексте
+struct Words<'a> {
+    text: &'a str,
+    spans: Vec<(usize, &'a str)>,
+    lower: Vec<String>,
+}
+
+impl<'a> Words<'a> {
+    fn new(text: &'a str) -> Words<'a> {
+        let mut spans = vec![];
+        let mut start = None;
+        for (position, c) in text
+            .char_indices()
+            .chain(std::iter::once((text.len(), ' ')))
+        {
+            match (start, c.is_whitespace()) {
+                (None, false) => start = Some(position),
+                (Some(word_start), true) => {
+                    spans.push((word_start, &text[word_start..position]));
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+        let lower = spans.iter().map(|(_, word)| word.to_lowercase()).collect();
+        Words { text, spans, lower }
+    }
+
+    fn len(&self) -> usize {
+        self.spans.len()
+    }
+
+    fn is(&self, index: usize, candidates: &[&str]) -> bool {
+        self.lower
+            .get(index)
+            .is_some_and(|word| candidates.contains(&word.as_str()))
+    }
+
+    fn has_phrase(&self, index: usize, phrase: &[&str]) -> bool {
+        self.lower
+            .get(index..index + phrase.len())
+            .is_some_and(|words| {
+                words
+                    .iter()
+                    .zip(phrase)
+                    .all(|(word, expected)| word == expected)
+            })
+    }
+
+    /// Имя контакта без знаков препинания вокруг: «Пете,» → «Пете»
+    fn contact(&self, index: usize) -> Option<&'a str> {
+        let (_, word) = self.spans.get(index)?;
+        Some(word.trim_matches([',', ':', ';', '.'])).filter(|word| !word.is_empty())
+    }
+
+    /// Текст начиная со слова `index`
+    fn rest(&self, index: usize) -> &'a str {
+        self.spans
+            .get(index)
+            .map(|(start, _)| &self.text[*start..])
+            .unwrap_or_default()
+    }
+}
+
+/// Суммы разбираются по правилам языка `locale`
+pub fn parse(text: &str, locale: Locale) -> Option<QuickEntry<'_>> {
+    let words = Words::new(text.trim());
+    if words.len() < 3 {
+        return None;
+    }
+    // «я должен Пете 30»
+    if let Some(phrase) = I_OWE.iter().find(|phrase| words.has_phrase(0, phrase)) {
+        return contact_then_amount(&words, phrase.len(), false, locale);
+    }
+    // «Петя должен мне 30»: имя — всё до глагола, оно может быть из нескольких слов
+    for phrase in OWES_ME {
+        if let Some(index) = (1..words.len()).find(|&index| words.has_phrase(index, phrase)) {
+            let contact = words.text[..words.spans[index].0].trim();
+            let (amount, comment) = amount_and_comment(words.rest(index + phrase.len()), locale)?;
+            return Some(QuickEntry {
+                lent: true,
+                contact,
+                amount,
+                comment,
+            });
+        }
+    }
+
+    let lent = if words.is(0, &LENT_VERBS) {
+        true
+    } else if words.is(0, &BORROWED_VERBS) {
+        false
+    } else {
+        return None;
+    };
+    // «взял у Пети 100», «pożyczyłem od Ani 50» — предлог «у/od» значит, что взяли
+    if words.is(1, &FROM_WORDS) {
+        return contact_then_amount(&words, 2, false, locale);
+    }
+    if words.is(1, &TO_WORDS) {
+        return contact_then_amount(&words, 2, lent, locale);
+    }
+    // «borrowed 20 from @alice for lunch»: сначала сумма, контакт после предлога
+    if let Some((amount, Some(comment))) = amount_and_comment(words.rest(1), locale) {
+        let tail = Words::new(comment);
+        let lent = if tail.is(0, &FROM_WORDS) {
+            false
+        } else if tail.is(0, &TO_WORDS) {
+            lent
+        } else {
+            return None;
+        };
+        return Some(QuickEntry {
+            lent,
+            contact: tail.contact(1)?,
+            amount,
+            comment: Some(tail.rest(2).trim()).filter(|comment| !comment.is_empty()),
+        });
+    }
+    // «дал Пете 50 за пиццу»
+    contact_then_amount(&words, 1, lent, locale)
+}
+
+fn contact_then_amount<'a>(
+    words: &Words<'a>,
+    index: usize,
+    lent: bool,
+    locale: Locale,
+) -> Option<QuickEntry<'a>> {
+    let contact = words.contact(index)?;
+    let (amount, comment) = amount_and_comment(words.rest(index + 1), locale)?;
+    Some(QuickEntry {
+        lent,
+        contact,
+        amount,
+        comment,
+    })
+}
+
+fn amount_and_comment(text: &str, locale: Locale) -> Option<(BigDecimal, Option<&str>)> {
+    match amount::parse_amount_input(text, locale).ok()? {
+        AmountInput::Exact { amount, comment }
+        | AmountInput::Computed {
+            amount, comment, ..
+        } => Some((amount, comment)),
+    }
+}
+
+/// Подходит ли написанное `word` к имени контакта `name`. Имя могут написать
+/// в косвенном падеже: «Пете», «Пети» — это «Петя», «Ивану» — «Иван»
+pub fn matches_name(word: &str, name: &str) -> bool {
+    let word = word.trim_start_matches('@').to_lowercase();
+    let name = name.trim_start_matches('@').to_lowercase();
+    if word == name {
+        return true;
+    }
+    let stem = |text: &str| {
+        let mut chars = text.chars();
+        chars.next_back();
+        chars.as_str().to_string()
+    };
+    let (word_stem, name_stem) = (stem(&word), stem(&name));
+    name.chars().count() >= 3 && (word_stem == name_stem || word_stem == name || word == name_stem)
+}
@@ -0,0 +1,83 @@
+use chrono::DateTime;
+use chrono::Datelike;
+use chrono::Days;
+use chrono::NaiveDate;
+use chrono::NaiveTime;
+use chrono::Utc;
+use diesel_derive_enum::DbEnum;
+use strum_macros::Display;
+use strum_macros::EnumString;
+
+/// Час по UTC, в который создаются регулярные транзакции
+const RUN_HOUR: u32 = 9;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, DbEnum, EnumString, Display)]
+#[ExistingTypePath = "crate::schema::sql_types::RecurrencePeriod"]
+#[strum(serialize_all = "snake_case")]
+pub enum RecurrencePeriod {
+    /// `period_day` — день недели, 1 = понедельник … 7 = воскресенье
+    #[db_rename = "weekly"]
+    Weekly,
+    /// `period_day` — число месяца; в коротких месяцах берётся последний день
+    #[db_rename = "monthly"]
+    Monthly,
+}
+
+impl RecurrencePeriod {
+    /// Ближайший запуск строго позже `after`
+    pub fn next_run_after(self, period_day: i16, after: DateTime<Utc>) -> DateTime<Utc> {
+        let run_time = NaiveTime::from_hms_opt(RUN_HOUR, 0, 0).expect("valid run hour");
+        let mut date = after.date_naive();
+        loop {
+            if self.matches(period_day, date) {
+                let candidate = date.and_time(run_time).and_utc();
+                if candidate > after {
+                    return candidate;
+                }
+            }
+            date = date + Days::new(1);
+        }
+    }
+
+    fn matches(self, period_day: i16, date: NaiveDate) -> bool {
+        match self {
+            RecurrencePeriod::Weekly => date.weekday().number_from_monday() == period_day as u32,
+            RecurrencePeriod::Monthly => {
+                let last_day = last_day_of_month(date);
+                date.day() == (period_day as u32).min(last_day)
+            }
+        }
+    }
+}
+
+fn last_day_of_month(date: NaiveDate) -> u32 {
+    let (year, month) = if date.month() == 12 {
+        (date.year() + 1, 1)
+    } else {
+        (date.year(), date.month() + 1)
+    };
+    NaiveDate::from_ymd_opt(year, month, 1)
+        .and_then(|first| first.pred_opt())
+        .map(|last| last.day())
+        .unwrap_or(28)
+}
+
+/// Разбирает расписание из одного слова: число месяца («5») или день недели («пн», «mon», «pn»)
+pub fn parse_schedule(token: &str) -> Option<(RecurrencePeriod, i16)> {
+    const WEEKDAYS: [[&str; 7]; 3] = [
+        ["пн", "вт", "ср", "чт", "пт", "сб", "вс"],
+        ["mon", "tue", "wed", "thu", "fri", "sat", "sun"],
+        ["pn", "wt", "śr", "cz", "pt", "sb", "nd"],
+    ];
+    let token = token.to_lowercase();
+    if let Some(index) = WEEKDAYS
+        .iter()
+        .find_map(|days| days.iter().position(|day| *day == token))
+    {
+        return Some((RecurrencePeriod::Weekly, index as i16 + 1));
+    }
+    match token.parse::<i16>() {
+        Ok(day) if (1..=31).contains(&day) => Some((RecurrencePeriod::Monthly, day)),
+        _ => None,
+    }
+}
@@ -0,0 +1,15 @@
+use diesel_derive_enum::DbEnum;
+use strum_macros::Display;
+use strum_macros::EnumString;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, DbEnum, EnumString, Display)]
+#[ExistingTypePath = "crate::schema::sql_types::ReminderFrequency"]
+#[strum(serialize_all = "snake_case")]
+pub enum ReminderFrequency {
+    #[db_rename = "off"]
+    Off,
+    #[db_rename = "daily"]
+    Daily,
+    #[db_rename = "weekly"]
+    Weekly,
+}
@@ -0,0 +1,287 @@
+use crate::amount;
+use crate::db_util;
+use crate::i18n::t;
+use crate::models::Transaction;
+use crate::models::User;
+use crate::reminder_frequency::ReminderFrequency;
+use crate::statement;
+use crate::HandlerResult;
+use bigdecimal::BigDecimal;
+use chrono::DateTime;
+use chrono::Datelike;
+use chrono::Days;
+use chrono::Months;
+use chrono::NaiveDate;
+use chrono::NaiveTime;
+use chrono::Timelike;
+use chrono::Utc;
+use std::time::Duration;
+use teloxide::prelude::*;
+use teloxide::types::InputFile;
+use teloxide::ApiError;
+use teloxide::RequestError;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(60);
+/// Час по UTC, на который планируется первое напоминание о сроке
+const REMINDER_HOUR: u32 = 9;
+/// Выписка длиннее стольких сообщений отправляется файлом
+const STATEMENT_MAX_MESSAGES: usize = 3;
+/// Столько часов ждёт подтверждения проверенный импорт, потом удаляется
+const PENDING_IMPORT_TTL_HOURS: i64 = 24;
+
+/// Фоновая задача: напоминания о сроках возврата, регулярные платежи, ежемесячные выписки
+/// и уборка неподтверждённых импортов.
+/// Всё расписание лежит в БД (`reminders`, `recurring_transactions`,
+/// `users_t.last_statement_month`), поэтому после перезапуска пропущенное за время простоя
+/// выполнится на первых же тиках.
+pub async fn run(bot: Bot) {
+    let mut interval = tokio::time::interval(POLL_INTERVAL);
+    loop {
+        interval.tick().await;
+        if let Err(err) = send_due_reminders(&bot).await {
+            log::error!("Error sending reminders: {:?}", err);
+        }
+        if let Err(err) = run_due_recurring_transactions(&bot).await {
+            log::error!("Error running recurring transactions: {:?}", err);
+        }
+        if let Err(err) = send_monthly_statements(&bot).await {
+            log::error!("Error sending monthly statements: {:?}", err);
+        }
+        let stale_before = Utc::now() - chrono::Duration::hours(PENDING_IMPORT_TTL_HOURS);
+        if let Err(err) = db_util::delete_stale_import_batches(stale_before) {
+            log::error!("Error deleting stale imports: {:?}", err);
+        }
+    }
+}
+
+/// Когда впервые напомнить о долге со сроком `due_date`: накануне утром, но не раньше `now`
+pub fn first_reminder_at(due_date: NaiveDate, now: DateTime<Utc>) -> DateTime<Utc> {
+    let day_before = due_date.checked_sub_days(Days::new(1)).unwrap_or(due_date);
+    let planned = day_before
+        .and_time(NaiveTime::from_hms_opt(REMINDER_HOUR, 0, 0).expect("valid reminder hour"))
+        .and_utc();
+    planned.max(now)
+}
+
+async fn send_due_reminders(bot: &Bot) -> HandlerResult {
+    let now = Utc::now();
+    for (reminder, transaction) in db_util::find_due_reminders(now)? {
+        let Some(due_date) = transaction.due_date else {
+            db_util::delete_reminder(&reminder)?;
+            continue;
+        };
+        if transaction.deleted_at.is_some() || is_settled(&transaction)? {
+            db_util::delete_reminder(&reminder)?;
+            continue;
+        }
+        let mut recipient = db_util::get_user_by_id(reminder.user_id)?;
+        let Some(recipient_telegram_id) = recipient.telegram_id else {
+            db_util::delete_reminder(&reminder)?;
+            continue;
+        };
+        if is_quiet_hour(&recipient, now.hour()) {
+            continue;
+        }
+        if recipient.reminder_frequency != ReminderFrequency::Off {
+            let text = reminder_text(&recipient, &transaction, due_date, now.date_naive())?;
+            if let Err(err) = bot.send_message(ChatId(recipient_telegram_id), text).await {
+                log::warn!("Error sending reminder {}: {:?}", reminder.id, err);
+                // Заблокировавшему бота не дозвониться: выключаем напоминания, чтобы не
+                // стучаться каждую минуту. Включит сам, когда вернётся
+                if is_blocked(&err) {
+                    recipient =
+                        db_util::set_reminder_frequency(&recipient, ReminderFrequency::Off)?;
+                }
+            }
+        }
+        let next = now + next_reminder_delay(recipient.reminder_frequency);
+        db_util::reschedule_reminder(&reminder, next)?;
+    }
+    Ok(())
+}
+
+/// За тик создаётся не больше одной транзакции на шаблон: пропущенные периоды догоняются постепенно
+async fn run_due_recurring_transactions(bot: &Bot) -> HandlerResult {
+    let now = Utc::now();
+    for recurring in db_util::find_due_recurring_transactions(now)? {
+        let next_run_at = recurring
+            .period
+            .next_run_after(recurring.period_day, recurring.next_run_at);
+        let transaction = db_util::run_recurring_transaction(&recurring, next_run_at)?;
+        let lender = db_util::get_user_by_id(transaction.from_user_id)?;
+        let borrower = db_util::get_user_by_id(transaction.to_user_id)?;
+        let comment = transaction
+            .comment
+            .as_deref()
+            .map(|comment| format!(" ({comment})"))
+            .unwrap_or_default();
+        let notifications = [
+            (
+                &lender,
+                t!(
+                    lender.locale(),
+                    "recurring-run-lender",
+                    name = db_util::get_contact_display_name(&lender, &borrower),
+                    amount = amount::format_amount(&transaction.amount, lender.locale()),
+                    comment = comment.as_str()
+                ),
+            ),
+            (
+                &borrower,
+                t!(
+                    borrower.locale(),
+                    "recurring-run-borrower",
+                    name = db_util::get_contact_display_name(&borrower, &lender),
+                    amount = amount::format_amount(&transaction.amount, borrower.locale()),
+                    comment = comment.as_str()
+                ),
+            ),
+        ];
+        for (user, text) in notifications {
+            let Some(telegram_id) = user.telegram_id else {
+                continue;
+            };
+            if let Err(err) = bot.send_message(ChatId(telegram_id), text).await {
+                log::warn!(
+                    "Error notifying about recurring {}: {:?}",
+                    recurring.id,
+                    err
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Выписка за прошлый месяц уходит первого числа в `REMINDER_HOUR` или позже, если у пользователя тихие часы
+async fn send_monthly_statements(bot: &Bot) -> HandlerResult {
+    let now = Utc::now();
+    if now.hour() < REMINDER_HOUR {
+        return Ok(());
+    }
+    let month_start = now.date_naive().with_day(1).expect("first day of month");
+    let previous_month_start = month_start - Months::new(1);
+    for user in db_util::find_users_due_statement(month_start)? {
+        if is_quiet_hour(&user, now.hour()) {
+            continue;
+        }
+        let Some(telegram_id) = user.telegram_id else {
+            continue;
+        };
+        let chat_id = ChatId(telegram_id);
+        if let Some(text) = statement::build_statement(&user, previous_month_start, month_start)? {
+            let parts = statement::split_message(&text, statement::MAX_MESSAGE_LEN);
+            let sent = if parts.len() > STATEMENT_MAX_MESSAGES {
+                let file_name = format!("statement-{}.txt", previous_month_start.format("%Y-%m"));
+                bot.send_document(chat_id, InputFile::memory(text).file_name(file_name))
+                    .caption(t!(user.locale(), "statement-caption"))
+                    .await
+                    .map(|_| ())
+            } else {
+                let mut result = Ok(());
+                for part in parts {
+                    result = bot.send_message(chat_id, part).await.map(|_| ());
+                    if result.is_err() {
+                        break;
+                    }
+                }
+                result
+            };
+            if let Err(err) = sent {
+                log::warn!("Error sending statement to user {}: {:?}", user.id, err);
+                // Заблокировавшему бота выписка не дойдёт до конца месяца — не собираем
+                // её заново каждую минуту, а пропускаем этот месяц
+                if !is_blocked(&err) {
+                    continue;
+                }
+            }
+        }
+        db_util::mark_statement_sent(&user, month_start)?;
+    }
+    Ok(())
+}
+
+/// Пользователь заблокировал бота или удалил аккаунт Telegram: повтор не поможет
+fn is_blocked(err: &RequestError) -> bool {
+    matches!(
+        err,
+        RequestError::Api(ApiError::BotBlocked | ApiError::UserDeactivated)
+    )
+}
+
+/// Долг закрыт, если заёмщик больше ничего не должен кредитору
+fn is_settled(transaction: &Transaction) -> Result<bool, diesel::result::Error> {
+    let lender = db_util::get_user_by_id(transaction.from_user_id)?;
+    let borrower = db_util::get_user_by_id(transaction.to_user_id)?;
+    Ok(db_util::get_balance(&lender, &borrower)? <= BigDecimal::from(0))
+}
+
+/// При выключенных напоминаниях всё равно проверяем раз в сутки — вдруг их снова включат
+fn next_reminder_delay(frequency: ReminderFrequency) -> chrono::Duration {
+    match frequency {
+        ReminderFrequency::Off | ReminderFrequency::Daily => chrono::Duration::days(1),
+        ReminderFrequency::Weekly => chrono::Duration::days(7),
+    }
+}
+
+fn is_quiet_hour(user: &User, hour: u32) -> bool {
+    let (Some(start), Some(end)) = (user.quiet_hours_start, user.quiet_hours_end) else {
+        return false;
+    };
+    let (start, end, hour) = (start as u32, end as u32, hour);
+    if start <= end {
+        (start..end).contains(&hour)
+    } else {
+        hour >= start || hour < end
+    }
+}
+
+fn reminder_text(
+    recipient: &User,
+    transaction: &Transaction,
+    due_date: NaiveDate,
+    today: NaiveDate,
+) -> Result<String, diesel::result::Error> {
+    let is_lender = transaction.from_user_id == recipient.id;
+    let other_id = if is_lender {
+        transaction.to_user_id
+    } else {
+        transaction.from_user_id
+    };
+    let other = db_util::get_user_by_id(other_id)?;
+    let name = db_util::get_contact_display_name(recipient, &other);
+    let locale = recipient.locale();
+    let amount = amount::format_amount(&transaction.amount, locale);
+    let date = due_date.format("%d.%m.%Y").to_string();
+    let text = match (is_lender, due_date < today) {
+        (false, false) => t!(
+            locale,
+            "reminder-borrower-upcoming",
+            date = date,
+            amount = amount,
+            name = name
+        ),
+        (false, true) => t!(
+            locale,
+            "reminder-borrower-overdue",
+            date = date,
+            amount = amount,
+            name = name
+        ),
+        (true, false) => t!(
+            locale,
+            "reminder-lender-upcoming",
+            date = date,
+            amount = amount,
+            name = name
+        ),
+        (true, true) => t!(
+            locale,
+            "reminder-lender-overdue",
+            date = date,
+            amount = amount,
+            name = name
+        ),
+    };
+    Ok(text)
+}
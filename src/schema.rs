@@ -1,23 +1,69 @@
 use diesel::allow_tables_to_appear_in_same_query;
 use diesel::joinable;
+use diesel::query_builder::QueryId;
 use diesel::sql_types::SqlType;
 use diesel::table;
 
 pub mod sql_types {
     use super::*;
-    #[derive(SqlType)]
+    #[derive(SqlType, QueryId)]
     #[diesel(postgres_type(name = "inputting_status"))]
-    pub struct Inputting_status;
+    pub struct InputtingStatus;
+
+    #[derive(SqlType, QueryId)]
+    #[diesel(postgres_type(name = "audit_action"))]
+    pub struct AuditAction;
+
+    #[derive(SqlType, QueryId)]
+    #[diesel(postgres_type(name = "reminder_frequency"))]
+    pub struct ReminderFrequency;
+
+    #[derive(SqlType, QueryId)]
+    #[diesel(postgres_type(name = "recurrence_period"))]
+    pub struct RecurrencePeriod;
+
+    #[derive(SqlType, QueryId)]
+    #[diesel(postgres_type(name = "interest_kind"))]
+    pub struct InterestKind;
+
+    #[derive(SqlType, QueryId)]
+    #[diesel(postgres_type(name = "import_status"))]
+    pub struct ImportStatus;
+
+    #[derive(SqlType, QueryId)]
+    #[diesel(postgres_type(name = "locale"))]
+    pub struct Locale;
+
+    #[derive(SqlType, QueryId)]
+    #[diesel(postgres_type(name = "screen"))]
+    pub struct Screen;
+
+    #[derive(SqlType, QueryId)]
+    #[diesel(postgres_type(name = "contact_sort"))]
+    pub struct ContactSort;
 }
 
 table! {
     users_t (id) {
         id -> Int4,
-        telegram_id -> BigInt,
+        telegram_id -> Nullable<BigInt>,
         telegram_username -> Text,
-        status -> crate::schema::sql_types::Inputting_status,
+        status -> crate::schema::sql_types::InputtingStatus,
         selected_contact_id -> Nullable<Integer>,
         selected_transaction_duration -> Nullable<Integer>, // TODO заменить на enum
+        selected_transaction_id -> Nullable<Integer>,
+        reminder_frequency -> crate::schema::sql_types::ReminderFrequency,
+        quiet_hours_start -> Nullable<SmallInt>,
+        quiet_hours_end -> Nullable<SmallInt>,
+        deleted_at -> Nullable<Timestamptz>,
+        monthly_statement -> Bool,
+        last_statement_month -> Nullable<Date>,
+        search_query -> Nullable<Text>,
+        language_code -> Nullable<Text>,
+        locale -> Nullable<crate::schema::sql_types::Locale>,
+        screen_stack -> Array<crate::schema::sql_types::Screen>,
+        contact_sort -> crate::schema::sql_types::ContactSort,
+        contact_search -> Nullable<Text>,
     }
 }
 
@@ -36,10 +82,113 @@ table! {
         from_user_id -> Int4,
         to_user_id -> Int4,
         amount -> Numeric,
+        created_at -> Timestamptz,
+        deleted_at -> Nullable<Timestamptz>,
+        due_date -> Nullable<Date>,
+        comment -> Nullable<Text>,
+        interest_kind -> crate::schema::sql_types::InterestKind,
+        interest_rate -> Nullable<Numeric>,
+        late_fee -> Nullable<Numeric>,
+        import_batch_id -> Nullable<Int4>,
+        attachment_file_id -> Nullable<Text>,
+    }
+}
+
+table! {
+    audit_log (id) {
+        id -> Int4,
+        actor_id -> Nullable<Int4>,
+        table_name -> Text,
+        record_id -> Int4,
+        action -> crate::schema::sql_types::AuditAction,
+        before -> Nullable<Jsonb>,
+        after -> Nullable<Jsonb>,
+        created_at -> Timestamptz,
+    }
+}
+
+table! {
+    reminders (id) {
+        id -> Int4,
+        transaction_id -> Int4,
+        user_id -> Int4,
+        remind_at -> Timestamptz,
+    }
+}
+
+table! {
+    nudges (id) {
+        id -> Int4,
+        from_user_id -> Int4,
+        to_user_id -> Int4,
+        sent_at -> Timestamptz,
+    }
+}
+
+table! {
+    nudge_mutes (user_id, muted_user_id) {
+        user_id -> Int4,
+        muted_user_id -> Int4,
+    }
+}
+
+table! {
+    recurring_transactions (id) {
+        id -> Int4,
+        owner_id -> Int4,
+        from_user_id -> Int4,
+        to_user_id -> Int4,
+        amount -> Numeric,
+        comment -> Nullable<Text>,
+        period -> crate::schema::sql_types::RecurrencePeriod,
+        period_day -> SmallInt,
+        next_run_at -> Timestamptz,
+        paused -> Bool,
+    }
+}
+
+table! {
+    import_batches (id) {
+        id -> Int4,
+        user_id -> Int4,
+        status -> crate::schema::sql_types::ImportStatus,
+        rows -> Jsonb,
+        created_at -> Timestamptz,
+    }
+}
+
+table! {
+    tags (id) {
+        id -> Int4,
+        user_id -> Int4,
+        name -> Text,
+    }
+}
+
+table! {
+    transaction_tags (transaction_id, tag_id) {
+        transaction_id -> Int4,
+        tag_id -> Int4,
     }
 }
 
 joinable!(contacts -> users_t (user_id));
 joinable!(transactions -> users_t (from_user_id));
+joinable!(audit_log -> users_t (actor_id));
+joinable!(reminders -> transactions (transaction_id));
+joinable!(transaction_tags -> transactions (transaction_id));
+joinable!(transaction_tags -> tags (tag_id));
 
-allow_tables_to_appear_in_same_query!(users_t, contacts, transactions,);
+allow_tables_to_appear_in_same_query!(
+    users_t,
+    contacts,
+    transactions,
+    audit_log,
+    reminders,
+    nudges,
+    nudge_mutes,
+    recurring_transactions,
+    import_batches,
+    tags,
+    transaction_tags,
+);
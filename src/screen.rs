@@ -0,0 +1,29 @@
+use diesel_derive_enum::DbEnum;
+use strum_macros::Display;
+use strum_macros::EnumString;
+
+/// Экран в единственном сообщении с меню. Открытые экраны хранятся стеком
+/// в `users_t.screen_stack`, кнопка «Назад» возвращает на предыдущий
+#[derive(Debug, Clone, Copy, PartialEq, Eq, DbEnum, EnumString, Display)]
+#[ExistingTypePath = "crate::schema::sql_types::Screen"]
+#[strum(serialize_all = "snake_case")]
+pub enum Screen {
+    #[db_rename = "menu"]
+    Menu,
+    #[db_rename = "select_contact"]
+    SelectContact,
+    #[db_rename = "contact_actions"]
+    ContactActions,
+    #[db_rename = "transaction_amount"]
+    TransactionAmount,
+    #[db_rename = "add_contact"]
+    AddContact,
+    #[db_rename = "edit_contact"]
+    EditContact,
+    #[db_rename = "rename_contact"]
+    RenameContact,
+    #[db_rename = "debts"]
+    Debts,
+    #[db_rename = "contact_search"]
+    ContactSearch,
+}
@@ -0,0 +1,278 @@
+use crate::amount;
+use crate::amount::AmountError;
+use crate::i18n::t;
+use crate::locale::Locale;
+use bigdecimal::BigDecimal;
+use chrono::NaiveDate;
+use std::fmt;
+
+/// Условие на сумму транзакции: `>100`, `>=100`, `<50`, `<=50`, `=20`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AmountFilter {
+    Greater(BigDecimal),
+    GreaterOrEqual(BigDecimal),
+    Less(BigDecimal),
+    LessOrEqual(BigDecimal),
+    Equal(BigDecimal),
+}
+
+/// Разобранный запрос `/find`. Все условия объединяются через «и»
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SearchQuery {
+    /// Слова, каждое из которых должно встречаться в комментарии
+    pub words: Vec<String>,
+    pub amounts: Vec<AmountFilter>,
+    /// Начало полуинтервала дат `[since, until)`
+    pub since: Option<NaiveDate>,
+    pub until: Option<NaiveDate>,
+    /// Имя или username контакта без «@»
+    pub contact: Option<String>,
+}
+
+/// Что не так с запросом; неразобранный фрагмент — как его написал пользователь
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SearchError {
+    Empty,
+    EmptyContact,
+    SeveralContacts,
+    InvertedDates,
+    InvalidDate(String),
+    InvalidAmount(String),
+    NegativeAmount(String),
+}
+
+impl SearchError {
+    pub fn message(&self, locale: Locale) -> String {
+        match self {
+            SearchError::Empty => t!(locale, "search-error-empty"),
+            SearchError::EmptyContact => t!(locale, "search-error-empty-contact"),
+            SearchError::SeveralContacts => t!(locale, "search-error-several-contacts"),
+            SearchError::InvertedDates => t!(locale, "search-error-inverted-dates"),
+            SearchError::InvalidDate(value) => {
+                t!(locale, "search-error-date", value = value.as_str())
+            }
+            SearchError::InvalidAmount(token) => {
+                t!(locale, "search-error-amount", token = token.as_str())
+            }
+            SearchError::NegativeAmount(token) => {
+                t!(
+                    locale,
+                    "search-error-negative-amount",
+                    token = token.as_str()
+                )
+            }
+        }
+    }
+}
+
+/// Для журналов; пользователю показывается `message`
+impl fmt::Display for SearchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message(Locale::En))
+    }
+}
+
+impl std::error::Error for SearchError {}
+
+impl SearchQuery {
+    /// Разбирает запрос вида `pizza >100 since:2026-01-01 @alice`.
+    /// Суммы пишутся как при вводе, на языке `locale`: `>1 000`, `<=12,50`
+    pub fn parse(input: &str, locale: Locale) -> Result<SearchQuery, SearchError> {
+        let mut query = SearchQuery::default();
+        let mut tokens = input.split_whitespace().peekable();
+        while let Some(token) = tokens.next() {
+            let mut token = token.to_string();
+            if token.starts_with(['>', '<', '=']) {
+                while let Some(group) = tokens.next_if(|next| is_digit_group(next)) {
+                    token.push(' ');
+                    token.push_str(group);
+                }
+            }
+            let token = token.as_str();
+            if let Some(date) = token.strip_prefix("since:") {
+                query.since = Some(parse_date(date)?);
+            } else if let Some(date) = token.strip_prefix("until:") {
+                query.until = Some(parse_date(date)?);
+            } else if let Some(contact) = token.strip_prefix('@') {
+                if contact.is_empty() {
+                    return Err(SearchError::EmptyContact);
+                }
+                if query.contact.is_some() {
+                    return Err(SearchError::SeveralContacts);
+                }
+                query.contact = Some(contact.to_string());
+            } else if let Some(filter) = parse_amount_filter(token, locale)? {
+                query.amounts.push(filter);
+            } else {
+                query.words.push(token.to_lowercase());
+            }
+        }
+        if query.is_empty() {
+            return Err(SearchError::Empty);
+        }
+        if let (Some(since), Some(until)) = (query.since, query.until) {
+            if since >= until {
+                return Err(SearchError::InvertedDates);
+            }
+        }
+        Ok(query)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+            && self.amounts.is_empty()
+            && self.since.is_none()
+            && self.until.is_none()
+            && self.contact.is_none()
+    }
+}
+
+fn parse_date(value: &str) -> Result<NaiveDate, SearchError> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| SearchError::InvalidDate(value.to_string()))
+}
+
+/// «000» или «000,50» после `>1`: продолжение суммы, разделённой пробелами
+fn is_digit_group(token: &str) -> bool {
+    let digits = token.chars().take_while(char::is_ascii_digit).count();
+    digits == 3
+        && token[digits..]
+            .chars()
+            .all(|c| c.is_ascii_digit() || c == '.' || c == ',')
+}
+
+/// `Ok(None)` — токен не похож на условие на сумму и считается словом
+fn parse_amount_filter(token: &str, locale: Locale) -> Result<Option<AmountFilter>, SearchError> {
+    let (constructor, value): (fn(BigDecimal) -> AmountFilter, &str) =
+        if let Some(value) = token.strip_prefix(">=") {
+            (AmountFilter::GreaterOrEqual, value)
+        } else if let Some(value) = token.strip_prefix("<=") {
+            (AmountFilter::LessOrEqual, value)
+        } else if let Some(value) = token.strip_prefix('>') {
+            (AmountFilter::Greater, value)
+        } else if let Some(value) = token.strip_prefix('<') {
+            (AmountFilter::Less, value)
+        } else if let Some(value) = token.strip_prefix('=') {
+            (AmountFilter::Equal, value)
+        } else {
+            return Ok(None);
+        };
+    match amount::parse_number(value, locale) {
+        Ok(amount) => Ok(Some(constructor(amount))),
+        Err(AmountError::NotPositive) => Err(SearchError::NegativeAmount(token.to_string())),
+        Err(_) => Err(SearchError::InvalidAmount(token.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn amount(value: &str) -> BigDecimal {
+        BigDecimal::from_str(value).unwrap()
+    }
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn parses_plain_words_as_lowercase_comment_terms() {
+        let query = SearchQuery::parse("Pizza  party", Locale::En).unwrap();
+        assert_eq!(query.words, vec!["pizza", "party"]);
+        assert!(query.amounts.is_empty());
+    }
+
+    #[test]
+    fn parses_amount_comparisons() {
+        let query = SearchQuery::parse(">100 <=250.5 =7,5", Locale::En).unwrap();
+        assert_eq!(
+            query.amounts,
+            vec![
+                AmountFilter::Greater(amount("100")),
+                AmountFilter::LessOrEqual(amount("250.5")),
+                AmountFilter::Equal(amount("7.5")),
+            ]
+        );
+        let query = SearchQuery::parse(">=10 <20", Locale::En).unwrap();
+        assert_eq!(
+            query.amounts,
+            vec![
+                AmountFilter::GreaterOrEqual(amount("10")),
+                AmountFilter::Less(amount("20")),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_amounts_like_amount_input() {
+        let query = SearchQuery::parse(">1 000 pizza <=2 500,50", Locale::Ru).unwrap();
+        assert_eq!(
+            query.amounts,
+            vec![
+                AmountFilter::Greater(amount("1000")),
+                AmountFilter::LessOrEqual(amount("2500.50")),
+            ]
+        );
+        assert_eq!(query.words, vec!["pizza"]);
+        let query = SearchQuery::parse(">1,000", Locale::En).unwrap();
+        assert_eq!(query.amounts, vec![AmountFilter::Greater(amount("1000"))]);
+        assert!(SearchQuery::parse(">1,000", Locale::Pl).is_err());
+        assert!(SearchQuery::parse(">1e9", Locale::En).is_err());
+    }
+
+    #[test]
+    fn parses_date_bounds() {
+        let query = SearchQuery::parse("since:2026-01-01 until:2026-02-01", Locale::En).unwrap();
+        assert_eq!(query.since, Some(date(2026, 1, 1)));
+        assert_eq!(query.until, Some(date(2026, 2, 1)));
+    }
+
+    #[test]
+    fn parses_contact_mention() {
+        let query = SearchQuery::parse("@alice", Locale::En).unwrap();
+        assert_eq!(query.contact.as_deref(), Some("alice"));
+    }
+
+    #[test]
+    fn parses_combined_query() {
+        let query = SearchQuery::parse("pizza >100 since:2026-01-01 @alice", Locale::En).unwrap();
+        assert_eq!(
+            query,
+            SearchQuery {
+                words: vec!["pizza".to_string()],
+                amounts: vec![AmountFilter::Greater(amount("100"))],
+                since: Some(date(2026, 1, 1)),
+                until: None,
+                contact: Some("alice".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_empty_query() {
+        assert_eq!(SearchQuery::parse("", Locale::En), Err(SearchError::Empty));
+        assert_eq!(
+            SearchQuery::parse("   ", Locale::En),
+            Err(SearchError::Empty)
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_tokens() {
+        assert!(SearchQuery::parse("since:2026-13-01", Locale::En).is_err());
+        assert!(SearchQuery::parse("until:yesterday", Locale::En).is_err());
+        assert!(SearchQuery::parse(">abc", Locale::En).is_err());
+        assert!(SearchQuery::parse(">", Locale::En).is_err());
+        assert!(SearchQuery::parse("<-5", Locale::En).is_err());
+        assert!(SearchQuery::parse("@", Locale::En).is_err());
+        assert!(SearchQuery::parse("@alice @bob", Locale::En).is_err());
+    }
+
+    #[test]
+    fn rejects_inverted_date_range() {
+        assert!(SearchQuery::parse("since:2026-02-01 until:2026-01-01", Locale::En).is_err());
+        assert!(SearchQuery::parse("since:2026-01-01 until:2026-01-01", Locale::En).is_err());
+    }
+}
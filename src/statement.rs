@@ -0,0 +1,195 @@
+use crate::amount;
+use crate::amount::format_signed;
+use crate::currency;
+use crate::db_util;
+use crate::i18n::t;
+use crate::models::User;
+use crate::BoxError;
+use bigdecimal::BigDecimal;
+use bigdecimal::Signed;
+use bigdecimal::Zero;
+use chrono::Datelike;
+use chrono::NaiveDate;
+use diesel::QueryableByName;
+
+/// Ограничение Telegram на длину одного сообщения
+pub const MAX_MESSAGE_LEN: usize = 4096;
+/// Сколько самых заметных изменений показывать в конце выписки
+const BIGGEST_CHANGES_SHOWN: usize = 3;
+
+/// Обороты с одним контрагентом за период. Суммы положительные, `opening` — со знаком:
+/// больше нуля — контрагент был должен пользователю
+#[derive(QueryableByName)]
+pub struct StatementRow {
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub counterparty_id: i32,
+    #[diesel(sql_type = diesel::sql_types::Numeric)]
+    pub opening: BigDecimal,
+    #[diesel(sql_type = diesel::sql_types::Numeric)]
+    pub lent: BigDecimal,
+    #[diesel(sql_type = diesel::sql_types::Numeric)]
+    pub borrowed: BigDecimal,
+}
+
+impl StatementRow {
+    pub fn closing(&self) -> BigDecimal {
+        &self.opening + &self.lent - &self.borrowed
+    }
+
+    /// Какая часть долга на начало периода погашена. Порядок платежей внутри периода
+    /// не учитывается: встречные суммы сперва закрывают старый долг
+    pub fn repaid(&self) -> BigDecimal {
+        let payments = if self.opening.is_positive() {
+            &self.borrowed
+        } else {
+            &self.lent
+        };
+        payments.clone().min(self.opening.abs())
+    }
+
+    /// Всё, что за период не ушло на погашение старого долга, — новые долги
+    pub fn new_debts(&self) -> BigDecimal {
+        &self.lent + &self.borrowed - self.repaid()
+    }
+
+    fn change(&self) -> BigDecimal {
+        &self.lent - &self.borrowed
+    }
+
+    fn is_empty(&self) -> bool {
+        self.opening.is_zero() && self.lent.is_zero() && self.borrowed.is_zero()
+    }
+}
+
+/// Текст выписки за `[since, until)` или `None`, если с контактами не было ни долгов, ни оборотов
+pub fn build_statement(
+    user: &User,
+    since: NaiveDate,
+    until: NaiveDate,
+) -> Result<Option<String>, BoxError> {
+    let rows: Vec<StatementRow> = db_util::get_statement_rows(
+        user,
+        since.and_hms_opt(0, 0, 0).expect("valid time").and_utc(),
+        until.and_hms_opt(0, 0, 0).expect("valid time").and_utc(),
+    )?
+    .into_iter()
+    .filter(|row| !row.is_empty())
+    .collect();
+    if rows.is_empty() {
+        return Ok(None);
+    }
+    let mut named_rows = Vec::with_capacity(rows.len());
+    for row in rows {
+        let counterparty = db_util::get_user_by_id(row.counterparty_id)?;
+        named_rows.push((db_util::get_contact_display_name(user, &counterparty), row));
+    }
+    named_rows.sort_by_key(|(name, _)| name.to_lowercase());
+
+    let locale = user.locale();
+    let mut lines = vec![
+        t!(
+            locale,
+            "statement-title",
+            month = since.month(),
+            year = since.year()
+        ),
+        String::new(),
+    ];
+    let zero = BigDecimal::from(0);
+    let (mut opening, mut new_debts, mut repaid, mut closing) =
+        (zero.clone(), zero.clone(), zero.clone(), zero);
+    for (name, row) in &named_rows {
+        lines.push(t!(
+            locale,
+            "statement-line",
+            name = name.as_str(),
+            opening = format_signed(&row.opening, locale),
+            closing = format_signed(&row.closing(), locale),
+            new_debts = amount::format_amount(&row.new_debts(), locale),
+            repaid = amount::format_amount(&row.repaid(), locale)
+        ));
+        opening += &row.opening;
+        new_debts += row.new_debts();
+        repaid += row.repaid();
+        closing += row.closing();
+    }
+    lines.push(String::new());
+    lines.push(t!(
+        locale,
+        "statement-opening",
+        amount = format_signed(&opening, locale),
+        currency = currency()
+    ));
+    lines.push(t!(
+        locale,
+        "statement-new-debts",
+        amount = amount::format_amount(&new_debts, locale),
+        currency = currency()
+    ));
+    lines.push(t!(
+        locale,
+        "statement-repaid",
+        amount = amount::format_amount(&repaid, locale),
+        currency = currency()
+    ));
+    lines.push(t!(
+        locale,
+        "statement-closing",
+        amount = format_signed(&closing, locale),
+        currency = currency()
+    ));
+
+    let mut changed: Vec<&(String, StatementRow)> = named_rows
+        .iter()
+        .filter(|(_, row)| !row.change().is_zero())
+        .collect();
+    changed.sort_by_key(|(_, row)| std::cmp::Reverse(row.change().abs()));
+    if !changed.is_empty() {
+        lines.push(String::new());
+        lines.push(t!(locale, "statement-biggest-changes"));
+        for (name, row) in changed.into_iter().take(BIGGEST_CHANGES_SHOWN) {
+            lines.push(format!("{name}: {}", format_signed(&row.change(), locale)));
+        }
+    }
+    lines.push(String::new());
+    lines.push(t!(locale, "statement-footer"));
+    Ok(Some(lines.join("\n")))
+}
+
+/// Режет текст на части не длиннее `limit` символов, по возможности по границам строк
+pub fn split_message(text: &str, limit: usize) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    for line in text.lines() {
+        let mut line = line.to_string();
+        // Строка длиннее лимита режется посимвольно
+        while line.chars().count() > limit {
+            if !current.is_empty() {
+                parts.push(std::mem::take(&mut current));
+            }
+            let split_at = line
+                .char_indices()
+                .nth(limit)
+                .map(|(index, _)| index)
+                .unwrap_or(line.len());
+            parts.push(line[..split_at].to_string());
+            line = line[split_at..].to_string();
+        }
+        let needed = if current.is_empty() {
+            line.chars().count()
+        } else {
+            current.chars().count() + 1 + line.chars().count()
+        };
+        if needed > limit {
+            parts.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push('\n');
+        }
+        current.push_str(&line);
+    }
+    if !current.is_empty() {
+        parts.push(current);
+    }
+    parts
+}
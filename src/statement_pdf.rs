@@ -0,0 +1,192 @@
+use crate::amount;
+use crate::amount::format_signed;
+use crate::currency;
+use crate::db_util;
+use crate::i18n::t;
+use crate::models::User;
+use crate::BoxError;
+use bigdecimal::BigDecimal;
+use bigdecimal::Signed;
+use chrono::Utc;
+use printpdf::IndirectFontRef;
+use printpdf::Mm;
+use printpdf::PdfDocument;
+use printpdf::PdfDocumentReference;
+use printpdf::PdfLayerReference;
+use std::io::Cursor;
+
+/// Шрифт с кириллицей встраивается в PDF, внешние утилиты и системные шрифты не нужны
+const FONT: &[u8] = include_bytes!("../assets/DejaVuSans.ttf");
+const PAGE_WIDTH: Mm = Mm(210.0);
+const PAGE_HEIGHT: Mm = Mm(297.0);
+const MARGIN: f32 = 15.0;
+const LINE_HEIGHT: f32 = 6.0;
+const FONT_SIZE: f32 = 10.0;
+/// Левые края колонок в мм: дата, операция, сумма, баланс, комментарий
+const COLUMNS: [f32; 5] = [MARGIN, 45.0, 70.0, 100.0, 130.0];
+const COMMENT_MAX_CHARS: usize = 32;
+/// Служебное имя слоя PDF, пользователю не показывается
+const LAYER_NAME: &str = "statement";
+
+/// Выписка по всем действующим транзакциям с контактом: нарастающий баланс и итоги.
+/// Подписывается именем бота и датой формирования
+pub fn contact_statement_pdf(
+    user: &User,
+    contact: &User,
+    bot_name: &str,
+) -> Result<Vec<u8>, BoxError> {
+    let locale = user.locale();
+    let contact_name = db_util::get_contact_display_name(user, contact);
+    let transactions: Vec<_> =
+        db_util::find_transactions_for_export(user, Some(contact), None, None)?
+            .into_iter()
+            .filter(|transaction| transaction.deleted_at.is_none())
+            .collect();
+    let generated_at = Utc::now();
+
+    let title = t!(
+        locale,
+        "pdf-title",
+        user = user.telegram_username.as_str(),
+        contact = contact_name.as_str()
+    );
+    let (document, page, layer) = PdfDocument::new(&title, PAGE_WIDTH, PAGE_HEIGHT, LAYER_NAME);
+    let font = document.add_external_font(Cursor::new(FONT))?;
+    let mut writer = PageWriter {
+        document: &document,
+        layer: document.get_page(page).get_layer(layer),
+        font: &font,
+        y: PAGE_HEIGHT.0 - MARGIN,
+    };
+
+    writer.line(&[(MARGIN, title.as_str())], 14.0);
+    writer.skip();
+    let header = [
+        t!(locale, "pdf-column-date"),
+        t!(locale, "pdf-column-operation"),
+        t!(locale, "pdf-column-amount"),
+        t!(locale, "pdf-column-balance"),
+        t!(locale, "pdf-column-comment"),
+    ];
+    let header = header.each_ref().map(String::as_str);
+    writer.row(&header);
+
+    let zero = BigDecimal::from(0);
+    let (mut lent, mut borrowed, mut balance) = (zero.clone(), zero.clone(), zero);
+    let (gave, took) = (t!(locale, "direction-gave"), t!(locale, "direction-took"));
+    for transaction in &transactions {
+        if writer.break_page_if_full() {
+            writer.row(&header);
+        }
+        let direction = if transaction.from_user_id == user.id {
+            lent += &transaction.amount;
+            balance += &transaction.amount;
+            gave.as_str()
+        } else {
+            borrowed += &transaction.amount;
+            balance -= &transaction.amount;
+            took.as_str()
+        };
+        let comment: String = transaction
+            .comment
+            .as_deref()
+            .unwrap_or_default()
+            .chars()
+            .take(COMMENT_MAX_CHARS)
+            .collect();
+        writer.row(&[
+            &transaction.created_at.format("%d.%m.%Y").to_string(),
+            direction,
+            &amount::format_amount(&transaction.amount, locale),
+            &format_signed(&balance, locale),
+            &comment,
+        ]);
+    }
+
+    writer.skip();
+    let totals = [
+        t!(
+            locale,
+            "pdf-total-lent",
+            amount = amount::format_amount(&lent, locale),
+            currency = currency()
+        ),
+        t!(
+            locale,
+            "pdf-total-borrowed",
+            amount = amount::format_amount(&borrowed, locale),
+            currency = currency()
+        ),
+        if balance.is_positive() {
+            t!(
+                locale,
+                "pdf-result-owed-to-you",
+                name = contact_name.as_str(),
+                amount = amount::format_amount(&balance, locale),
+                currency = currency()
+            )
+        } else if balance.is_negative() {
+            t!(
+                locale,
+                "pdf-result-you-owe",
+                name = contact_name.as_str(),
+                amount = amount::format_amount(&balance.abs(), locale),
+                currency = currency()
+            )
+        } else {
+            t!(locale, "pdf-result-settled")
+        },
+    ];
+    for total in &totals {
+        writer.line(&[(MARGIN, total.as_str())], FONT_SIZE);
+    }
+    writer.skip();
+    let signature = t!(
+        locale,
+        "pdf-signature",
+        bot = bot_name,
+        date = generated_at.format("%d.%m.%Y %H:%M").to_string()
+    );
+    writer.line(&[(MARGIN, signature.as_str())], 8.0);
+
+    Ok(document.save_to_bytes()?)
+}
+
+struct PageWriter<'a> {
+    document: &'a PdfDocumentReference,
+    layer: PdfLayerReference,
+    font: &'a IndirectFontRef,
+    /// Базовая линия следующей строки, мм от низа страницы
+    y: f32,
+}
+
+impl PageWriter<'_> {
+    fn line(&mut self, cells: &[(f32, &str)], font_size: f32) {
+        self.break_page_if_full();
+        for (x, text) in cells {
+            self.layer
+                .use_text(*text, font_size, Mm(*x), Mm(self.y), self.font);
+        }
+        self.y -= LINE_HEIGHT.max(font_size * 0.5);
+    }
+
+    fn row(&mut self, cells: &[&str; 5]) {
+        let cells: Vec<(f32, &str)> = COLUMNS.iter().copied().zip(cells.iter().copied()).collect();
+        self.line(&cells, FONT_SIZE);
+    }
+
+    fn skip(&mut self) {
+        self.y -= LINE_HEIGHT;
+    }
+
+    /// Начинает новую страницу, если на текущей не осталось места. `true`, если начала
+    fn break_page_if_full(&mut self) -> bool {
+        if self.y >= MARGIN {
+            return false;
+        }
+        let (page, layer) = self.document.add_page(PAGE_WIDTH, PAGE_HEIGHT, LAYER_NAME);
+        self.layer = self.document.get_page(page).get_layer(layer);
+        self.y = PAGE_HEIGHT.0 - MARGIN;
+        true
+    }
+}
@@ -0,0 +1,135 @@
+use crate::amount;
+use crate::currency;
+use crate::db_util;
+use crate::i18n::t;
+use crate::locale::Locale;
+use crate::models::User;
+use crate::BoxError;
+use chrono::DateTime;
+use chrono::Datelike;
+use chrono::NaiveDate;
+use chrono::Utc;
+use strum_macros::Display;
+use strum_macros::EnumString;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, Display)]
+#[strum(serialize_all = "snake_case")]
+pub enum StatsPeriod {
+    Month,
+    Quarter,
+    Year,
+    All,
+}
+
+impl StatsPeriod {
+    pub const ALL: [StatsPeriod; 4] = [
+        StatsPeriod::Month,
+        StatsPeriod::Quarter,
+        StatsPeriod::Year,
+        StatsPeriod::All,
+    ];
+
+    /// Начало текущего календарного периода, `None` — за всё время
+    pub fn since(self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let today = now.date_naive();
+        let start = match self {
+            StatsPeriod::Month => NaiveDate::from_ymd_opt(today.year(), today.month(), 1),
+            StatsPeriod::Quarter => {
+                NaiveDate::from_ymd_opt(today.year(), today.month0() / 3 * 3 + 1, 1)
+            }
+            StatsPeriod::Year => NaiveDate::from_ymd_opt(today.year(), 1, 1),
+            StatsPeriod::All => None,
+        }?;
+        Some(start.and_hms_opt(0, 0, 0).expect("valid time").and_utc())
+    }
+
+    pub fn label(self, locale: Locale) -> String {
+        match self {
+            StatsPeriod::Month => t!(locale, "stats-period-month"),
+            StatsPeriod::Quarter => t!(locale, "stats-period-quarter"),
+            StatsPeriod::Year => t!(locale, "stats-period-year"),
+            StatsPeriod::All => t!(locale, "stats-period-all"),
+        }
+    }
+
+    fn title(self, locale: Locale) -> String {
+        match self {
+            StatsPeriod::Month => t!(locale, "stats-title-month"),
+            StatsPeriod::Quarter => t!(locale, "stats-title-quarter"),
+            StatsPeriod::Year => t!(locale, "stats-title-year"),
+            StatsPeriod::All => t!(locale, "stats-title-all"),
+        }
+    }
+}
+
+pub fn stats_text(user: &User, period: StatsPeriod) -> Result<String, BoxError> {
+    let locale = user.locale();
+    let since = period.since(Utc::now());
+    let (lent, borrowed) = db_util::get_period_totals(user, since)?;
+    let open_debts = db_util::count_open_debts(user)?;
+    let mut lines = vec![
+        period.title(locale),
+        String::new(),
+        t!(
+            locale,
+            "stats-lent",
+            amount = amount::format_amount(&lent, locale),
+            currency = currency()
+        ),
+        t!(
+            locale,
+            "stats-borrowed",
+            amount = amount::format_amount(&borrowed, locale),
+            currency = currency()
+        ),
+        t!(locale, "stats-open-debts", count = open_debts),
+    ];
+    if let Some(days) = db_util::get_average_repay_days(user, since)? {
+        lines.push(t!(
+            locale,
+            "stats-average-repay",
+            days = format!("{days:.1}")
+        ));
+    }
+    if let Some((counterparty_id, count)) = db_util::find_most_frequent_counterparty(user, since)? {
+        let counterparty = db_util::get_user_by_id(counterparty_id)?;
+        let name = db_util::get_contact_display_name(user, &counterparty);
+        lines.push(t!(
+            locale,
+            "stats-most-frequent",
+            name = name,
+            count = count
+        ));
+    }
+    if let Some(largest) = db_util::find_largest_debt(user, since)? {
+        let (lent_by_user, counterparty_id) = if largest.from_user_id == user.id {
+            (true, largest.to_user_id)
+        } else {
+            (false, largest.from_user_id)
+        };
+        let counterparty = db_util::get_user_by_id(counterparty_id)?;
+        let name = db_util::get_contact_display_name(user, &counterparty);
+        let amount = amount::format_amount(&largest.amount, locale);
+        let date = largest.created_at.format("%d.%m.%Y").to_string();
+        lines.push(if lent_by_user {
+            t!(
+                locale,
+                "stats-largest-lent",
+                amount = amount,
+                currency = currency(),
+                name = name,
+                date = date
+            )
+        } else {
+            t!(
+                locale,
+                "stats-largest-borrowed",
+                amount = amount,
+                currency = currency(),
+                name = name,
+                date = date
+            )
+        });
+    }
+    Ok(lines.join("\n"))
+}
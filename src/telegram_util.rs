@@ -1,45 +1,300 @@
+use crate::amount;
+use crate::amount::AmountError;
+use crate::amount::AmountInput;
+use crate::audit_action::AuditAction;
+use crate::backup;
+use crate::chart;
+use crate::contact_sort::ContactSort;
+use crate::currency;
 use crate::db_util;
+use crate::db_util::ContactListEntry;
+use crate::export;
+use crate::export::ExportFilter;
+use crate::i18n::t;
+use crate::import;
 use crate::inputting_status::InputtingStatus;
+use crate::interest;
+use crate::interest::InterestRule;
+use crate::locale::Locale;
+use crate::models::NewRecurringTransaction;
+use crate::models::RecurringTransaction;
+use crate::models::Tag;
 use crate::models::Transaction;
 use crate::models::User;
+use crate::quick_entry;
+use crate::quick_entry::QuickEntry;
+use crate::recurrence_period;
+use crate::recurrence_period::RecurrencePeriod;
+use crate::reminder_frequency::ReminderFrequency;
+use crate::scheduler;
+use crate::screen::Screen;
+use crate::search::SearchQuery;
+use crate::statement_pdf;
+use crate::stats;
+use crate::stats::StatsPeriod;
+use crate::BoxError;
 use crate::HandlerResult;
 use bigdecimal::BigDecimal;
-use diesel::prelude::*;
+use bigdecimal::Signed;
+use chrono::NaiveDate;
+use chrono::Utc;
 use diesel::result::Error;
+use std::collections::HashMap;
 use std::str::FromStr;
 use strum_macros::Display;
 use strum_macros::EnumString;
 use teloxide::dispatching::DpHandlerDescription;
+use teloxide::net::Download;
 use teloxide::payloads::SendMessageSetters;
 use teloxide::prelude::*;
+use teloxide::types::BotCommand;
+use teloxide::types::FileId;
 use teloxide::types::InlineKeyboardButton;
 use teloxide::types::InlineKeyboardMarkup;
+use teloxide::types::InlineQueryResult;
+use teloxide::types::InlineQueryResultArticle;
+use teloxide::types::InlineQueryResultsButton;
+use teloxide::types::InlineQueryResultsButtonKind;
+use teloxide::types::InputFile;
+use teloxide::types::InputMessageContent;
+use teloxide::types::InputMessageContentText;
 use teloxide::types::MessageId;
+use teloxide::types::ReplyParameters;
 use teloxide::utils::command::BotCommands;
 use teloxide::utils::command::ParseError;
 use teloxide::Bot;
 
-const CALLBACK_SELECT_USER_PREFIX: &'static str = "selected_contact_";
+const CALLBACK_SELECT_USER_PREFIX: &str = "selected_contact_";
+const CALLBACK_CONTACTS_PAGE_PREFIX: &str = "contacts_page_";
+const CALLBACK_CONTACTS_SORT_PREFIX: &str = "contacts_sort_";
+const CALLBACK_TRANSACTION_PREFIX: &str = "transaction_";
+const CALLBACK_DELETE_TRANSACTION_PREFIX: &str = "delete_transaction_";
+const CALLBACK_TRANSACTION_INTEREST_PREFIX: &str = "transaction_interest_";
+const CALLBACK_REMINDER_FREQUENCY_PREFIX: &str = "reminder_frequency_";
+const CALLBACK_QUIET_HOURS_PREFIX: &str = "quiet_hours_";
+const CALLBACK_QUIET_HOURS_OFF: &str = "quiet_hours_off";
+const CALLBACK_MUTE_NUDGES_PREFIX: &str = "mute_nudges_";
+const CALLBACK_UNMUTE_NUDGES_PREFIX: &str = "unmute_nudges_";
+const CALLBACK_RECURRING_PREFIX: &str = "recurring_";
+const CALLBACK_PAUSE_RECURRING_PREFIX: &str = "pause_recurring_";
+const CALLBACK_RESUME_RECURRING_PREFIX: &str = "resume_recurring_";
+const CALLBACK_DELETE_RECURRING_PREFIX: &str = "delete_recurring_";
+const CALLBACK_CONFIRM_IMPORT_PREFIX: &str = "confirm_import_";
+const CALLBACK_CANCEL_IMPORT_PREFIX: &str = "cancel_import_";
+/// `confirm_amount_<id контакта>_<направление>`, направление как в `selected_transaction_duration`
+const CALLBACK_CONFIRM_AMOUNT_PREFIX: &str = "confirm_amount_";
+/// `confirm_quick_entry_<id контакта>_<направление>_<сумма>`, направление как в `confirm_amount_`
+const CALLBACK_CONFIRM_QUICK_ENTRY_PREFIX: &str = "confirm_quick_entry_";
+const CALLBACK_REVERT_IMPORT_PREFIX: &str = "revert_import_";
+const CALLBACK_STATS_PREFIX: &str = "stats_";
+const CALLBACK_FIND_PAGE_PREFIX: &str = "find_page_";
+const CALLBACK_TRANSACTION_TAGS_PREFIX: &str = "transaction_tags_";
+const CALLBACK_TRANSACTION_ATTACHMENT_PREFIX: &str = "transaction_attachment_";
+const CALLBACK_TOGGLE_TAG_PREFIX: &str = "toggle_tag_";
+const CALLBACK_SUMMARY_TAG_PREFIX: &str = "summary_tag_";
+const CALLBACK_LOCALE_PREFIX: &str = "locale_";
+const CALLBACK_LOCALE_AUTO: &str = "locale_auto";
+/// Параметр /start из inline-режима: сразу открыть действия с контактом
+const START_RECORD_PREFIX: &str = "record_";
+/// Ограничение длины названия тега, чтобы кнопки оставались читаемыми
+const TAG_NAME_MAX_CHARS: usize = 32;
+const HISTORY_LIMIT: i64 = 20;
+const SEARCH_PAGE_SIZE: i64 = 10;
+/// Четыре ряда по два контакта с балансом; если контактов больше, появляется поиск
+const CONTACTS_PAGE_SIZE: i64 = 8;
+const INLINE_RESULTS_LIMIT: i64 = 20;
+/// Ответ inline-запроса кэшируется ненадолго, чтобы баланс не устаревал
+const INLINE_CACHE_SECONDS: u32 = 10;
+/// Сколько ошибок импорта показывать в предпросмотре
+const IMPORT_ERRORS_SHOWN: usize = 10;
+/// Не чаще одного напоминания контакту в сутки
+const NUDGE_COOLDOWN_HOURS: i64 = 24;
+/// Сколько открытых записей перечислять в напоминании; остальные — одной строкой
+const NUDGE_ITEMS_SHOWN: usize = 10;
+/// Предустановки тихих часов по UTC: (начало, конец)
+const QUIET_HOURS_PRESETS: [(i16, i16); 2] = [(22, 8), (23, 9)];
 
 /*
   TODO
     Комментарии к долгу
     Подтверждения
     Расчитались
-    Кнопка НАЗАД В МЕНЮ
 */
 
 #[derive(BotCommands, Clone)]
 #[command(rename_rule = "lowercase")]
 enum Command {
     #[command()]
-    Start,
+    Start(String),
     #[command()]
     Debts,
     #[command()]
     Contacts,
     #[command()]
     Menu,
+    #[command()]
+    Settings,
+    #[command()]
+    Export(String),
+    #[command()]
+    Import,
+    #[command()]
+    Backup,
+    #[command()]
+    Restore,
+    #[command()]
+    Deleteme,
+    #[command()]
+    Chart,
+    #[command()]
+    Stats,
+    #[command()]
+    Find(String),
+    // Сумму разбираем уже в обработчике: правила зависят от языка пользователя
+    #[command()]
+    Gave(String),
+    #[command()]
+    Took(String),
+    #[command(parse_with = parse_contact_arg)]
+    Balance(String),
+    #[command(parse_with = parse_contact_arg)]
+    History(String),
+    #[command(parse_with = parse_add_contact_args)]
+    AddContact(String, Option<String>),
+    #[command(parse_with = parse_rename_args)]
+    Rename(String, String),
+}
+
+/// «<контакт> <сумма> [комментарий]». Контакт может быть из нескольких слов:
+/// он заканчивается там, где начинается сумма
+fn parse_debt_args(
+    input: &str,
+    locale: Locale,
+) -> Result<(String, BigDecimal, Option<String>), ParseError> {
+    let input = input.trim();
+    let mut first_error = None;
+    for (position, _) in input.match_indices(char::is_whitespace) {
+        let contact = input[..position].trim();
+        if contact.is_empty() {
+            continue;
+        }
+        match amount::parse_amount_input(&input[position..], locale) {
+            Ok(AmountInput::Exact { amount, comment })
+            | Ok(AmountInput::Computed {
+                amount, comment, ..
+            }) => return Ok((contact.to_string(), amount, comment.map(str::to_string))),
+            Err(error) => {
+                first_error.get_or_insert(error);
+            }
+        }
+    }
+    match first_error {
+        Some(error) => Err(ParseError::IncorrectFormat(Box::new(error))),
+        None => Err(ParseError::TooFewArguments {
+            expected: 2,
+            found: usize::from(!input.is_empty()),
+            message: "contact and amount are required".to_string(),
+        }),
+    }
+}
+
+fn parse_contact_arg(input: String) -> Result<(String,), ParseError> {
+    match input.trim() {
+        "" => Err(ParseError::TooFewArguments {
+            expected: 1,
+            found: 0,
+            message: "contact is required".to_string(),
+        }),
+        contact => Ok((contact.to_string(),)),
+    }
+}
+
+/// «@username [имя]»
+fn parse_add_contact_args(input: String) -> Result<(String, Option<String>), ParseError> {
+    let input = input.trim();
+    let (username, name) = input.split_once(char::is_whitespace).unwrap_or((input, ""));
+    let username = username.trim_start_matches('@');
+    if username.is_empty() {
+        return Err(ParseError::TooFewArguments {
+            expected: 1,
+            found: 0,
+            message: "username is required".to_string(),
+        });
+    }
+    let name = Some(name.trim()).filter(|name| !name.is_empty());
+    Ok((username.to_string(), name.map(str::to_string)))
+}
+
+/// «<старое имя> <новое имя>»: старое имя — одно слово или @username, новое — всё остальное
+fn parse_rename_args(input: String) -> Result<(String, String), ParseError> {
+    let input = input.trim();
+    match input.split_once(char::is_whitespace) {
+        Some((old, new)) if !new.trim().is_empty() => Ok((old.to_string(), new.trim().to_string())),
+        _ => Err(ParseError::TooFewArguments {
+            expected: 2,
+            found: usize::from(!input.is_empty()),
+            message: "old and new names are required".to_string(),
+        }),
+    }
+}
+
+/// Подсказка по аргументам для команд, которые их требуют
+fn command_usage(locale: Locale, command: &str) -> Option<String> {
+    match command {
+        "gave" => Some(t!(locale, "usage-gave")),
+        "took" => Some(t!(locale, "usage-took")),
+        "balance" => Some(t!(locale, "usage-balance")),
+        "history" => Some(t!(locale, "usage-history")),
+        "addcontact" => Some(t!(locale, "usage-add-contact")),
+        "rename" => Some(t!(locale, "usage-rename")),
+        _ => None,
+    }
+}
+
+/// Описание команды в меню Telegram
+fn command_description(locale: Locale, command: &str) -> String {
+    match command {
+        "start" => t!(locale, "command-start"),
+        "debts" => t!(locale, "command-debts"),
+        "contacts" => t!(locale, "command-contacts"),
+        "menu" => t!(locale, "command-menu"),
+        "settings" => t!(locale, "command-settings"),
+        "export" => t!(locale, "command-export"),
+        "import" => t!(locale, "command-import"),
+        "backup" => t!(locale, "command-backup"),
+        "restore" => t!(locale, "command-restore"),
+        "deleteme" => t!(locale, "command-deleteme"),
+        "chart" => t!(locale, "command-chart"),
+        "stats" => t!(locale, "command-stats"),
+        "find" => t!(locale, "command-find"),
+        "gave" => t!(locale, "command-gave"),
+        "took" => t!(locale, "command-took"),
+        "balance" => t!(locale, "command-balance"),
+        "history" => t!(locale, "command-history"),
+        "addcontact" => t!(locale, "command-add-contact"),
+        "rename" => t!(locale, "command-rename"),
+        _ => command.to_string(),
+    }
+}
+
+/// Меню команд в Telegram: для каждого языка интерфейса и основное, на языке по умолчанию
+pub async fn register_commands(bot: &Bot) -> HandlerResult {
+    for locale in Locale::ALL {
+        let commands = Command::bot_commands()
+            .into_iter()
+            .map(|command| {
+                let name = command.command.trim_start_matches('/');
+                BotCommand::new(name, command_description(locale, name))
+            })
+            .collect::<Vec<_>>();
+        if locale == Locale::DEFAULT {
+            bot.set_my_commands(commands.clone()).await?;
+        }
+        bot.set_my_commands(commands)
+            .language_code(locale.to_string())
+            .await?;
+    }
+    Ok(())
 }
 
 #[derive(EnumString, Display, Debug)]
@@ -54,37 +309,133 @@ enum MenuCommand {
     TransactionDirectionTook,
     TransactionSettledAccounts,
     TransactionHistory,
+    TransactionNoDueDate,
+    NudgeContact,
+    RecurringTransaction,
+    RecurringDirectionGave,
+    RecurringDirectionTook,
+    RecurringList,
+    Settings,
+    ConfirmDeleteAccount,
+    CancelDeleteAccount,
+    MonthlyStatementOn,
+    MonthlyStatementOff,
+    TransactionStatementPdf,
+    TransactionTagsDone,
+    TagSummary,
+    CancelAmount,
+    CancelQuickEntry,
+    Back,
+    Cancel,
+    SearchContacts,
+    ClearContactSearch,
 }
 
 pub fn message_handler_schema() -> Handler<'static, HandlerResult, DpHandlerDescription> {
     let commands = Update::filter_message()
         .filter(|msg: Message| msg.text().map(|t| t.starts_with('/')).unwrap_or(false))
         .endpoint(handle_command);
+    let documents = Update::filter_message()
+        .filter(|msg: Message| msg.document().is_some())
+        .endpoint(handle_document);
+    let photos = Update::filter_message()
+        .filter(|msg: Message| msg.photo().is_some())
+        .endpoint(handle_photo);
     let messages = Update::filter_message()
         .filter(|msg: Message| !msg.text().map(|t| t.starts_with('/')).unwrap_or(false))
         .endpoint(handle_message);
     let callbacks = Update::filter_callback_query().endpoint(handle_callback);
+    let inline_queries = Update::filter_inline_query().endpoint(handle_inline_query);
     dptree::entry()
         .branch(commands)
+        .branch(documents)
+        .branch(photos)
         .branch(messages)
         .branch(callbacks)
+        .branch(inline_queries)
+}
+
+/// `@grosze_bot alice` в любом чате: баланс с подходящими контактами и кнопка над
+/// результатами, открывающая бота сразу на записи долга первому из них
+async fn handle_inline_query(bot: Bot, query: InlineQuery) -> HandlerResult {
+    let Ok(user) = db_util::get_user_by_telegram_id(query.from.id.0 as i64) else {
+        let locale = query
+            .from
+            .language_code
+            .as_deref()
+            .map_or(Locale::DEFAULT, Locale::from_language_code);
+        bot.answer_inline_query(query.id, Vec::<InlineQueryResult>::new())
+            .is_personal(true)
+            .cache_time(INLINE_CACHE_SECONDS)
+            .button(InlineQueryResultsButton {
+                text: t!(locale, "inline-start-button"),
+                kind: InlineQueryResultsButtonKind::StartParameter("inline".to_string()),
+            })
+            .await?;
+        return Ok(());
+    };
+    let locale = user.locale();
+    let contacts = db_util::search_contacts_by_prefix(&user, &query.query, INLINE_RESULTS_LIMIT)?;
+    // Кнопку над результатами видит только отправитель; в отправленном сообщении
+    // она была бы бесполезна для собеседника
+    let record_button = contacts.first().map(|contact| InlineQueryResultsButton {
+        text: t!(locale, "inline-record-button", name = contact.name.as_str()),
+        kind: InlineQueryResultsButtonKind::StartParameter(format!(
+            "{START_RECORD_PREFIX}{}",
+            contact.contact_id
+        )),
+    });
+    let mut results = vec![];
+    for contact in contacts {
+        // Описание видит только отправитель, а сообщение уйдёт в чужой чат: в нём
+        // не «ты» и не личное имя контакта, а оба @username
+        let description = balance_text(locale, &contact.name, &contact.balance);
+        let text = shared_balance_text(&user, &contact);
+        let content = InputMessageContent::Text(InputMessageContentText::new(text));
+        let article =
+            InlineQueryResultArticle::new(contact.contact_id.to_string(), contact.name, content)
+                .description(description);
+        results.push(InlineQueryResult::Article(article));
+    }
+    let answer = bot
+        .answer_inline_query(query.id, results)
+        .is_personal(true)
+        .cache_time(INLINE_CACHE_SECONDS);
+    match record_button {
+        Some(button) => answer.button(button).await?,
+        None => answer.await?,
+    };
+    Ok(())
 }
 
 async fn handle_message(bot: Bot, msg: Message) -> HandlerResult {
     let telegram_id = msg.chat.id;
-    let user = db_util::get_user_by_telegram_id(telegram_id.0).unwrap();
-    let msg_text = msg.text().expect("ERROR getting message text").to_string();
+    remember_language_code(telegram_id.0, msg.from.as_ref());
+    let user = match db_util::get_user_by_telegram_id(telegram_id.0) {
+        Ok(user) => user,
+        Err(Error::NotFound) => return ask_to_start(&bot, telegram_id, msg.from.as_ref()).await,
+        Err(err) => return Err(err.into()),
+    };
+    let locale = user.locale();
+    let Some(msg_text) = msg.text().map(str::to_string) else {
+        bot.send_message(telegram_id, t!(locale, "unsupported-message"))
+            .await?;
+        return Ok(());
+    };
     match user.status {
-        InputtingStatus::None => {
-            bot.send_message(telegram_id, "Никакого действия не выбрано, зайди в /menu")
-                .await?;
-        }
+        InputtingStatus::None => match quick_entry::parse(&msg_text, locale) {
+            Some(entry) => ask_quick_entry_confirmation(&bot, &user, &msg, &entry).await?,
+            None => {
+                bot.send_message(telegram_id, t!(locale, "no-action-selected"))
+                    .await?;
+            }
+        },
         InputtingStatus::NewContactTelegramUsername => {
             let username = msg_text.replace("@", "");
             let result = add_new_contact(&user, &username);
             match result {
                 Ok(contact) => {
-                    bot.send_message(telegram_id, "Пришли как ты хочешь подписать этот контакт")
+                    bot.send_message(telegram_id, t!(locale, "new-contact-name-prompt"))
                         .await
                         .expect("ERROR executing NewContactTelegramUsername");
                     db_util::set_selected_contact(&user, contact.id)
@@ -92,9 +443,9 @@ async fn handle_message(bot: Bot, msg: Message) -> HandlerResult {
                     set_user_status(&user, &InputtingStatus::NewContactInternalName);
                 }
                 Err(_) => {
-                    bot.send_message(telegram_id, "Пользователь не найден\nСкорее всего он не зарегестирован в боте или ошибка в имени\nПришли еще раз или перейди в /menu")
-						.await
-						.expect("ERROR executing NewContactTelegramUsername");
+                    bot.send_message(telegram_id, t!(locale, "new-contact-not-found"))
+                        .await
+                        .expect("ERROR executing NewContactTelegramUsername");
                 }
             }
         }
@@ -103,12 +454,12 @@ async fn handle_message(bot: Bot, msg: Message) -> HandlerResult {
             let result = edit_contact(&user, &contact, &msg_text);
             match result {
                 Ok(_) => {
-                    bot.send_message(telegram_id, "Готово")
+                    bot.send_message(telegram_id, t!(locale, "done"))
                         .await
                         .expect("ERROR executing NewContactInternalName");
                 }
                 Err(_) => {
-                    bot.send_message(telegram_id, "Ошибка")
+                    bot.send_message(telegram_id, t!(locale, "error"))
                         .await
                         .expect("ERROR executing NewContactInternalName");
                 }
@@ -118,54 +469,333 @@ async fn handle_message(bot: Bot, msg: Message) -> HandlerResult {
         InputtingStatus::EditContactInternalName => {
             let contact = db_util::get_selected_contact(&user).unwrap();
             edit_contact(&user, &contact, &msg_text).expect("ERROR executing EditContact callback");
-            bot.send_message(telegram_id, "Готово")
+            bot.send_message(telegram_id, t!(locale, "done"))
                 .await
                 .expect("ERROR executing EditContactInternalName");
             send_menu(&bot, telegram_id).await;
         }
         InputtingStatus::DeleteContact => { /*TODO*/ }
         InputtingStatus::SelectContactForTransaction => {}
+        InputtingStatus::ContactSearch => {
+            // Найденное приходит новым сообщением, а поиск снимается со стека: «Назад» из
+            // выбора контакта ведёт туда же, откуда выбор открыли
+            let query = Some(msg_text.trim()).filter(|query| !query.is_empty());
+            let user = db_util::set_contact_search(&user, query)?;
+            let mut stack = user.screen_stack.clone();
+            if stack.last() == Some(&Screen::ContactSearch) {
+                stack.pop();
+            }
+            let user = db_util::set_screen_stack(&user, &stack)?;
+            set_user_status(&user, &contact_picker_status(&user));
+            let (text, keyboard) = contacts_page(&user, 0)?;
+            bot.send_message(telegram_id, text)
+                .reply_markup(keyboard)
+                .await?;
+        }
+        InputtingStatus::TransactionDueDate => match parse_due_date(&msg_text) {
+            Some(due_date) if due_date >= Utc::now().date_naive() => {
+                let transaction_id = user
+                    .selected_transaction_id
+                    .ok_or("no selected transaction")?;
+                let remind_at = scheduler::first_reminder_at(due_date, Utc::now());
+                db_util::set_transaction_due_date(&user, transaction_id, due_date, remind_at)?;
+                bot.send_message(telegram_id, t!(locale, "due-date-saved"))
+                    .await?;
+                send_menu(&bot, telegram_id).await;
+            }
+            _ => {
+                bot.send_message(telegram_id, t!(locale, "due-date-invalid"))
+                    .await?;
+            }
+        },
+        InputtingStatus::TransactionTags => {
+            let transaction_id = user
+                .selected_transaction_id
+                .ok_or("no selected transaction")?;
+            let name = msg_text
+                .trim()
+                .trim_start_matches('#')
+                .trim()
+                .to_lowercase();
+            if name.is_empty() || name.chars().count() > TAG_NAME_MAX_CHARS {
+                bot.send_message(
+                    telegram_id,
+                    t!(locale, "tag-name-invalid", max = TAG_NAME_MAX_CHARS),
+                )
+                .await?;
+                return Ok(());
+            }
+            let tag = db_util::find_or_create_tag(&user, &name)?;
+            db_util::add_transaction_tag(&user, transaction_id, &tag)?;
+            bot.send_message(telegram_id, t!(locale, "tags-prompt"))
+                .reply_markup(tags_keyboard(&user, transaction_id)?)
+                .await?;
+        }
+        InputtingStatus::RecurringTransaction => {
+            let contact = db_util::get_selected_contact(&user)?;
+            match parse_recurring_input(&user, &contact, &msg_text) {
+                Some(new_recurring) => {
+                    let recurring = db_util::create_recurring_transaction(&new_recurring)?;
+                    let text = t!(
+                        locale,
+                        "recurring-created",
+                        recurring = describe_recurring(&user, &recurring),
+                        next_run = recurring.next_run_at.format("%d.%m.%Y %H:%M").to_string()
+                    );
+                    bot.send_message(telegram_id, text).await?;
+                    send_menu(&bot, telegram_id).await;
+                }
+                None => {
+                    bot.send_message(telegram_id, t!(locale, "recurring-input-invalid"))
+                        .await?;
+                }
+            }
+        }
+        InputtingStatus::TransactionInterest => match interest::parse_rule(&msg_text, locale) {
+            Some(rule) => {
+                let transaction_id = user
+                    .selected_transaction_id
+                    .ok_or("no selected transaction")?;
+                let transaction = db_util::set_transaction_interest(&user, transaction_id, &rule)?;
+                let text = match (&rule, transaction.due_date) {
+                    (InterestRule::LateFee { .. }, None) => t!(locale, "late-fee-without-due-date"),
+                    _ => t!(locale, "done"),
+                };
+                bot.send_message(telegram_id, text).await?;
+                send_menu(&bot, telegram_id).await;
+            }
+            None => {
+                bot.send_message(telegram_id, t!(locale, "interest-rule-invalid"))
+                    .await?;
+            }
+        },
+        InputtingStatus::RestoreFile => {
+            bot.send_message(telegram_id, t!(locale, "waiting-backup-file"))
+                .await?;
+        }
+        InputtingStatus::ImportFile => {
+            bot.send_message(telegram_id, t!(locale, "waiting-import-file"))
+                .await?;
+        }
         InputtingStatus::TransactionAmount => {
             let contact = db_util::get_selected_contact(&user).unwrap();
             if user.selected_transaction_duration.eq(&Option::from(0)) {
-                create_transaction(
-                    &user,
-                    &contact,
-                    msg_text.as_str(),
-                    &bot,
-                    telegram_id.clone(),
-                )
-                .await;
+                create_transaction(&user, &user, &contact, &msg, false, &bot).await;
             } else {
-                create_transaction(
-                    &contact,
-                    &user,
-                    msg_text.as_str(),
-                    &bot,
-                    telegram_id.clone(),
+                create_transaction(&user, &contact, &user, &msg, false, &bot).await;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Фото чека вместо суммы: в подписи сумма и комментарий, фото прикрепляется к транзакции
+async fn handle_photo(bot: Bot, msg: Message) -> HandlerResult {
+    let telegram_id = msg.chat.id;
+    remember_language_code(telegram_id.0, msg.from.as_ref());
+    let user = db_util::get_user_by_telegram_id(telegram_id.0)?;
+    if user.status != InputtingStatus::TransactionAmount {
+        bot.send_message(telegram_id, t!(user.locale(), "photo-unexpected"))
+            .await?;
+        return Ok(());
+    }
+    if msg
+        .caption()
+        .is_none_or(|caption| caption.trim().is_empty())
+    {
+        bot.send_message(telegram_id, t!(user.locale(), "photo-caption-missing"))
+            .await?;
+        return Ok(());
+    }
+    let contact = db_util::get_selected_contact(&user)?;
+    let (from, to) = if user.selected_transaction_duration == Some(0) {
+        (&user, &contact)
+    } else {
+        (&contact, &user)
+    };
+    create_transaction(&user, from, to, &msg, false, &bot).await;
+    Ok(())
+}
+
+async fn handle_document(bot: Bot, msg: Message) -> HandlerResult {
+    let telegram_id = msg.chat.id;
+    remember_language_code(telegram_id.0, msg.from.as_ref());
+    let user = db_util::get_user_by_telegram_id(telegram_id.0)?;
+    let document = msg.document().expect("ERROR document is filtered");
+    if !matches!(
+        user.status,
+        InputtingStatus::ImportFile | InputtingStatus::RestoreFile
+    ) {
+        bot.send_message(telegram_id, t!(user.locale(), "document-unexpected"))
+            .await?;
+        return Ok(());
+    }
+    if document.file.size > import::MAX_FILE_SIZE {
+        bot.send_message(telegram_id, t!(user.locale(), "file-too-large"))
+            .await?;
+        return Ok(());
+    }
+    let file = bot.get_file(document.file.id.clone()).await?;
+    let mut content = vec![];
+    bot.download_file(&file.path, &mut content).await?;
+    let file_name = document.file_name.as_deref().unwrap_or_default();
+    if user.status == InputtingStatus::RestoreFile {
+        restore_backup(&bot, &user, telegram_id, &content).await
+    } else {
+        preview_import(&bot, &user, telegram_id, file_name, &content).await
+    }
+}
+
+async fn restore_backup(
+    bot: &Bot,
+    user: &User,
+    telegram_id: ChatId,
+    content: &[u8],
+) -> HandlerResult {
+    let locale = user.locale();
+    let text = match backup::parse_backup(content) {
+        // Чужую копию не загружаем: она бы записала долги от имени другого человека
+        Ok(backup) if Some(backup.profile.telegram_id) != user.telegram_id => {
+            t!(locale, "backup-wrong-owner")
+        }
+        Ok(backup) => match db_util::restore_backup(user, &backup) {
+            Ok(summary) => {
+                set_user_status(user, &InputtingStatus::None);
+                t!(
+                    locale,
+                    "backup-restored",
+                    contacts = summary.contacts,
+                    transactions = summary.transactions,
+                    duplicates = summary.duplicates,
+                    skipped = summary.skipped
                 )
-                .await;
             }
+            Err(err) => {
+                log::warn!("Error restoring backup: {:?}", err);
+                t!(locale, "backup-restore-failed")
+            }
+        },
+        Err(err) => t!(locale, "file-unreadable", error = err.message(locale)),
+    };
+    bot.send_message(telegram_id, text).await?;
+    Ok(())
+}
+
+async fn preview_import(
+    bot: &Bot,
+    user: &User,
+    telegram_id: ChatId,
+    file_name: &str,
+    content: &[u8],
+) -> HandlerResult {
+    let locale = user.locale();
+    let preview = match import::preview(user, file_name, content) {
+        Ok(preview) => preview,
+        Err(err) => {
+            let error = err.message(locale);
+            bot.send_message(telegram_id, t!(locale, "file-unreadable", error = error))
+                .await?;
+            return Ok(());
         }
+    };
+    let mut text = t!(locale, "import-rows", count = preview.total);
+    if preview.skipped > 0 {
+        text += &t!(locale, "import-skipped", count = preview.skipped);
     }
+    if preview.errors.is_empty() {
+        text += &t!(locale, "import-no-errors");
+    } else {
+        let lines = preview
+            .errors
+            .iter()
+            .map(|error| error.line.to_string())
+            .collect::<Vec<_>>();
+        text += &t!(
+            locale,
+            "import-errors",
+            count = preview.errors.len(),
+            lines_count = lines.len(),
+            lines = join_with_and(locale, &lines)
+        );
+        for error in preview.errors.iter().take(IMPORT_ERRORS_SHOWN) {
+            text += "\n";
+            text += &t!(
+                locale,
+                "import-error-line",
+                line = error.line,
+                message = error.kind.message(locale)
+            );
+        }
+    }
+    if preview.transactions.is_empty() {
+        text += "\n\n";
+        text += &t!(locale, "import-nothing");
+        bot.send_message(telegram_id, text).await?;
+        return Ok(());
+    }
+    let batch = db_util::create_import_batch(user, &preview.transactions)?;
+    set_user_status(user, &InputtingStatus::None);
+    let keyboard = InlineKeyboardMarkup::new(vec![vec![
+        InlineKeyboardButton::callback(
+            t!(
+                locale,
+                "import-confirm-button",
+                count = preview.transactions.len()
+            ),
+            format!("{CALLBACK_CONFIRM_IMPORT_PREFIX}{}", batch.id),
+        ),
+        InlineKeyboardButton::callback(
+            t!(locale, "cancel-button"),
+            format!("{CALLBACK_CANCEL_IMPORT_PREFIX}{}", batch.id),
+        ),
+    ]]);
+    bot.send_message(telegram_id, text)
+        .reply_markup(keyboard)
+        .await?;
     Ok(())
 }
 
 async fn handle_command(bot: Bot, msg: Message) -> HandlerResult {
     let telegram_id = msg.chat.id;
+    remember_language_code(telegram_id.0, msg.from.as_ref());
     if let Some(text) = msg.text() {
         match Command::parse(text, "") {
-            Ok(Command::Start) => {
+            Ok(Command::Start(payload)) => {
                 let username = msg
                     .chat
                     .username()
                     .expect("ERROR Username is not null")
                     .to_string();
-                db_util::find_or_create_user(telegram_id.0, &username);
-                send_menu(&bot, telegram_id.clone()).await;
+                let user = db_util::find_or_create_user(telegram_id.0, &username);
+                // Новый пользователь появился только что — язык запоминаем уже для него
+                remember_language_code(telegram_id.0, msg.from.as_ref());
+                // Ссылка «записать долг» из inline-режима: t.me/<bot>?start=record_<id>
+                let contact_id = payload
+                    .trim()
+                    .strip_prefix(START_RECORD_PREFIX)
+                    .and_then(|id| id.parse::<i32>().ok())
+                    .filter(|&id| {
+                        db_util::find_all_contacts_for_user(&user)
+                            .iter()
+                            .any(|contact| contact.contact_id == id)
+                    });
+                match contact_id {
+                    Some(contact_id) => {
+                        db_util::set_selected_contact(&user, contact_id)?;
+                        let user = db_util::set_screen_stack(&user, &[Screen::ContactActions])?;
+                        set_user_status(&user, &InputtingStatus::SelectContactForTransaction);
+                        let locale = user.locale();
+                        let keyboard =
+                            contact_actions_keyboard(locale).append_row(navigation_row(locale));
+                        bot.send_message(telegram_id, t!(locale, "choose"))
+                            .reply_markup(keyboard)
+                            .await?;
+                    }
+                    None => send_menu(&bot, telegram_id).await,
+                }
             }
             Ok(Command::Menu) => {
-                send_menu(&bot, telegram_id.clone()).await;
+                send_menu(&bot, telegram_id).await;
             }
             Ok(Command::Debts) => {
                 let username = msg
@@ -174,57 +804,358 @@ async fn handle_command(bot: Bot, msg: Message) -> HandlerResult {
                     .expect("ERROR Username is not null")
                     .to_string();
                 let user = db_util::find_or_create_user(telegram_id.0, &username);
-                let summary: Vec<(String, BigDecimal)> = get_debit(&user).expect("DB error");
-                let text = summary
-                    .iter()
-                    .map(|(name, amount)| format!("{}: {}", name, amount))
-                    .collect::<Vec<_>>()
-                    .join("\n");
+                let text = debit_summary_text(&user, None)?;
                 bot.send_message(telegram_id, text)
+                    .reply_markup(summary_keyboard(&user)?)
                     .await
                     .expect("ERROR executing getting debits");
-                send_menu(&bot, telegram_id.clone()).await;
+                send_menu(&bot, telegram_id).await;
+            }
+            Ok(Command::Settings) => {
+                let user = db_util::get_user_by_telegram_id(telegram_id.0)?;
+                bot.send_message(telegram_id, settings_text(&user))
+                    .reply_markup(settings_keyboard(&user))
+                    .await?;
+            }
+            Ok(Command::Export(args)) => {
+                let user = db_util::get_user_by_telegram_id(telegram_id.0)?;
+                let Some(filter) = ExportFilter::parse(&args) else {
+                    bot.send_message(telegram_id, t!(user.locale(), "export-usage"))
+                        .await?;
+                    return Ok(());
+                };
+                match export::transactions_csv(&user, &filter) {
+                    Ok(csv) => {
+                        bot.send_document(
+                            telegram_id,
+                            InputFile::memory(csv).file_name("grosze.csv"),
+                        )
+                        .await?;
+                    }
+                    Err(err) => {
+                        log::warn!("Error exporting transactions: {:?}", err);
+                        bot.send_message(telegram_id, t!(user.locale(), "export-failed"))
+                            .await?;
+                    }
+                }
+            }
+            Ok(Command::Import) => {
+                let user = db_util::get_user_by_telegram_id(telegram_id.0)?;
+                set_user_status(&user, &InputtingStatus::ImportFile);
+                let text = t!(user.locale(), "import-prompt");
+                let batches = db_util::find_imported_batches(&user, 5)?;
+                let mut request = bot.send_message(telegram_id, text);
+                if !batches.is_empty() {
+                    let lines = batches
+                        .iter()
+                        .map(|batch| {
+                            vec![InlineKeyboardButton::callback(
+                                t!(
+                                    user.locale(),
+                                    "import-revert-button",
+                                    date = batch.created_at.format("%d.%m.%Y %H:%M").to_string()
+                                ),
+                                format!("{CALLBACK_REVERT_IMPORT_PREFIX}{}", batch.id),
+                            )]
+                        })
+                        .collect::<Vec<_>>();
+                    request = request.reply_markup(InlineKeyboardMarkup::new(lines));
+                }
+                request.await?;
+            }
+            Ok(Command::Backup) => {
+                let user = db_util::get_user_by_telegram_id(telegram_id.0)?;
+                let backup = backup::build_backup(&user)?;
+                let file_name = format!("grosze-backup-{}.json", Utc::now().format("%Y%m%d"));
+                let json = serde_json::to_vec_pretty(&backup)?;
+                bot.send_document(telegram_id, InputFile::memory(json).file_name(file_name))
+                    .caption(t!(user.locale(), "backup-caption"))
+                    .await?;
+            }
+            Ok(Command::Restore) => {
+                let user = db_util::get_user_by_telegram_id(telegram_id.0)?;
+                set_user_status(&user, &InputtingStatus::RestoreFile);
+                bot.send_message(telegram_id, t!(user.locale(), "restore-prompt"))
+                    .await?;
+            }
+            Ok(Command::Find(args)) => {
+                let user = db_util::get_user_by_telegram_id(telegram_id.0)?;
+                if let Err(err) = SearchQuery::parse(&args, user.locale()) {
+                    bot.send_message(
+                        telegram_id,
+                        t!(
+                            user.locale(),
+                            "find-usage",
+                            error = err.message(user.locale())
+                        ),
+                    )
+                    .await?;
+                    return Ok(());
+                }
+                let user = db_util::set_search_query(&user, args.trim())?;
+                let (text, keyboard) = search_results_page(&user, 0)?;
+                bot.send_message(telegram_id, text)
+                    .reply_markup(keyboard)
+                    .await?;
+            }
+            Ok(Command::Stats) => {
+                let user = db_util::get_user_by_telegram_id(telegram_id.0)?;
+                bot.send_message(telegram_id, stats::stats_text(&user, StatsPeriod::Month)?)
+                    .reply_markup(stats_keyboard(user.locale()))
+                    .await?;
+            }
+            Ok(Command::Chart) => {
+                let user = db_util::get_user_by_telegram_id(telegram_id.0)?;
+                // Отрисовка и запросы к БД блокирующие, держать на них async-поток нельзя
+                let chart_user = user.clone();
+                let chart =
+                    tokio::task::spawn_blocking(move || chart::balance_chart(&chart_user)).await?;
+                match chart? {
+                    Some(png) => {
+                        bot.send_photo(telegram_id, InputFile::memory(png).file_name("chart.png"))
+                            .await?;
+                    }
+                    None => {
+                        bot.send_message(telegram_id, t!(user.locale(), "chart-empty"))
+                            .await?;
+                    }
+                }
+            }
+            Ok(Command::Deleteme) => {
+                let user = db_util::get_user_by_telegram_id(telegram_id.0)?;
+                let locale = user.locale();
+                let keyboard = InlineKeyboardMarkup::new(vec![vec![
+                    InlineKeyboardButton::callback(
+                        t!(locale, "delete-account-confirm-button"),
+                        MenuCommand::ConfirmDeleteAccount.to_string(),
+                    ),
+                    InlineKeyboardButton::callback(
+                        t!(locale, "cancel-button"),
+                        MenuCommand::CancelDeleteAccount.to_string(),
+                    ),
+                ]]);
+                bot.send_message(telegram_id, t!(locale, "delete-account-prompt"))
+                    .reply_markup(keyboard)
+                    .await?;
             }
             Ok(Command::Contacts) => {
-                let user = db_util::get_user_by_telegram_id(telegram_id.0).unwrap();
+                let user = db_util::get_user_by_telegram_id(telegram_id.0)?;
                 let contacts_str = get_contacts_names(&user).join("\n");
-                bot.send_message(telegram_id, format!("Твои контакты:\n{contacts_str}"))
-                    .await
-                    .expect("ERROR executing getting contacts");
+                bot.send_message(
+                    telegram_id,
+                    t!(user.locale(), "contacts-list", contacts = contacts_str),
+                )
+                .await
+                .expect("ERROR executing getting contacts");
+            }
+            Ok(Command::Gave(args)) => {
+                let user = db_util::get_user_by_telegram_id(telegram_id.0)?;
+                let reply = match parse_debt_args(&args, user.locale()) {
+                    Ok((contact, amount, comment)) => {
+                        let entry = QuickEntry {
+                            lent: true,
+                            contact: &contact,
+                            amount,
+                            comment: comment.as_deref(),
+                        };
+                        Some(save_command_entry(&user, &entry)?)
+                    }
+                    Err(err) => command_error_text(user.locale(), text, &err),
+                };
+                if let Some(reply) = reply {
+                    bot.send_message(telegram_id, reply).await?;
+                }
+            }
+            Ok(Command::Took(args)) => {
+                let user = db_util::get_user_by_telegram_id(telegram_id.0)?;
+                let reply = match parse_debt_args(&args, user.locale()) {
+                    Ok((contact, amount, comment)) => {
+                        let entry = QuickEntry {
+                            lent: false,
+                            contact: &contact,
+                            amount,
+                            comment: comment.as_deref(),
+                        };
+                        Some(save_command_entry(&user, &entry)?)
+                    }
+                    Err(err) => command_error_text(user.locale(), text, &err),
+                };
+                if let Some(reply) = reply {
+                    bot.send_message(telegram_id, reply).await?;
+                }
+            }
+            Ok(Command::Balance(contact)) => {
+                let user = db_util::get_user_by_telegram_id(telegram_id.0)?;
+                let text = match find_contact_by_word(&user, &contact)? {
+                    Ok(contact) => {
+                        let name = db_util::get_contact_display_name(&user, &contact);
+                        let balance = db_util::get_balance(&user, &contact)?;
+                        balance_text(user.locale(), &name, &balance)
+                    }
+                    Err(text) => text,
+                };
+                bot.send_message(telegram_id, text).await?;
+            }
+            Ok(Command::History(contact)) => {
+                let user = db_util::get_user_by_telegram_id(telegram_id.0)?;
+                let locale = user.locale();
+                let contact = match find_contact_by_word(&user, &contact)? {
+                    Ok(contact) => contact,
+                    Err(text) => {
+                        bot.send_message(telegram_id, text).await?;
+                        return Ok(());
+                    }
+                };
+                db_util::set_selected_contact(&user, contact.id)?;
+                match transaction_history_keyboard(&user, &contact)? {
+                    Some(keyboard) => {
+                        bot.send_message(telegram_id, t!(locale, "history"))
+                            .reply_markup(keyboard)
+                            .await?;
+                    }
+                    None => {
+                        bot.send_message(telegram_id, t!(locale, "history-empty"))
+                            .await?;
+                    }
+                }
+            }
+            Ok(Command::AddContact(username, name)) => {
+                let user = db_util::get_user_by_telegram_id(telegram_id.0)?;
+                let locale = user.locale();
+                let text = match db_util::get_user_by_username(&username) {
+                    Ok(contact) => {
+                        db_util::find_or_create_contact(&user, &contact);
+                        if let Some(name) = &name {
+                            db_util::edit_contact(&user, &contact, name)?;
+                        }
+                        let name = db_util::get_contact_display_name(&user, &contact);
+                        t!(locale, "contact-added", name = name)
+                    }
+                    Err(Error::NotFound) => {
+                        t!(locale, "contact-not-registered", username = username)
+                    }
+                    Err(err) => return Err(err.into()),
+                };
+                bot.send_message(telegram_id, text).await?;
+            }
+            Ok(Command::Rename(old, new)) => {
+                let user = db_util::get_user_by_telegram_id(telegram_id.0)?;
+                let locale = user.locale();
+                let text = match find_contact_by_word(&user, &old)? {
+                    Ok(contact) => {
+                        let old = db_util::get_contact_display_name(&user, &contact);
+                        db_util::edit_contact(&user, &contact, &new)?;
+                        t!(locale, "contact-renamed", old = old, new = new)
+                    }
+                    Err(text) => text,
+                };
+                bot.send_message(telegram_id, text).await?;
+            }
+            // Чужие команды и команды другим ботам в группах не наши
+            Err(ParseError::UnknownCommand(_) | ParseError::WrongBotName(_)) => {}
+            Err(err) => {
+                let user = db_util::get_user_by_telegram_id(telegram_id.0)?;
+                if let Some(reply) = command_error_text(user.locale(), text, &err) {
+                    bot.send_message(telegram_id, reply).await?;
+                }
             }
-            Err(_) => {}
         }
     }
     Ok(())
 }
 
+/// Причина ошибки, если она в сумме, и подсказка по команде из `text`
+fn command_error_text(locale: Locale, text: &str, err: &ParseError) -> Option<String> {
+    let command = text
+        .split_whitespace()
+        .next()
+        .and_then(|command| command.trim_start_matches('/').split('@').next())
+        .unwrap_or_default()
+        .to_lowercase();
+    let Some(usage) = command_usage(locale, &command) else {
+        log::warn!("Error parsing command {text}: {err:?}");
+        return None;
+    };
+    let reason = match err {
+        ParseError::IncorrectFormat(error) => error
+            .downcast_ref::<AmountError>()
+            .map(|error| error.message(locale) + "\n"),
+        _ => None,
+    };
+    Some(reason.unwrap_or_default() + &usage)
+}
+
+/// Записывает долг из /gave или /took и возвращает ответ для пользователя
+fn save_command_entry(user: &User, entry: &QuickEntry) -> Result<String, BoxError> {
+    let contact = match find_contact_by_word(user, entry.contact)? {
+        Ok(contact) => contact,
+        Err(text) => return Ok(text),
+    };
+    let (from, to) = if entry.lent {
+        (user, &contact)
+    } else {
+        (&contact, user)
+    };
+    db_util::create_transaction(user, from, to, entry.amount.clone(), entry.comment, None);
+    Ok(t!(
+        user.locale(),
+        "quick-entry-saved",
+        entry = describe_quick_entry(user, &contact, entry)
+    ))
+}
+
 async fn handle_callback(bot: Bot, callback: CallbackQuery) -> HandlerResult {
     let telegram_id = callback.from.id;
-    let message_id = callback.message.expect("Message ID not found").id();
-    let mut user = db_util::get_user_by_telegram_id(telegram_id.0 as i64).unwrap(); //FIXME если нет пользователя то приложение падает
+    let message = callback.message.expect("Message ID not found");
+    let message_id = message.id();
+    remember_language_code(telegram_id.0 as i64, Some(&callback.from));
     bot.answer_callback_query(callback.id.clone()).await?;
+    let mut user = match db_util::get_user_by_telegram_id(telegram_id.0 as i64) {
+        Ok(user) => user,
+        Err(Error::NotFound) => {
+            return ask_to_start(&bot, ChatId(telegram_id.0 as i64), Some(&callback.from)).await;
+        }
+        Err(err) => return Err(err.into()),
+    };
+    let locale = user.locale();
     if let Some(data) = callback.data {
         match data.parse::<MenuCommand>() {
             Ok(MenuCommand::AddNewContact) => {
-                bot.edit_message_text(
-                    telegram_id,
-                    message_id,
-                    "Пришли telegram username нового контакта",
-                )
-                .await?;
-                set_user_status(&user, &InputtingStatus::NewContactTelegramUsername);
+                open_screen(&bot, &user, Screen::AddContact, telegram_id, message_id).await?;
+            }
+            Ok(MenuCommand::Back) => {
+                go_back(&bot, &user, telegram_id, message_id).await?;
+            }
+            Ok(MenuCommand::Cancel) => {
+                let user = db_util::set_screen_stack(&user, &[])?;
+                show_screen(&bot, &user, Screen::Menu, telegram_id, message_id).await?;
+            }
+            Ok(MenuCommand::CancelAmount) => {
+                bot.edit_message_text(telegram_id, message_id, t!(locale, "amount-prompt"))
+                    .reply_markup(InlineKeyboardMarkup::new(vec![navigation_row(locale)]))
+                    .await?;
+            }
+            Ok(MenuCommand::CancelQuickEntry) => {
+                bot.edit_message_text(telegram_id, message_id, t!(locale, "quick-entry-cancelled"))
+                    .await?;
             }
             Ok(MenuCommand::SelectContact) => {
-                send_contacts(&bot, &user, telegram_id.clone(), message_id.clone())
-                    .await
-                    .expect("ERROR executing SelectContact");
-                set_user_status(&user, &InputtingStatus::SelectContactForTransaction);
+                let user = db_util::set_contact_search(&user, None)?;
+                open_screen(&bot, &user, Screen::SelectContact, telegram_id, message_id).await?;
             }
             Ok(MenuCommand::EditContact) => {
-                send_contacts(&bot, &user, telegram_id.clone(), message_id.clone())
-                    .await
-                    .expect("ERROR executing EditContact");
-                set_user_status(&user, &InputtingStatus::EditContactInternalName);
+                let user = db_util::set_contact_search(&user, None)?;
+                open_screen(&bot, &user, Screen::EditContact, telegram_id, message_id).await?;
+            }
+            Ok(MenuCommand::SearchContacts) => {
+                open_screen(&bot, &user, Screen::ContactSearch, telegram_id, message_id).await?;
+            }
+            Ok(MenuCommand::ClearContactSearch) => {
+                let user = db_util::set_contact_search(&user, None)?;
+                let (text, keyboard) = contacts_page(&user, 0)?;
+                bot.edit_message_text(telegram_id, message_id, text)
+                    .reply_markup(keyboard)
+                    .await?;
             }
             Ok(MenuCommand::DeleteContact) => {
                 // TODO
@@ -236,41 +1167,442 @@ async fn handle_callback(bot: Bot, callback: CallbackQuery) -> HandlerResult {
                 user.selected_transaction_duration = Option::from(0);
                 db_util::set_selected_transaction_duration(&user, 0)
                     .expect("ERROR executing TransactionDirectionGave");
-                set_user_status(&user, &InputtingStatus::TransactionAmount);
-                let text = "Пришли сумму или вернись в /menu для отмены";
-                bot.edit_message_text(telegram_id, message_id, text)
-                    .await
-                    .expect("ERROR executing EditContact");
+                open_screen(
+                    &bot,
+                    &user,
+                    Screen::TransactionAmount,
+                    telegram_id,
+                    message_id,
+                )
+                .await?;
             }
             Ok(MenuCommand::TransactionDirectionTook) => {
                 user.selected_transaction_duration = Option::from(1);
                 db_util::set_selected_transaction_duration(&user, 1)
                     .expect("ERROR executing TransactionDirectionTook");
-                set_user_status(&user, &InputtingStatus::TransactionAmount);
-                let text = "Пришли сумму или вернись в /menu для отмены";
-                bot.edit_message_text(telegram_id, message_id, text)
-                    .await
-                    .expect("ERROR executing EditContact");
+                open_screen(
+                    &bot,
+                    &user,
+                    Screen::TransactionAmount,
+                    telegram_id,
+                    message_id,
+                )
+                .await?;
             }
             Ok(MenuCommand::Debts) => {
-                let summary: Vec<(String, BigDecimal)> = get_debit(&user).expect("DB error");
-                let text = summary
-                    .iter()
-                    .map(|(name, amount)| format!("{}: {}", name, amount))
-                    .collect::<Vec<_>>()
-                    .join("\n");
+                open_screen(&bot, &user, Screen::Debts, telegram_id, message_id).await?;
+            }
+            Ok(MenuCommand::TagSummary) => {
+                let summary = db_util::get_tag_summary(&user)?;
+                let text = if summary.is_empty() {
+                    t!(locale, "tag-summary-empty")
+                } else {
+                    let lines = summary
+                        .iter()
+                        .map(|(name, lent, borrowed)| {
+                            t!(
+                                locale,
+                                "tag-summary-line",
+                                tag = name.as_str(),
+                                lent = amount::format_amount(lent, locale),
+                                borrowed = amount::format_amount(borrowed, locale),
+                                currency = currency()
+                            )
+                        })
+                        .collect::<Vec<_>>()
+                        .join("\n");
+                    t!(locale, "tag-summary", lines = lines)
+                };
                 bot.edit_message_text(telegram_id, message_id, text)
-                    .await
-                    .expect("ERROR executing getting debits");
+                    .reply_markup(summary_keyboard(&user)?)
+                    .await?;
+            }
+            Ok(MenuCommand::TransactionTagsDone) => {
+                let transaction_id = user
+                    .selected_transaction_id
+                    .ok_or("no selected transaction")?;
+                if user.status == InputtingStatus::TransactionTags {
+                    let transaction = db_util::find_transaction_for_user(&user, transaction_id)?;
+                    // Срок ставит только кредитор, должнику запись на этом заканчивается
+                    if transaction.from_user_id == user.id {
+                        set_user_status(&user, &InputtingStatus::TransactionDueDate);
+                        bot.edit_message_text(
+                            telegram_id,
+                            message_id,
+                            t!(locale, "due-date-prompt"),
+                        )
+                        .reply_markup(due_date_keyboard(locale))
+                        .await?;
+                    } else {
+                        finish_transaction_entry(&bot, &user, telegram_id, message_id).await?;
+                    }
+                } else {
+                    send_transaction_view(&bot, &user, transaction_id, telegram_id, message_id)
+                        .await?;
+                }
             }
             Ok(MenuCommand::TransactionSettledAccounts) => { /*TODO*/ }
-            Ok(MenuCommand::TransactionHistory) => { /*TODO*/ }
+            Ok(MenuCommand::TransactionHistory) => {
+                send_transaction_history(&bot, &user, telegram_id, message_id).await?;
+            }
+            Ok(MenuCommand::TransactionStatementPdf) => {
+                let contact = db_util::get_selected_contact(&user)?;
+                let me = bot.get_me().await?;
+                let bot_name = me.username.as_deref().unwrap_or("grosze_bot");
+                let pdf = statement_pdf::contact_statement_pdf(&user, &contact, bot_name)?;
+                let file_name = format!(
+                    "statement-{}-{}.pdf",
+                    contact.telegram_username,
+                    Utc::now().format("%Y%m%d")
+                );
+                bot.send_document(telegram_id, InputFile::memory(pdf).file_name(file_name))
+                    .await?;
+            }
+            Ok(MenuCommand::TransactionNoDueDate) => {
+                finish_transaction_entry(&bot, &user, telegram_id, message_id).await?;
+            }
+            Ok(MenuCommand::NudgeContact) => {
+                let contact = db_util::get_selected_contact(&user)?;
+                let text = send_nudge(&bot, &user, &contact).await?;
+                bot.edit_message_text(telegram_id, message_id, text).await?;
+            }
+            Ok(MenuCommand::RecurringTransaction) => {
+                let keyboard = InlineKeyboardMarkup::new(vec![vec![
+                    InlineKeyboardButton::callback(
+                        t!(locale, "recurring-gave-button"),
+                        MenuCommand::RecurringDirectionGave.to_string(),
+                    ),
+                    InlineKeyboardButton::callback(
+                        t!(locale, "recurring-took-button"),
+                        MenuCommand::RecurringDirectionTook.to_string(),
+                    ),
+                ]]);
+                bot.edit_message_text(telegram_id, message_id, t!(locale, "recurring-direction"))
+                    .reply_markup(keyboard)
+                    .await?;
+            }
+            Ok(MenuCommand::RecurringDirectionGave) => {
+                ask_recurring_input(&bot, &user, 0, telegram_id, message_id).await?;
+            }
+            Ok(MenuCommand::RecurringDirectionTook) => {
+                ask_recurring_input(&bot, &user, 1, telegram_id, message_id).await?;
+            }
+            Ok(MenuCommand::RecurringList) => {
+                send_recurring_list(&bot, &user, telegram_id, message_id).await?;
+            }
+            Ok(MenuCommand::ConfirmDeleteAccount) => {
+                db_util::anonymize_user(&user)?;
+                bot.edit_message_text(telegram_id, message_id, t!(locale, "account-deleted"))
+                    .await?;
+            }
+            Ok(MenuCommand::CancelDeleteAccount) => {
+                bot.edit_message_text(
+                    telegram_id,
+                    message_id,
+                    t!(locale, "account-delete-cancelled"),
+                )
+                .await?;
+            }
+            Ok(MenuCommand::MonthlyStatementOn) | Ok(MenuCommand::MonthlyStatementOff) => {
+                let enabled = data == MenuCommand::MonthlyStatementOn.to_string();
+                let user = db_util::set_monthly_statement(&user, enabled)?;
+                bot.edit_message_text(telegram_id, message_id, settings_text(&user))
+                    .reply_markup(settings_keyboard(&user))
+                    .await?;
+            }
+            Ok(MenuCommand::Settings) => {
+                bot.edit_message_text(telegram_id, message_id, settings_text(&user))
+                    .reply_markup(settings_keyboard(&user))
+                    .await?;
+            }
             Err(_) => {
                 if data.starts_with(CALLBACK_SELECT_USER_PREFIX) {
                     handle_callback_for_selected_user(&data, &user, &bot, telegram_id, message_id)
-                        .await;
+                        .await?;
+                } else if let Some(args) = data.strip_prefix(CALLBACK_CONFIRM_AMOUNT_PREFIX) {
+                    // Контакт и направление берём из кнопки: с тех пор пользователь мог
+                    // выбрать в меню другого человека
+                    let (contact_id, direction) = args.split_once('_').ok_or("bad callback")?;
+                    let source = message
+                        .regular_message()
+                        .and_then(Message::reply_to_message);
+                    let Some(source) =
+                        source.filter(|_| user.status == InputtingStatus::TransactionAmount)
+                    else {
+                        // Сумма уже записана или пользователь ушёл в другое меню
+                        bot.edit_message_text(
+                            telegram_id,
+                            message_id,
+                            t!(locale, "amount-confirm-expired"),
+                        )
+                        .await?;
+                        return Ok(());
+                    };
+                    let contact = db_util::find_contact_user_by_id(&user, contact_id.parse()?)?;
+                    bot.edit_message_reply_markup(telegram_id, message_id)
+                        .await?;
+                    let (from, to) = if direction.parse::<i32>()? == 0 {
+                        (&user, &contact)
+                    } else {
+                        (&contact, &user)
+                    };
+                    create_transaction(&user, from, to, source, true, &bot).await;
+                } else if let Some(args) = data.strip_prefix(CALLBACK_CONFIRM_QUICK_ENTRY_PREFIX) {
+                    // Кнопку убираем сразу, чтобы повторное нажатие не записало долг дважды
+                    bot.edit_message_reply_markup(telegram_id, message_id)
+                        .await?;
+                    // Запись берём из кнопки, а не из исходной фразы: её могли отредактировать
+                    let mut args = args.splitn(3, '_');
+                    let (Some(contact_id), Some(direction), Some(amount)) =
+                        (args.next(), args.next(), args.next())
+                    else {
+                        return Err("bad callback".into());
+                    };
+                    let contact = db_util::find_contact_user_by_id(&user, contact_id.parse()?)?;
+                    let lent = direction.parse::<i32>()? == 0;
+                    let amount = BigDecimal::from_str(amount)?;
+                    // Комментарий в кнопку не помещается, поэтому фраза должна описывать
+                    // ту же запись, что показана в вопросе
+                    let entry = message
+                        .regular_message()
+                        .and_then(Message::reply_to_message)
+                        .and_then(Message::text)
+                        .and_then(|text| quick_entry::parse(text, locale))
+                        .filter(|entry| entry.lent == lent && entry.amount == amount);
+                    let entry = match entry {
+                        Some(entry) => match find_contact_by_word(&user, entry.contact)? {
+                            Ok(found) if found.id == contact.id => Some(entry),
+                            _ => None,
+                        },
+                        None => None,
+                    };
+                    let text = match entry {
+                        Some(entry) => {
+                            let (from, to) = if entry.lent {
+                                (&user, &contact)
+                            } else {
+                                (&contact, &user)
+                            };
+                            db_util::create_transaction(
+                                &user,
+                                from,
+                                to,
+                                entry.amount.clone(),
+                                entry.comment,
+                                None,
+                            );
+                            t!(
+                                locale,
+                                "quick-entry-saved",
+                                entry = describe_quick_entry(&user, &contact, &entry)
+                            )
+                        }
+                        None => t!(locale, "quick-entry-changed"),
+                    };
+                    bot.edit_message_text(telegram_id, message_id, text).await?;
+                } else if let Some(page) = data.strip_prefix(CALLBACK_CONTACTS_PAGE_PREFIX) {
+                    let (text, keyboard) = contacts_page(&user, page.parse()?)?;
+                    bot.edit_message_text(telegram_id, message_id, text)
+                        .reply_markup(keyboard)
+                        .await?;
+                } else if let Some(sort) = data.strip_prefix(CALLBACK_CONTACTS_SORT_PREFIX) {
+                    let user = db_util::set_contact_sort(&user, ContactSort::from_str(sort)?)?;
+                    let (text, keyboard) = contacts_page(&user, 0)?;
+                    bot.edit_message_text(telegram_id, message_id, text)
+                        .reply_markup(keyboard)
+                        .await?;
+                } else if let Some(id) = data.strip_prefix(CALLBACK_TRANSACTION_ATTACHMENT_PREFIX) {
+                    let transaction = db_util::find_transaction_for_user(&user, id.parse()?)?;
+                    match &transaction.attachment_file_id {
+                        Some(file_id) => {
+                            bot.send_photo(
+                                telegram_id,
+                                InputFile::file_id(FileId(file_id.clone())),
+                            )
+                            .caption(format_transaction_line(&user, &transaction))
+                            .await?;
+                        }
+                        None => {
+                            bot.send_message(telegram_id, t!(locale, "attachment-missing"))
+                                .await?;
+                        }
+                    }
+                } else if let Some(id) = data.strip_prefix(CALLBACK_TRANSACTION_TAGS_PREFIX) {
+                    let transaction = db_util::find_transaction_for_user(&user, id.parse()?)?;
+                    db_util::set_selected_transaction(&user, transaction.id)?;
+                    bot.edit_message_text(telegram_id, message_id, t!(locale, "tags-prompt"))
+                        .reply_markup(tags_keyboard(&user, transaction.id)?)
+                        .await?;
+                } else if let Some(id) = data.strip_prefix(CALLBACK_TRANSACTION_INTEREST_PREFIX) {
+                    let transaction = db_util::find_transaction_for_user(&user, id.parse()?)?;
+                    if transaction.from_user_id != user.id {
+                        return Err("only the lender sets interest".into());
+                    }
+                    db_util::set_selected_transaction(&user, transaction.id)?;
+                    set_user_status(&user, &InputtingStatus::TransactionInterest);
+                    let text = t!(locale, "interest-prompt");
+                    bot.edit_message_text(telegram_id, message_id, text).await?;
+                } else if let Some(id) = data.strip_prefix(CALLBACK_TRANSACTION_PREFIX) {
+                    let transaction_id = id.parse::<i32>()?;
+                    send_transaction_view(&bot, &user, transaction_id, telegram_id, message_id)
+                        .await?;
+                } else if data == CALLBACK_QUIET_HOURS_OFF {
+                    user = db_util::set_quiet_hours(&user, None)?;
+                    bot.edit_message_text(telegram_id, message_id, settings_text(&user))
+                        .reply_markup(settings_keyboard(&user))
+                        .await?;
+                } else if let Some(code) = data.strip_prefix(CALLBACK_LOCALE_PREFIX) {
+                    let locale = if data == CALLBACK_LOCALE_AUTO {
+                        None
+                    } else {
+                        Some(Locale::from_str(code)?)
+                    };
+                    user = db_util::set_locale(&user, locale)?;
+                    bot.edit_message_text(telegram_id, message_id, settings_text(&user))
+                        .reply_markup(settings_keyboard(&user))
+                        .await?;
+                } else if let Some(tag_id) = data.strip_prefix(CALLBACK_TOGGLE_TAG_PREFIX) {
+                    let transaction_id = user
+                        .selected_transaction_id
+                        .ok_or("no selected transaction")?;
+                    db_util::toggle_transaction_tag(&user, transaction_id, tag_id.parse()?)?;
+                    bot.edit_message_reply_markup(telegram_id, message_id)
+                        .reply_markup(tags_keyboard(&user, transaction_id)?)
+                        .await?;
+                } else if let Some(tag_id) = data.strip_prefix(CALLBACK_SUMMARY_TAG_PREFIX) {
+                    let tag = db_util::find_tag_for_user(&user, tag_id.parse()?)?;
+                    bot.edit_message_text(
+                        telegram_id,
+                        message_id,
+                        debit_summary_text(&user, Some(&tag))?,
+                    )
+                    .reply_markup(summary_keyboard(&user)?)
+                    .await?;
+                } else if let Some(page) = data.strip_prefix(CALLBACK_FIND_PAGE_PREFIX) {
+                    let (text, keyboard) = search_results_page(&user, page.parse()?)?;
+                    bot.edit_message_text(telegram_id, message_id, text)
+                        .reply_markup(keyboard)
+                        .await?;
+                } else if let Some(period) = data.strip_prefix(CALLBACK_STATS_PREFIX) {
+                    let period = StatsPeriod::from_str(period)?;
+                    bot.edit_message_text(
+                        telegram_id,
+                        message_id,
+                        stats::stats_text(&user, period)?,
+                    )
+                    .reply_markup(stats_keyboard(locale))
+                    .await?;
+                } else if let Some(hours) = data.strip_prefix(CALLBACK_QUIET_HOURS_PREFIX) {
+                    let (start, end) = hours.split_once('_').ok_or("bad quiet hours")?;
+                    user = db_util::set_quiet_hours(&user, Some((start.parse()?, end.parse()?)))?;
+                    bot.edit_message_text(telegram_id, message_id, settings_text(&user))
+                        .reply_markup(settings_keyboard(&user))
+                        .await?;
+                } else if let Some(frequency) =
+                    data.strip_prefix(CALLBACK_REMINDER_FREQUENCY_PREFIX)
+                {
+                    user = db_util::set_reminder_frequency(&user, frequency.parse()?)?;
+                    bot.edit_message_text(telegram_id, message_id, settings_text(&user))
+                        .reply_markup(settings_keyboard(&user))
+                        .await?;
+                } else if let Some(id) = data.strip_prefix(CALLBACK_MUTE_NUDGES_PREFIX) {
+                    let sender = db_util::get_user_by_id(id.parse()?)?;
+                    db_util::mute_nudges(&user, sender.id)?;
+                    let name = db_util::get_contact_display_name(&user, &sender);
+                    let keyboard =
+                        InlineKeyboardMarkup::new(vec![vec![InlineKeyboardButton::callback(
+                            t!(locale, "nudges-unmute-button"),
+                            format!("{CALLBACK_UNMUTE_NUDGES_PREFIX}{}", sender.id),
+                        )]]);
+                    bot.edit_message_text(
+                        telegram_id,
+                        message_id,
+                        t!(locale, "nudges-muted", name = name),
+                    )
+                    .reply_markup(keyboard)
+                    .await?;
+                } else if let Some(id) = data.strip_prefix(CALLBACK_UNMUTE_NUDGES_PREFIX) {
+                    let sender = db_util::get_user_by_id(id.parse()?)?;
+                    db_util::unmute_nudges(&user, sender.id)?;
+                    let name = db_util::get_contact_display_name(&user, &sender);
+                    bot.edit_message_text(
+                        telegram_id,
+                        message_id,
+                        t!(locale, "nudges-unmuted", name = name),
+                    )
+                    .await?;
+                } else if let Some(id) = data.strip_prefix(CALLBACK_RECURRING_PREFIX) {
+                    send_recurring_view(&bot, &user, id.parse()?, telegram_id, message_id).await?;
+                } else if let Some(id) = data.strip_prefix(CALLBACK_PAUSE_RECURRING_PREFIX) {
+                    let recurring =
+                        db_util::find_recurring_transaction_for_user(&user, id.parse()?)?;
+                    db_util::set_recurring_transaction_paused(
+                        &user,
+                        recurring.id,
+                        true,
+                        recurring.next_run_at,
+                    )?;
+                    send_recurring_view(&bot, &user, recurring.id, telegram_id, message_id).await?;
+                } else if let Some(id) = data.strip_prefix(CALLBACK_RESUME_RECURRING_PREFIX) {
+                    let recurring =
+                        db_util::find_recurring_transaction_for_user(&user, id.parse()?)?;
+                    let next_run_at = recurring
+                        .period
+                        .next_run_after(recurring.period_day, Utc::now());
+                    db_util::set_recurring_transaction_paused(
+                        &user,
+                        recurring.id,
+                        false,
+                        next_run_at,
+                    )?;
+                    send_recurring_view(&bot, &user, recurring.id, telegram_id, message_id).await?;
+                } else if let Some(id) = data.strip_prefix(CALLBACK_DELETE_RECURRING_PREFIX) {
+                    db_util::delete_recurring_transaction(&user, id.parse()?)?;
+                    send_recurring_list(&bot, &user, telegram_id, message_id).await?;
+                } else if let Some(id) = data.strip_prefix(CALLBACK_CONFIRM_IMPORT_PREFIX) {
+                    let batch_id = id.parse()?;
+                    match db_util::apply_import_batch(&user, batch_id) {
+                        Ok(count) => {
+                            let keyboard = InlineKeyboardMarkup::new(vec![vec![
+                                InlineKeyboardButton::callback(
+                                    t!(locale, "import-undo-button"),
+                                    format!("{CALLBACK_REVERT_IMPORT_PREFIX}{batch_id}"),
+                                ),
+                            ]]);
+                            let text = t!(locale, "import-applied", count = count);
+                            bot.edit_message_text(telegram_id, message_id, text)
+                                .reply_markup(keyboard)
+                                .await?;
+                        }
+                        Err(err) => {
+                            log::warn!("Error applying import {batch_id}: {:?}", err);
+                            bot.edit_message_text(
+                                telegram_id,
+                                message_id,
+                                t!(locale, "import-failed"),
+                            )
+                            .await?;
+                        }
+                    }
+                } else if let Some(id) = data.strip_prefix(CALLBACK_CANCEL_IMPORT_PREFIX) {
+                    db_util::discard_import_batch(&user, id.parse()?)?;
+                    bot.edit_message_text(telegram_id, message_id, t!(locale, "import-cancelled"))
+                        .await?;
+                } else if let Some(id) = data.strip_prefix(CALLBACK_REVERT_IMPORT_PREFIX) {
+                    let text = match db_util::revert_import_batch(&user, id.parse()?) {
+                        Ok(count) => t!(locale, "import-reverted", count = count),
+                        Err(_) => t!(locale, "import-revert-failed"),
+                    };
+                    bot.edit_message_text(telegram_id, message_id, text).await?;
+                } else if let Some(id) = data.strip_prefix(CALLBACK_DELETE_TRANSACTION_PREFIX) {
+                    let transaction_id = id.parse::<i32>()?;
+                    let text = match db_util::soft_delete_transaction(&user, transaction_id) {
+                        Ok(_) => t!(locale, "transaction-deleted"),
+                        Err(_) => t!(locale, "transaction-delete-failed"),
+                    };
+                    bot.edit_message_text(telegram_id, message_id, text).await?;
                 } else {
-                    let text = format!("Необработанное нажатие:\n\"{data}\"");
+                    let text = t!(locale, "unknown-callback", data = data.as_str());
                     bot.edit_message_text(telegram_id, message_id, text).await?;
                 }
             }
@@ -280,53 +1612,24 @@ async fn handle_callback(bot: Bot, callback: CallbackQuery) -> HandlerResult {
 }
 
 async fn handle_callback_for_selected_user(
-    data: &String,
+    data: &str,
     user: &User,
     bot: &Bot,
     telegram_id: UserId,
     message_id: MessageId,
-) {
-    let contact_name = data.replace(CALLBACK_SELECT_USER_PREFIX, "");
-    let contact =
-        db_util::find_user_by_contact_name(user, &contact_name).expect("ERROR executing Username");
+) -> HandlerResult {
+    let locale = user.locale();
+    let contact_id = data
+        .replace(CALLBACK_SELECT_USER_PREFIX, "")
+        .parse::<i32>()?;
+    let contact = db_util::find_contact_user_by_id(user, contact_id)?;
     db_util::set_selected_contact(user, contact.id).expect("ERROR executing Username");
     match user.status {
         InputtingStatus::EditContactInternalName => {
-            let text = "Пришли новое имя или вернись в /menu для отмены";
-            bot.edit_message_text(telegram_id, message_id, text)
-                .await
-                .expect("ERROR executing EditContact");
+            open_screen(bot, user, Screen::RenameContact, telegram_id, message_id).await?;
         }
         InputtingStatus::SelectContactForTransaction => {
-            let keyboard = InlineKeyboardMarkup::new(vec![
-                vec![
-                    InlineKeyboardButton::callback(
-                        "Взял в долг",
-                        MenuCommand::TransactionDirectionGave.to_string(),
-                    ),
-                    InlineKeyboardButton::callback(
-                        "Дал в долг",
-                        MenuCommand::TransactionDirectionTook.to_string(),
-                    ),
-                ],
-                vec![
-                    InlineKeyboardButton::callback(
-                        "История",
-                        MenuCommand::TransactionHistory.to_string(),
-                    ),
-                    InlineKeyboardButton::callback(
-                        "Расчитались",
-                        MenuCommand::TransactionSettledAccounts.to_string(),
-                    ),
-                ],
-            ]);
-            bot.edit_message_text(telegram_id, message_id, "Выбери:")
-                .await
-                .expect("ERROR executing SelectContactForTransaction");
-            bot.edit_message_reply_markup(telegram_id, message_id)
-                .reply_markup(keyboard)
-                .await
-                .expect("ERROR executing SelectContactForTransaction");
+            open_screen(bot, user, Screen::ContactActions, telegram_id, message_id).await?;
         }
         // InputtingStatus::SelectDirectionForTransaction => {
         //     set_user_status(user, &InputtingStatus::TransactionAmount);
@@ -336,19 +1639,206 @@ async fn handle_callback_for_selected_user(
         //         .expect("ERROR executing EditContact");
         // }
         _ => {
-            let text = format!("Необработанное нажатие:\n\"{data}\"");
+            let text = t!(locale, "unknown-callback", data = data);
             bot.edit_message_text(telegram_id, message_id, text)
                 .await
                 .expect("ERROR executing handle_callback_for_selected_user");
         }
     }
+    Ok(())
+}
+
+/// Открывает экран в том же сообщении поверх текущего: «Назад» вернёт туда, откуда пришли
+async fn open_screen(
+    bot: &Bot,
+    user: &User,
+    screen: Screen,
+    telegram_id: UserId,
+    message_id: MessageId,
+) -> HandlerResult {
+    let mut stack = user.screen_stack.clone();
+    // Вернулись на экран, который уже в стеке, — всё, что было открыто поверх, снимается.
+    // Так каждый экран лежит в стеке не больше одного раза и стек не растёт бесконечно
+    match stack.iter().position(|opened| *opened == screen) {
+        Some(position) => stack.truncate(position + 1),
+        None => stack.push(screen),
+    }
+    let user = db_util::set_screen_stack(user, &stack)?;
+    show_screen(bot, &user, screen, telegram_id, message_id).await
+}
+
+/// «Назад»: снимает текущий экран со стека и показывает предыдущий, а если его нет — меню
+async fn go_back(
+    bot: &Bot,
+    user: &User,
+    telegram_id: UserId,
+    message_id: MessageId,
+) -> HandlerResult {
+    let mut stack = user.screen_stack.clone();
+    stack.pop();
+    let screen = stack.last().copied().unwrap_or(Screen::Menu);
+    let user = db_util::set_screen_stack(user, &stack)?;
+    show_screen(bot, &user, screen, telegram_id, message_id).await
+}
+
+/// Рисует экран, редактируя сообщение, и ставит статус, в котором экран ждёт ввода.
+/// Все экраны, кроме меню, получают строку «Назад / Отмена»
+async fn show_screen(
+    bot: &Bot,
+    user: &User,
+    screen: Screen,
+    telegram_id: UserId,
+    message_id: MessageId,
+) -> HandlerResult {
+    let locale = user.locale();
+    let navigation = InlineKeyboardMarkup::new(vec![navigation_row(locale)]);
+    let (status, text, keyboard) = match screen {
+        Screen::Menu => (
+            InputtingStatus::None,
+            t!(locale, "menu-prompt"),
+            menu_keyboard(locale),
+        ),
+        Screen::SelectContact | Screen::EditContact => {
+            let (text, keyboard) = contacts_page(user, 0)?;
+            (contact_picker_status(user), text, keyboard)
+        }
+        Screen::ContactSearch => (
+            InputtingStatus::ContactSearch,
+            t!(locale, "contacts-search-prompt"),
+            navigation,
+        ),
+        Screen::ContactActions => (
+            InputtingStatus::SelectContactForTransaction,
+            t!(locale, "choose"),
+            contact_actions_keyboard(locale).append_row(navigation_row(locale)),
+        ),
+        Screen::TransactionAmount => (
+            InputtingStatus::TransactionAmount,
+            t!(locale, "amount-prompt"),
+            navigation,
+        ),
+        Screen::AddContact => (
+            InputtingStatus::NewContactTelegramUsername,
+            t!(locale, "new-contact-username-prompt"),
+            navigation,
+        ),
+        Screen::RenameContact => (
+            InputtingStatus::EditContactInternalName,
+            t!(locale, "edit-contact-prompt"),
+            navigation,
+        ),
+        Screen::Debts => (
+            InputtingStatus::None,
+            debit_summary_text(user, None)?,
+            summary_keyboard(user)?,
+        ),
+    };
+    set_user_status(user, &status);
+    bot.edit_message_text(telegram_id, message_id, text)
+        .reply_markup(keyboard)
+        .await?;
+    Ok(())
+}
+
+/// «Alice (+120)» — контакт должен, «Bob (−35)» — должен сам пользователь, без долгов — только имя
+fn contact_button_label(contact: &ContactListEntry, locale: Locale) -> String {
+    let amount = amount::format_short(&contact.balance.abs(), locale);
+    if contact.balance.is_positive() {
+        format!("{} (+{amount})", contact.name)
+    } else if contact.balance.is_negative() {
+        format!("{} (\u{2212}{amount})", contact.name)
+    } else {
+        contact.name.clone()
+    }
+}
+
+/// Выбор контакта открыт для записи долга или для переименования — по экрану в стеке
+fn contact_picker_status(user: &User) -> InputtingStatus {
+    match user.screen_stack.last() {
+        Some(Screen::EditContact) => InputtingStatus::EditContactInternalName,
+        _ => InputtingStatus::SelectContactForTransaction,
+    }
+}
+
+fn navigation_row(locale: Locale) -> Vec<InlineKeyboardButton> {
+    vec![
+        InlineKeyboardButton::callback(t!(locale, "back-button"), MenuCommand::Back.to_string()),
+        InlineKeyboardButton::callback(
+            t!(locale, "cancel-button"),
+            MenuCommand::Cancel.to_string(),
+        ),
+    ]
+}
+
+/// Действия с выбранным контактом: дал/взял, история, расчёт и остальное
+fn contact_actions_keyboard(locale: Locale) -> InlineKeyboardMarkup {
+    InlineKeyboardMarkup::new(vec![
+        vec![
+            InlineKeyboardButton::callback(
+                t!(locale, "gave-button"),
+                MenuCommand::TransactionDirectionGave.to_string(),
+            ),
+            InlineKeyboardButton::callback(
+                t!(locale, "took-button"),
+                MenuCommand::TransactionDirectionTook.to_string(),
+            ),
+        ],
+        vec![
+            InlineKeyboardButton::callback(
+                t!(locale, "history-button"),
+                MenuCommand::TransactionHistory.to_string(),
+            ),
+            InlineKeyboardButton::callback(
+                t!(locale, "settled-button"),
+                MenuCommand::TransactionSettledAccounts.to_string(),
+            ),
+        ],
+        vec![
+            InlineKeyboardButton::callback(
+                t!(locale, "nudge-button"),
+                MenuCommand::NudgeContact.to_string(),
+            ),
+            InlineKeyboardButton::callback(
+                t!(locale, "recurring-button"),
+                MenuCommand::RecurringTransaction.to_string(),
+            ),
+        ],
+        vec![InlineKeyboardButton::callback(
+            t!(locale, "statement-pdf-button"),
+            MenuCommand::TransactionStatementPdf.to_string(),
+        )],
+    ])
+}
+
+/// Запоминает язык клиента Telegram: по нему выбирается язык бота, пока его не выбрали в /settings
+fn remember_language_code(telegram_id: i64, from: Option<&teloxide::types::User>) {
+    let Some(code) = from.and_then(|from| from.language_code.as_deref()) else {
+        return;
+    };
+    if let Err(err) = db_util::set_language_code(telegram_id, code) {
+        log::warn!("Error saving language code: {:?}", err);
+    }
+}
+
+/// Пользователя нет в базе: он не нажимал /start или удалил свои данные
+async fn ask_to_start(
+    bot: &Bot,
+    chat_id: ChatId,
+    from: Option<&teloxide::types::User>,
+) -> HandlerResult {
+    let locale = from
+        .and_then(|from| from.language_code.as_deref())
+        .map_or(Locale::DEFAULT, Locale::from_language_code);
+    bot.send_message(chat_id, t!(locale, "start-required"))
+        .await?;
+    Ok(())
 }
 
 fn set_user_status(user: &User, new_status: &InputtingStatus) {
     db_util::set_user_status(user, new_status).expect("ERROR setting user status");
 }
 
-fn add_new_contact(user: &User, new_contact_name: &String) -> Result<User, Error> {
+fn add_new_contact(user: &User, new_contact_name: &str) -> Result<User, Error> {
     match db_util::get_user_by_username(new_contact_name) {
         Ok(contact) => {
             db_util::find_or_create_contact(user, &contact);
@@ -359,7 +1849,7 @@ fn add_new_contact(user: &User, new_contact_name: &String) -> Result<User, Error
     }
 }
 
-fn edit_contact(user: &User, contact: &User, contact_new_name: &String) -> Result<(), Error> {
+fn edit_contact(user: &User, contact: &User, contact_new_name: &str) -> Result<(), Error> {
     match db_util::edit_contact(user, contact, contact_new_name) {
         Ok(_) => {
             set_user_status(user, &InputtingStatus::None);
@@ -370,108 +1860,1152 @@ fn edit_contact(user: &User, contact: &User, contact_new_name: &String) -> Resul
 }
 
 fn get_contacts_names(user: &User) -> Vec<String> {
-    db_util::find_all_contacts_for_user(user)
+    db_util::find_named_contacts(user)
+        .expect("ERROR loading contacts")
+        .into_iter()
+        .map(|(contact, name)| name.unwrap_or(contact.telegram_username))
+        .collect::<Vec<_>>()
+}
+
+/// Сводка по контактам: основной долг и отдельно набежавшие проценты
+fn debit_summary_text(user: &User, tag: Option<&Tag>) -> Result<String, BoxError> {
+    let locale = user.locale();
+    let summary = db_util::get_debit(user, tag)?;
+    let header = tag
+        .map(|tag| t!(locale, "summary-tag-header", tag = tag.name.as_str()) + "\n\n")
+        .unwrap_or_default();
+    if summary.is_empty() {
+        return Ok(header + &t!(locale, "summary-empty"));
+    }
+    let today = Utc::now().date_naive();
+    let mut interest_by_name: HashMap<String, BigDecimal> = HashMap::new();
+    for debt in db_util::find_interest_bearing_debts(user, tag)? {
+        let name = debt.name.unwrap_or_else(|| "<unknown>".to_string());
+        let accrued_on = debt
+            .repaid_at
+            .map_or(today, |repaid_at| repaid_at.date_naive().min(today));
+        *interest_by_name.entry(name).or_default() += debt.transaction.accrued_interest(accrued_on);
+    }
+    let lines = summary
         .iter()
-        .map(|contact| {
-            contact.name.clone().unwrap_or_else(|| {
-                db_util::find_or_create_user(contact.id as i64, &String::new())
-                    .telegram_username
-                    .clone()
-            })
+        .map(|(name, amount)| match interest_by_name.get(name) {
+            Some(interest) if *interest > BigDecimal::from(0) => t!(
+                locale,
+                "summary-line-with-interest",
+                name = name.as_str(),
+                amount = amount::format_amount(amount, locale),
+                interest = amount::format_amount(interest, locale)
+            ),
+            _ => format!("{name}: {}", amount::format_amount(amount, locale)),
         })
         .collect::<Vec<_>>()
+        .join("\n");
+    Ok(format!("{header}{lines}"))
 }
 
-fn get_debit(user: &User) -> Option<Vec<(String, BigDecimal)>> {
-    match db_util::get_debit(user) {
-        Ok(el) => Some(el),
-        Err(_) => None,
+/// Фильтр сводки по тегам; пустая клавиатура, если тегов у пользователя нет
+fn summary_keyboard(user: &User) -> Result<InlineKeyboardMarkup, BoxError> {
+    let tags = db_util::find_tags_for_user(user)?;
+    let mut rows: Vec<Vec<InlineKeyboardButton>> = tags
+        .chunks(3)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .map(|tag| {
+                    InlineKeyboardButton::callback(
+                        format!("#{}", tag.name),
+                        format!("{CALLBACK_SUMMARY_TAG_PREFIX}{}", tag.id),
+                    )
+                })
+                .collect()
+        })
+        .collect();
+    if !tags.is_empty() {
+        rows.push(vec![
+            InlineKeyboardButton::callback(
+                t!(user.locale(), "summary-all-button"),
+                MenuCommand::Debts.to_string(),
+            ),
+            InlineKeyboardButton::callback(
+                t!(user.locale(), "summary-by-tag-button"),
+                MenuCommand::TagSummary.to_string(),
+            ),
+        ]);
     }
+    rows.push(navigation_row(user.locale()));
+    Ok(InlineKeyboardMarkup::new(rows))
 }
 
+/// Теги пользователя для транзакции: отмеченные помечены галочкой
+fn tags_keyboard(user: &User, transaction_id: i32) -> Result<InlineKeyboardMarkup, BoxError> {
+    let selected = db_util::find_transaction_tag_ids(user, transaction_id)?;
+    let tags = db_util::find_tags_for_user(user)?;
+    let mut rows: Vec<Vec<InlineKeyboardButton>> = tags
+        .chunks(3)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .map(|tag| {
+                    let label = if selected.contains(&tag.id) {
+                        format!("✓ {}", tag.name)
+                    } else {
+                        tag.name.clone()
+                    };
+                    InlineKeyboardButton::callback(
+                        label,
+                        format!("{CALLBACK_TOGGLE_TAG_PREFIX}{}", tag.id),
+                    )
+                })
+                .collect()
+        })
+        .collect();
+    rows.push(vec![InlineKeyboardButton::callback(
+        t!(user.locale(), "done"),
+        MenuCommand::TransactionTagsDone.to_string(),
+    )]);
+    Ok(InlineKeyboardMarkup::new(rows))
+}
+
+fn due_date_keyboard(locale: Locale) -> InlineKeyboardMarkup {
+    InlineKeyboardMarkup::new(vec![vec![InlineKeyboardButton::callback(
+        t!(locale, "no-due-date-button"),
+        MenuCommand::TransactionNoDueDate.to_string(),
+    )]])
+}
+
+/// Новое сообщение с меню. С него навигация начинается заново
 async fn send_menu(bot: &Bot, telegram_id: ChatId) {
-    let keyboard = InlineKeyboardMarkup::new(vec![
+    let user = db_util::get_user_by_telegram_id(telegram_id.0).unwrap();
+    let locale = user.locale();
+    let user = db_util::set_screen_stack(&user, &[]).expect("ERROR resetting screens");
+    set_user_status(&user, &InputtingStatus::None);
+    bot.send_message(telegram_id, t!(locale, "menu-prompt"))
+        .reply_markup(menu_keyboard(locale))
+        .await
+        .expect("ERROR creating menu");
+}
+
+fn menu_keyboard(locale: Locale) -> InlineKeyboardMarkup {
+    InlineKeyboardMarkup::new(vec![
         vec![
-            InlineKeyboardButton::callback("Долги", MenuCommand::SelectContact.to_string()),
-            InlineKeyboardButton::callback("Сводка", MenuCommand::Debts.to_string()),
+            InlineKeyboardButton::callback(
+                t!(locale, "menu-debts"),
+                MenuCommand::SelectContact.to_string(),
+            ),
+            InlineKeyboardButton::callback(
+                t!(locale, "menu-summary"),
+                MenuCommand::Debts.to_string(),
+            ),
         ],
         vec![
             InlineKeyboardButton::callback(
-                "Добавить контакт",
+                t!(locale, "menu-add-contact"),
                 MenuCommand::AddNewContact.to_string(),
             ),
             InlineKeyboardButton::callback(
-                "Редактировать контакт",
+                t!(locale, "menu-edit-contact"),
                 MenuCommand::EditContact.to_string(),
             ),
             InlineKeyboardButton::callback(
-                "Удалить контакт",
+                t!(locale, "menu-delete-contact"),
                 MenuCommand::DeleteContact.to_string(),
             ),
         ],
-    ]);
-    let user = db_util::get_user_by_telegram_id(telegram_id.0).unwrap();
-    set_user_status(&user, &InputtingStatus::None);
-    bot.send_message(telegram_id, "Выбери действие:")
-        .reply_markup(keyboard)
-        .await
-        .expect("ERROR creating menu");
+        vec![
+            InlineKeyboardButton::callback(
+                t!(locale, "menu-recurring"),
+                MenuCommand::RecurringList.to_string(),
+            ),
+            InlineKeyboardButton::callback(
+                t!(locale, "menu-settings"),
+                MenuCommand::Settings.to_string(),
+            ),
+        ],
+    ])
 }
 
-async fn send_contacts(
-    bot: &Bot,
-    user: &User,
-    telegram_id: UserId,
-    message_id: MessageId,
-) -> Result<(), Error> {
-    let mut current_line: Vec<InlineKeyboardButton> = vec![];
-    let mut lines: Vec<Vec<InlineKeyboardButton>> = vec![];
-    let mut buttons_in_line: i8 = 0;
-    for contact_name in get_contacts_names(user) {
-        let callback_data = format!("{CALLBACK_SELECT_USER_PREFIX}{}", &contact_name);
-        current_line.push(InlineKeyboardButton::callback(&contact_name, callback_data));
-        buttons_in_line += 1;
-        if buttons_in_line >= 3 {
-            lines.push(current_line.clone());
-            current_line.clear();
-            buttons_in_line = 0;
-        }
-    }
-    if !current_line.is_empty() {
-        lines.push(current_line.clone());
-    }
-    if !&lines.is_empty() {
-        bot.edit_message_text(telegram_id, message_id, "Выбери контакт:")
-            .await
-            .expect("ERROR executing send_contacts");
-        bot.edit_message_reply_markup(telegram_id, message_id)
-            .reply_markup(InlineKeyboardMarkup::new(lines))
-            .await
-            .expect("ERROR executing send_contacts");
+/// Страница выбора контакта: контакты, листание, порядок и поиск, когда контактов много.
+/// Без контактов предлагает сразу добавить первый
+fn contacts_page(user: &User, page: i64) -> Result<(String, InlineKeyboardMarkup), BoxError> {
+    let locale = user.locale();
+    // Номер страницы приходит из кнопки и может быть подделан или устареть
+    let page = page.max(0);
+    let search = user.contact_search.as_deref();
+    let (contacts, total) = db_util::find_contacts_page(
+        user,
+        user.contact_sort,
+        search,
+        page * CONTACTS_PAGE_SIZE,
+        CONTACTS_PAGE_SIZE,
+    )?;
+    let clear_search = InlineKeyboardButton::callback(
+        t!(locale, "contacts-search-clear-button"),
+        MenuCommand::ClearContactSearch.to_string(),
+    );
+    if total == 0 {
+        let (text, action) = match search {
+            Some(query) => (
+                t!(locale, "contacts-search-empty", query = query),
+                clear_search,
+            ),
+            None => (
+                t!(locale, "contacts-empty"),
+                InlineKeyboardButton::callback(
+                    t!(locale, "menu-add-contact"),
+                    MenuCommand::AddNewContact.to_string(),
+                ),
+            ),
+        };
+        let keyboard = InlineKeyboardMarkup::new(vec![vec![action], navigation_row(locale)]);
+        return Ok((text, keyboard));
     }
-    Ok(())
+    let mut rows: Vec<Vec<InlineKeyboardButton>> = contacts
+        .chunks(2)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .map(|contact| {
+                    InlineKeyboardButton::callback(
+                        contact_button_label(contact, locale),
+                        format!("{CALLBACK_SELECT_USER_PREFIX}{}", contact.contact_id),
+                    )
+                })
+                .collect()
+        })
+        .collect();
+    let pages = (total + CONTACTS_PAGE_SIZE - 1) / CONTACTS_PAGE_SIZE;
+    if page >= pages {
+        return contacts_page(user, pages - 1);
+    }
+    let mut paging = vec![];
+    if page > 0 {
+        paging.push(InlineKeyboardButton::callback(
+            t!(locale, "previous-page-button"),
+            format!("{CALLBACK_CONTACTS_PAGE_PREFIX}{}", page - 1),
+        ));
+    }
+    if page + 1 < pages {
+        paging.push(InlineKeyboardButton::callback(
+            t!(locale, "next-page-button"),
+            format!("{CALLBACK_CONTACTS_PAGE_PREFIX}{}", page + 1),
+        ));
+    }
+    if !paging.is_empty() {
+        rows.push(paging);
+    }
+    let (sort_label, other_sort) = match user.contact_sort {
+        ContactSort::Recent => (
+            t!(locale, "contacts-sort-balance-button"),
+            ContactSort::Balance,
+        ),
+        ContactSort::Balance => (
+            t!(locale, "contacts-sort-recent-button"),
+            ContactSort::Recent,
+        ),
+    };
+    let mut tools = vec![InlineKeyboardButton::callback(
+        sort_label,
+        format!("{CALLBACK_CONTACTS_SORT_PREFIX}{other_sort}"),
+    )];
+    if search.is_some() || total > CONTACTS_PAGE_SIZE {
+        tools.push(InlineKeyboardButton::callback(
+            t!(locale, "contacts-search-button"),
+            MenuCommand::SearchContacts.to_string(),
+        ));
+    }
+    if search.is_some() {
+        tools.push(clear_search);
+    }
+    rows.push(tools);
+    rows.push(navigation_row(locale));
+    let mut text = t!(locale, "choose-contact");
+    if let Some(query) = search {
+        text = format!(
+            "{text}\n{}",
+            t!(locale, "contacts-search-active", query = query)
+        );
+    }
+    if pages > 1 {
+        let page_info = t!(locale, "contacts-page", page = page + 1, pages = pages);
+        text = format!("{text}\n{page_info}");
+    }
+    Ok((text, InlineKeyboardMarkup::new(rows)))
 }
 
+/// Записывает долг из сообщения с суммой: текста вида «45,50 пицца» или фото чека
+/// с такой подписью. Сумму-выражение («120/3 пицца») сначала показывает посчитанной
+/// и записывает, только когда `confirmed`
 async fn create_transaction(
+    actor: &User,
     from: &User,
     to: &User,
-    msg_text: &str,
+    source: &Message,
+    confirmed: bool,
     bot: &Bot,
-    telegram_id: ChatId,
 ) -> Option<Transaction> {
-    match db_util::create_transaction(from, to, msg_text) {
-        Ok(transaction) => {
-            bot.send_message(telegram_id, "Готово")
+    let telegram_id = source.chat.id;
+    let locale = actor.locale();
+    let text = source.text().or(source.caption()).unwrap_or_default();
+    // Telegram присылает несколько размеров фото, последний — самый крупный
+    let attachment_file_id = source
+        .photo()
+        .and_then(|sizes| sizes.last())
+        .map(|photo| photo.file.id.0.as_str());
+    let parsed = match amount::parse_amount_input(text, locale) {
+        Ok(AmountInput::Computed {
+            expression, amount, ..
+        }) if !confirmed => {
+            let (contact, direction) = if from.id == actor.id {
+                (to, 0)
+            } else {
+                (from, 1)
+            };
+            let keyboard = InlineKeyboardMarkup::new(vec![vec![
+                InlineKeyboardButton::callback(
+                    t!(locale, "amount-confirm-button"),
+                    format!("{CALLBACK_CONFIRM_AMOUNT_PREFIX}{}_{direction}", contact.id),
+                ),
+                InlineKeyboardButton::callback(
+                    t!(locale, "cancel-button"),
+                    MenuCommand::CancelAmount.to_string(),
+                ),
+            ]]);
+            let text = t!(
+                locale,
+                "amount-computed",
+                expression = expression,
+                amount = amount::format_amount(&amount, locale)
+            );
+            // Ответом на исходное сообщение: при подтверждении сумма берётся из него
+            bot.send_message(telegram_id, text)
+                .reply_parameters(ReplyParameters::new(source.id))
+                .reply_markup(keyboard)
+                .await
+                .expect("ERROR execute TransactionAmount");
+            return None;
+        }
+        Ok(AmountInput::Exact { amount, comment })
+        | Ok(AmountInput::Computed {
+            amount, comment, ..
+        }) => Ok((amount, comment)),
+        Err(error) => Err(error),
+    };
+    match parsed {
+        Ok((amount, comment)) => {
+            let transaction =
+                db_util::create_transaction(actor, from, to, amount, comment, attachment_file_id);
+            db_util::set_selected_transaction(actor, transaction.id)
+                .expect("ERROR execute TransactionAmount");
+            set_user_status(actor, &InputtingStatus::TransactionTags);
+            let keyboard = tags_keyboard(actor, transaction.id).expect("ERROR loading tags");
+            let text = t!(locale, "transaction-saved") + " " + &t!(locale, "tags-prompt");
+            bot.send_message(telegram_id, text)
+                .reply_markup(keyboard)
                 .await
                 .expect("ERROR execute TransactionAmount");
-            send_menu(bot, telegram_id).await;
             Some(transaction)
         }
-        Err(_) => {
-            bot.send_message(telegram_id, "Ошибка парсинга суммы")
+        Err(error) => {
+            bot.send_message(telegram_id, error.message(locale))
                 .await
                 .expect("ERROR execute TransactionAmount");
             None
         }
     }
 }
+
+/// Контакт по имени или username из фразы быстрого ввода или аргумента команды. Точное совпадение имени или username важнее
+/// совпадения с учётом падежа. `Err` — текст ответа, если контакт не найден или их несколько
+fn find_contact_by_word(user: &User, word: &str) -> Result<Result<User, String>, BoxError> {
+    let locale = user.locale();
+    let mut contacts = db_util::find_named_contacts(user)?;
+    let names = |(contact, name): &(User, Option<String>)| {
+        [name.clone(), Some(contact.telegram_username.clone())]
+            .into_iter()
+            .flatten()
+            .filter(|name| !name.is_empty())
+            .collect::<Vec<_>>()
+    };
+    let username = word.trim_start_matches('@').to_lowercase();
+    let is_exact = |contact: &(User, Option<String>)| {
+        names(contact)
+            .iter()
+            .any(|name| name.to_lowercase() == username)
+    };
+    if contacts.iter().any(is_exact) {
+        contacts.retain(is_exact);
+    } else {
+        contacts.retain(|contact| {
+            names(contact)
+                .iter()
+                .any(|name| quick_entry::matches_name(word, name))
+        });
+    }
+    Ok(match contacts.len() {
+        1 => Ok(contacts.remove(0).0),
+        0 => Err(t!(locale, "quick-entry-contact-not-found", name = word)),
+        _ => Err(t!(
+            locale,
+            "quick-entry-contact-ambiguous",
+            name = word,
+            names = contacts
+                .iter()
+                .map(|(contact, _)| db_util::get_contact_display_name(user, contact))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+    })
+}
+
+/// «Alice owes you 120 zł» с точки зрения того, чей это `balance`
+fn balance_text(locale: Locale, name: &str, balance: &BigDecimal) -> String {
+    if balance.is_positive() {
+        t!(
+            locale,
+            "balance-owed-to-you",
+            name = name,
+            amount = amount::format_amount(balance, locale),
+            currency = currency()
+        )
+    } else if balance.is_negative() {
+        t!(
+            locale,
+            "balance-you-owe",
+            name = name,
+            amount = amount::format_amount(&balance.abs(), locale),
+            currency = currency()
+        )
+    } else {
+        t!(locale, "balance-settled", name = name)
+    }
+}
+
+/// «@alice owes @bob 120 zł» — баланс для чужих глаз, без личных имён
+fn shared_balance_text(user: &User, contact: &ContactListEntry) -> String {
+    let locale = user.locale();
+    let (debtor, creditor) = if contact.balance.is_positive() {
+        (&contact.telegram_username, &user.telegram_username)
+    } else if contact.balance.is_negative() {
+        (&user.telegram_username, &contact.telegram_username)
+    } else {
+        return t!(
+            locale,
+            "inline-balance-settled",
+            user = user.telegram_username.as_str(),
+            contact = contact.telegram_username.as_str()
+        );
+    };
+    t!(
+        locale,
+        "inline-balance-owes",
+        debtor = debtor.as_str(),
+        creditor = creditor.as_str(),
+        amount = amount::format_amount(&contact.balance.abs(), locale),
+        currency = currency()
+    )
+}
+
+/// «Петя: ты дал 50,00 — за пиццу»
+fn describe_quick_entry(user: &User, contact: &User, entry: &QuickEntry) -> String {
+    let locale = user.locale();
+    let name = db_util::get_contact_display_name(user, contact);
+    let amount = amount::format_amount(&entry.amount, locale);
+    let line = if entry.lent {
+        t!(locale, "quick-entry-lent", name = name, amount = amount)
+    } else {
+        t!(locale, "quick-entry-borrowed", name = name, amount = amount)
+    };
+    let comment = entry
+        .comment
+        .map(|comment| format!(" — {comment}"))
+        .unwrap_or_default();
+    format!("{line}{comment}")
+}
+
+/// Быстрый ввод записывается только после подтверждения. Контакт, направление и сумма
+/// уходят в кнопку, а вопрос — ответом на исходную фразу, из которой берётся комментарий
+async fn ask_quick_entry_confirmation(
+    bot: &Bot,
+    user: &User,
+    msg: &Message,
+    entry: &QuickEntry<'_>,
+) -> HandlerResult {
+    let locale = user.locale();
+    let contact = match find_contact_by_word(user, entry.contact)? {
+        Ok(contact) => contact,
+        Err(text) => {
+            bot.send_message(msg.chat.id, text).await?;
+            return Ok(());
+        }
+    };
+    let keyboard = InlineKeyboardMarkup::new(vec![vec![
+        InlineKeyboardButton::callback(
+            t!(locale, "amount-confirm-button"),
+            format!(
+                "{CALLBACK_CONFIRM_QUICK_ENTRY_PREFIX}{}_{}_{}",
+                contact.id,
+                if entry.lent { 0 } else { 1 },
+                entry.amount
+            ),
+        ),
+        InlineKeyboardButton::callback(
+            t!(locale, "cancel-button"),
+            MenuCommand::CancelQuickEntry.to_string(),
+        ),
+    ]]);
+    let text = t!(
+        locale,
+        "quick-entry-confirm",
+        entry = describe_quick_entry(user, &contact, entry)
+    );
+    bot.send_message(msg.chat.id, text)
+        .reply_parameters(ReplyParameters::new(msg.id))
+        .reply_markup(keyboard)
+        .await?;
+    Ok(())
+}
+
+/// Отправляет контакту вежливое напоминание о его долге; возвращает ответ для отправителя
+async fn send_nudge(bot: &Bot, user: &User, contact: &User) -> Result<String, BoxError> {
+    let locale = user.locale();
+    let balance = db_util::get_balance(user, contact)?;
+    if balance <= BigDecimal::from(0) {
+        return Ok(t!(locale, "nudge-nothing-owed"));
+    }
+    if db_util::is_nudge_muted(contact, user)? {
+        return Ok(t!(locale, "nudge-muted-by-contact"));
+    }
+    let Some(contact_telegram_id) = contact.telegram_id else {
+        return Ok(t!(locale, "error"));
+    };
+    // Напоминание читает контакт, поэтому оно на его языке
+    let contact_locale = contact.locale();
+    let sender_name = db_util::get_contact_display_name(contact, user);
+    let open_debts = db_util::find_open_debts(user, contact)?;
+    let mut open_items = open_debts
+        .iter()
+        .take(NUDGE_ITEMS_SHOWN)
+        .map(|(transaction, remaining)| {
+            let due_date = transaction
+                .due_date
+                .map(|date| {
+                    let date = date.format("%d.%m.%Y").to_string();
+                    format!(" ({})", t!(contact_locale, "nudge-due-date", date = date))
+                })
+                .unwrap_or_default();
+            format!(
+                "{} — {}{due_date}",
+                transaction.created_at.format("%d.%m.%Y"),
+                amount::format_amount(remaining, contact_locale)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    // Сообщение в Telegram не длиннее 4096 символов
+    if open_debts.len() > NUDGE_ITEMS_SHOWN {
+        open_items += "\n";
+        open_items += &t!(
+            contact_locale,
+            "nudge-more-items",
+            count = open_debts.len() - NUDGE_ITEMS_SHOWN
+        );
+    }
+    let text = t!(
+        contact_locale,
+        "nudge-text",
+        name = sender_name.as_str(),
+        balance = amount::format_amount(&balance, contact_locale),
+        items = open_items
+    );
+    let keyboard = InlineKeyboardMarkup::new(vec![vec![InlineKeyboardButton::callback(
+        t!(contact_locale, "nudge-mute-button", name = sender_name),
+        format!("{CALLBACK_MUTE_NUDGES_PREFIX}{}", user.id),
+    )]]);
+    let since = Utc::now() - chrono::Duration::hours(NUDGE_COOLDOWN_HOURS);
+    let nudge_id = match db_util::claim_nudge(user, contact, since)? {
+        Ok(nudge_id) => nudge_id,
+        Err(last_nudge_at) => {
+            let next_allowed = last_nudge_at + chrono::Duration::hours(NUDGE_COOLDOWN_HOURS);
+            return Ok(t!(
+                locale,
+                "nudge-cooldown",
+                date = next_allowed.format("%d.%m.%Y %H:%M").to_string()
+            ));
+        }
+    };
+    if let Err(err) = bot
+        .send_message(ChatId(contact_telegram_id), text)
+        .reply_markup(keyboard)
+        .await
+    {
+        db_util::release_nudge(nudge_id)?;
+        return Err(err.into());
+    }
+    Ok(t!(locale, "nudge-sent"))
+}
+
+async fn ask_recurring_input(
+    bot: &Bot,
+    user: &User,
+    direction: i32,
+    telegram_id: UserId,
+    message_id: MessageId,
+) -> HandlerResult {
+    db_util::set_selected_transaction_duration(user, direction)?;
+    set_user_status(user, &InputtingStatus::RecurringTransaction);
+    let text = t!(user.locale(), "recurring-prompt");
+    bot.edit_message_text(telegram_id, message_id, text).await?;
+    Ok(())
+}
+
+/// Разбирает «<сумма> <день> [комментарий]» в шаблон регулярного платежа с выбранным контактом
+fn parse_recurring_input(
+    user: &User,
+    contact: &User,
+    text: &str,
+) -> Option<NewRecurringTransaction> {
+    let mut parts = text.trim().splitn(3, char::is_whitespace);
+    let amount = amount::parse_amount(parts.next()?, user.locale()).ok()?;
+    let (period, period_day) = recurrence_period::parse_schedule(parts.next()?)?;
+    let comment = parts
+        .next()
+        .map(str::trim)
+        .filter(|comment| !comment.is_empty())
+        .map(str::to_string);
+    let (from, to) = if user.selected_transaction_duration == Some(0) {
+        (user, contact)
+    } else {
+        (contact, user)
+    };
+    Some(NewRecurringTransaction {
+        owner_id: user.id,
+        from_user_id: from.id,
+        to_user_id: to.id,
+        amount,
+        comment,
+        period,
+        period_day,
+        next_run_at: period.next_run_after(period_day, Utc::now()),
+    })
+}
+
+/// «Маша: даю 40 каждый месяц 5-го — Netflix» с точки зрения владельца шаблона
+fn describe_recurring(user: &User, recurring: &RecurringTransaction) -> String {
+    let locale = user.locale();
+    let other_id = if recurring.from_user_id == user.id {
+        recurring.to_user_id
+    } else {
+        recurring.from_user_id
+    };
+    let name = db_util::get_user_by_id(other_id)
+        .map(|other| db_util::get_contact_display_name(user, &other))
+        .unwrap_or_else(|_| "?".to_string());
+    let schedule = match recurring.period {
+        RecurrencePeriod::Weekly => t!(
+            locale,
+            "recurring-weekly",
+            weekday = recurring.period_day.clamp(1, 7)
+        ),
+        RecurrencePeriod::Monthly => t!(locale, "recurring-monthly", day = recurring.period_day),
+    };
+    let comment = recurring
+        .comment
+        .as_deref()
+        .map(|comment| format!(" — {comment}"))
+        .unwrap_or_default();
+    let paused = if recurring.paused {
+        format!(" ({})", t!(locale, "recurring-paused"))
+    } else {
+        String::new()
+    };
+    let amount = amount::format_amount(&recurring.amount, locale);
+    let line = if recurring.from_user_id == user.id {
+        t!(
+            locale,
+            "recurring-gives",
+            name = name,
+            amount = amount,
+            schedule = schedule
+        )
+    } else {
+        t!(
+            locale,
+            "recurring-takes",
+            name = name,
+            amount = amount,
+            schedule = schedule
+        )
+    };
+    format!("{line}{comment}{paused}")
+}
+
+async fn send_recurring_list(
+    bot: &Bot,
+    user: &User,
+    telegram_id: UserId,
+    message_id: MessageId,
+) -> HandlerResult {
+    let recurring = db_util::find_recurring_transactions_for_user(user)?;
+    if recurring.is_empty() {
+        let text = t!(user.locale(), "recurring-list-empty");
+        bot.edit_message_text(telegram_id, message_id, text).await?;
+        return Ok(());
+    }
+    let lines = recurring
+        .iter()
+        .map(|recurring| {
+            vec![InlineKeyboardButton::callback(
+                describe_recurring(user, recurring),
+                format!("{CALLBACK_RECURRING_PREFIX}{}", recurring.id),
+            )]
+        })
+        .collect::<Vec<_>>();
+    bot.edit_message_text(telegram_id, message_id, t!(user.locale(), "recurring-list"))
+        .reply_markup(InlineKeyboardMarkup::new(lines))
+        .await?;
+    Ok(())
+}
+
+async fn send_recurring_view(
+    bot: &Bot,
+    user: &User,
+    recurring_id: i32,
+    telegram_id: UserId,
+    message_id: MessageId,
+) -> HandlerResult {
+    let locale = user.locale();
+    let recurring = db_util::find_recurring_transaction_for_user(user, recurring_id)?;
+    let (text, toggle) = if recurring.paused {
+        (
+            describe_recurring(user, &recurring),
+            InlineKeyboardButton::callback(
+                t!(locale, "resume-button"),
+                format!("{CALLBACK_RESUME_RECURRING_PREFIX}{}", recurring.id),
+            ),
+        )
+    } else {
+        (
+            t!(
+                locale,
+                "recurring-view",
+                recurring = describe_recurring(user, &recurring),
+                next_run = recurring.next_run_at.format("%d.%m.%Y %H:%M").to_string()
+            ),
+            InlineKeyboardButton::callback(
+                t!(locale, "pause-button"),
+                format!("{CALLBACK_PAUSE_RECURRING_PREFIX}{}", recurring.id),
+            ),
+        )
+    };
+    let keyboard = InlineKeyboardMarkup::new(vec![vec![
+        toggle,
+        InlineKeyboardButton::callback(
+            t!(locale, "delete-button"),
+            format!("{CALLBACK_DELETE_RECURRING_PREFIX}{}", recurring.id),
+        ),
+    ]]);
+    bot.edit_message_text(telegram_id, message_id, text)
+        .reply_markup(keyboard)
+        .await?;
+    Ok(())
+}
+
+/// «5», «5 и 9», «3, 5 и 9»
+fn join_with_and(locale: Locale, items: &[String]) -> String {
+    match items {
+        [] => String::new(),
+        [single] => single.clone(),
+        [rest @ .., last] => t!(
+            locale,
+            "join-with-and",
+            rest = rest.join(", "),
+            last = last.as_str()
+        ),
+    }
+}
+
+fn parse_due_date(text: &str) -> Option<NaiveDate> {
+    ["%d.%m.%Y", "%Y-%m-%d"]
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(text.trim(), format).ok())
+}
+
+fn settings_text(user: &User) -> String {
+    let locale = user.locale();
+    let frequency = match user.reminder_frequency {
+        ReminderFrequency::Off => t!(locale, "settings-reminders-off"),
+        ReminderFrequency::Daily => t!(locale, "settings-reminders-daily"),
+        ReminderFrequency::Weekly => t!(locale, "settings-reminders-weekly"),
+    };
+    let quiet_hours = match (user.quiet_hours_start, user.quiet_hours_end) {
+        (Some(start), Some(end)) => format!("{start:02}:00–{end:02}:00 UTC"),
+        _ => t!(locale, "settings-quiet-hours-none"),
+    };
+    let statement = if user.monthly_statement {
+        t!(locale, "settings-statement-on")
+    } else {
+        t!(locale, "settings-statement-off")
+    };
+    let language = match user.locale {
+        Some(locale) => locale.native_name().to_string(),
+        None => t!(
+            locale,
+            "settings-language-auto",
+            language = locale.native_name()
+        ),
+    };
+    t!(
+        locale,
+        "settings-text",
+        frequency = frequency,
+        quiet_hours = quiet_hours,
+        statement = statement,
+        language = language
+    )
+}
+
+fn settings_keyboard(user: &User) -> InlineKeyboardMarkup {
+    let locale = user.locale();
+    let frequency_button = |label: String, frequency: ReminderFrequency| {
+        InlineKeyboardButton::callback(
+            label,
+            format!("{CALLBACK_REMINDER_FREQUENCY_PREFIX}{frequency}"),
+        )
+    };
+    let mut quiet_hours_line = vec![InlineKeyboardButton::callback(
+        t!(locale, "quiet-hours-off-button"),
+        CALLBACK_QUIET_HOURS_OFF,
+    )];
+    quiet_hours_line.extend(QUIET_HOURS_PRESETS.iter().map(|(start, end)| {
+        InlineKeyboardButton::callback(
+            format!("{start:02}–{end:02} UTC"),
+            format!("{CALLBACK_QUIET_HOURS_PREFIX}{start}_{end}"),
+        )
+    }));
+    let mut language_line = vec![InlineKeyboardButton::callback(
+        t!(locale, "language-auto-button"),
+        CALLBACK_LOCALE_AUTO,
+    )];
+    language_line.extend(Locale::ALL.iter().map(|locale| {
+        InlineKeyboardButton::callback(
+            locale.native_name(),
+            format!("{CALLBACK_LOCALE_PREFIX}{locale}"),
+        )
+    }));
+    InlineKeyboardMarkup::new(vec![
+        vec![
+            frequency_button(t!(locale, "reminders-off-button"), ReminderFrequency::Off),
+            frequency_button(
+                t!(locale, "reminders-daily-button"),
+                ReminderFrequency::Daily,
+            ),
+            frequency_button(
+                t!(locale, "reminders-weekly-button"),
+                ReminderFrequency::Weekly,
+            ),
+        ],
+        quiet_hours_line,
+        vec![
+            InlineKeyboardButton::callback(
+                t!(locale, "statement-on-button"),
+                MenuCommand::MonthlyStatementOn.to_string(),
+            ),
+            InlineKeyboardButton::callback(
+                t!(locale, "statement-off-button"),
+                MenuCommand::MonthlyStatementOff.to_string(),
+            ),
+        ],
+        language_line,
+    ])
+}
+
+/// Страница результатов последнего запроса `/find`: кнопки ведут в карточки транзакций
+fn search_results_page(user: &User, page: i64) -> Result<(String, InlineKeyboardMarkup), BoxError> {
+    let locale = user.locale();
+    // Номер страницы приходит из кнопки и может быть подделан или устареть
+    let page = page.max(0);
+    let query = SearchQuery::parse(user.search_query.as_deref().unwrap_or_default(), locale)?;
+    let contact = match &query.contact {
+        Some(name) => match db_util::find_contact_user(user, name) {
+            Ok(contact) => Some(contact),
+            Err(Error::NotFound) => {
+                return Ok((
+                    t!(locale, "find-contact-not-found", name = name.as_str()),
+                    InlineKeyboardMarkup::default(),
+                ))
+            }
+            Err(err) => return Err(err.into()),
+        },
+        None => None,
+    };
+    let (transactions, total) = db_util::search_transactions(
+        user,
+        contact.as_ref(),
+        &query,
+        page * SEARCH_PAGE_SIZE,
+        SEARCH_PAGE_SIZE,
+    )?;
+    if total == 0 {
+        return Ok((t!(locale, "find-nothing"), InlineKeyboardMarkup::default()));
+    }
+    let pages = (total + SEARCH_PAGE_SIZE - 1) / SEARCH_PAGE_SIZE;
+    if page >= pages {
+        return search_results_page(user, pages - 1);
+    }
+    let mut rows = Vec::with_capacity(transactions.len() + 1);
+    for transaction in &transactions {
+        let counterparty_id = if transaction.from_user_id == user.id {
+            transaction.to_user_id
+        } else {
+            transaction.from_user_id
+        };
+        let counterparty = db_util::get_user_by_id(counterparty_id)?;
+        let name = db_util::get_contact_display_name(user, &counterparty);
+        rows.push(vec![InlineKeyboardButton::callback(
+            format!("{name}: {}", format_transaction_line(user, transaction)),
+            format!("{CALLBACK_TRANSACTION_PREFIX}{}", transaction.id),
+        )]);
+    }
+    let mut navigation = vec![];
+    if page > 0 {
+        navigation.push(InlineKeyboardButton::callback(
+            t!(locale, "previous-page-button"),
+            format!("{CALLBACK_FIND_PAGE_PREFIX}{}", page - 1),
+        ));
+    }
+    if page + 1 < pages {
+        navigation.push(InlineKeyboardButton::callback(
+            t!(locale, "next-page-button"),
+            format!("{CALLBACK_FIND_PAGE_PREFIX}{}", page + 1),
+        ));
+    }
+    if !navigation.is_empty() {
+        rows.push(navigation);
+    }
+    let text = t!(
+        locale,
+        "find-results",
+        total = total,
+        page = page + 1,
+        pages = pages
+    );
+    Ok((text, InlineKeyboardMarkup::new(rows)))
+}
+
+fn stats_keyboard(locale: Locale) -> InlineKeyboardMarkup {
+    InlineKeyboardMarkup::new(vec![StatsPeriod::ALL
+        .iter()
+        .map(|period| {
+            InlineKeyboardButton::callback(
+                period.label(locale),
+                format!("{CALLBACK_STATS_PREFIX}{period}"),
+            )
+        })
+        .collect::<Vec<_>>()])
+}
+
+/// Строка транзакции с точки зрения `user`: дата, направление и сумма
+fn format_transaction_line(user: &User, transaction: &Transaction) -> String {
+    let direction = if transaction.from_user_id == user.id {
+        t!(user.locale(), "direction-gave")
+    } else {
+        t!(user.locale(), "direction-took")
+    };
+    let comment = transaction
+        .comment
+        .as_deref()
+        .map(|comment| format!(" — {comment}"))
+        .unwrap_or_default();
+    format!(
+        "{} {direction} {}{comment}",
+        transaction.created_at.format("%d.%m.%Y"),
+        amount::format_amount(&transaction.amount, user.locale())
+    )
+}
+
+async fn send_transaction_history(
+    bot: &Bot,
+    user: &User,
+    telegram_id: UserId,
+    message_id: MessageId,
+) -> HandlerResult {
+    let contact = db_util::get_selected_contact(user)?;
+    match transaction_history_keyboard(user, &contact)? {
+        Some(keyboard) => {
+            bot.edit_message_text(telegram_id, message_id, t!(user.locale(), "history"))
+                .reply_markup(keyboard)
+                .await?;
+        }
+        None => {
+            bot.edit_message_text(telegram_id, message_id, t!(user.locale(), "history-empty"))
+                .await?;
+        }
+    }
+    Ok(())
+}
+
+/// Последние записи с контактом кнопками; `None`, если записей нет
+fn transaction_history_keyboard(
+    user: &User,
+    contact: &User,
+) -> Result<Option<InlineKeyboardMarkup>, BoxError> {
+    let transactions = db_util::find_transactions_between(user, contact, HISTORY_LIMIT)?;
+    if transactions.is_empty() {
+        return Ok(None);
+    }
+    let lines = transactions
+        .iter()
+        .map(|transaction| {
+            let mut line = vec![InlineKeyboardButton::callback(
+                format_transaction_line(user, transaction),
+                format!("{CALLBACK_TRANSACTION_PREFIX}{}", transaction.id),
+            )];
+            if transaction.attachment_file_id.is_some() {
+                line.push(InlineKeyboardButton::callback(
+                    format!("📎 {}", t!(user.locale(), "receipt-button")),
+                    format!("{CALLBACK_TRANSACTION_ATTACHMENT_PREFIX}{}", transaction.id),
+                ));
+            }
+            line
+        })
+        .collect::<Vec<_>>();
+    Ok(Some(InlineKeyboardMarkup::new(lines)))
+}
+
+fn describe_interest_rule(locale: Locale, rule: &InterestRule) -> Option<String> {
+    match rule {
+        InterestRule::None => None,
+        InterestRule::Simple { annual_rate } => Some(t!(
+            locale,
+            "interest-simple",
+            rate = annual_rate.to_string()
+        )),
+        InterestRule::Compound { annual_rate } => Some(t!(
+            locale,
+            "interest-compound",
+            rate = annual_rate.to_string()
+        )),
+        InterestRule::LateFee { fee } => Some(t!(
+            locale,
+            "interest-late-fee",
+            fee = amount::format_amount(fee, locale)
+        )),
+    }
+}
+
+/// Запись закончена: то же сообщение становится меню, стек экранов начинается заново
+async fn finish_transaction_entry(
+    bot: &Bot,
+    user: &User,
+    telegram_id: UserId,
+    message_id: MessageId,
+) -> HandlerResult {
+    let locale = user.locale();
+    let user = db_util::set_screen_stack(user, &[])?;
+    set_user_status(&user, &InputtingStatus::None);
+    let text = format!("{}\n\n{}", t!(locale, "done"), t!(locale, "menu-prompt"));
+    bot.edit_message_text(telegram_id, message_id, text)
+        .reply_markup(menu_keyboard(locale))
+        .await?;
+    Ok(())
+}
+
+/// Карточка транзакции: детали, журнал изменений и кнопка удаления
+async fn send_transaction_view(
+    bot: &Bot,
+    user: &User,
+    transaction_id: i32,
+    telegram_id: UserId,
+    message_id: MessageId,
+) -> HandlerResult {
+    let locale = user.locale();
+    let transaction = db_util::find_transaction_for_user(user, transaction_id)?;
+    let audit = db_util::find_audit_entries("transactions", transaction.id)?
+        .iter()
+        .map(|(entry, actor)| {
+            let action = match entry.action {
+                AuditAction::Insert => t!(locale, "audit-insert"),
+                AuditAction::Update => t!(locale, "audit-update"),
+                AuditAction::Delete => t!(locale, "audit-delete"),
+            };
+            format!(
+                "{} @{}: {action}",
+                entry.created_at.format("%d.%m.%Y %H:%M"),
+                actor.as_deref().unwrap_or("?")
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    let due_date = transaction
+        .due_date
+        .map(|date| {
+            "\n".to_string()
+                + &t!(
+                    locale,
+                    "transaction-due-date",
+                    date = date.format("%d.%m.%Y").to_string()
+                )
+        })
+        .unwrap_or_default();
+    let interest = describe_interest_rule(locale, &transaction.interest_rule())
+        .map(|condition| {
+            "\n".to_string()
+                + &t!(
+                    locale,
+                    "transaction-interest",
+                    principal = amount::format_amount(&transaction.amount, locale),
+                    condition = condition,
+                    interest = amount::format_amount(
+                        &transaction.accrued_interest(Utc::now().date_naive()),
+                        locale
+                    )
+                )
+        })
+        .unwrap_or_default();
+    let tag_ids = db_util::find_transaction_tag_ids(user, transaction.id)?;
+    let tags = db_util::find_tags_for_user(user)?
+        .into_iter()
+        .filter(|tag| tag_ids.contains(&tag.id))
+        .map(|tag| format!("#{}", tag.name))
+        .collect::<Vec<_>>();
+    let tags = if tags.is_empty() {
+        String::new()
+    } else {
+        "\n".to_string() + &t!(locale, "transaction-tags", tags = tags.join(" "))
+    };
+    let text = format!(
+        "{}{due_date}{interest}{tags}\n\n{}\n{audit}",
+        format_transaction_line(user, &transaction),
+        t!(locale, "transaction-audit")
+    );
+    let mut rows = vec![];
+    if transaction.deleted_at.is_none() {
+        let mut row = vec![];
+        // Проценты назначает только кредитор
+        if transaction.from_user_id == user.id {
+            row.push(InlineKeyboardButton::callback(
+                t!(locale, "interest-button"),
+                format!("{CALLBACK_TRANSACTION_INTEREST_PREFIX}{}", transaction.id),
+            ));
+        }
+        row.push(InlineKeyboardButton::callback(
+            t!(locale, "tags-button"),
+            format!("{CALLBACK_TRANSACTION_TAGS_PREFIX}{}", transaction.id),
+        ));
+        row.push(InlineKeyboardButton::callback(
+            t!(locale, "delete-button"),
+            format!("{CALLBACK_DELETE_TRANSACTION_PREFIX}{}", transaction.id),
+        ));
+        rows.push(row);
+    }
+    if transaction.attachment_file_id.is_some() {
+        rows.push(vec![InlineKeyboardButton::callback(
+            t!(locale, "receipt-button"),
+            format!("{CALLBACK_TRANSACTION_ATTACHMENT_PREFIX}{}", transaction.id),
+        )]);
+    }
+    bot.edit_message_text(telegram_id, message_id, text)
+        .reply_markup(InlineKeyboardMarkup::new(rows))
+        .await?;
+    Ok(())
+}